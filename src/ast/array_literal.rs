@@ -0,0 +1,53 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ArrayLiteral {
+    token: Token,
+    elements: Vec<Expressions>,
+}
+
+impl ArrayLiteral {
+    pub fn new(token: Token, elements: Vec<Expressions>) -> Self {
+        Self { token, elements }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn elements(&self) -> &[Expressions] {
+        &self.elements
+    }
+
+    pub fn elements_mut(&mut self) -> &mut [Expressions] {
+        &mut self.elements
+    }
+}
+
+impl Expression for ArrayLiteral {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for ArrayLiteral {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for ArrayLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, element) in self.elements.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", element)?;
+        }
+        write!(f, "]")
+    }
+}
@@ -1,5 +1,5 @@
-use super::Expressions;
-use crate::ast::{Expression, Node, Token};
+use super::{Expressions, Identifier};
+use crate::ast::{Expression, Node, Position, Token};
 use std::fmt::Display;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
@@ -7,6 +7,9 @@ pub struct CallExpression {
     token: Token,
     function: Expressions,
     arguments: Vec<Expressions>,
+    named: Vec<(Identifier, Expressions)>,
+    // Where the argument list opens
+    position: Position,
 }
 
 impl CallExpression {
@@ -15,8 +18,25 @@ impl CallExpression {
             token,
             function,
             arguments,
+            named: Vec::new(),
+            position: Position::default(),
         }
     }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    // `name: value` arguments, always written after the positional ones
+    pub fn with_named(mut self, named: Vec<(Identifier, Expressions)>) -> Self {
+        self.named = named;
+        self
+    }
     pub fn token(&self) -> &Token {
         &self.token
     }
@@ -36,6 +56,14 @@ impl CallExpression {
     pub fn arguments_mut(&mut self) -> &mut [Expressions] {
         &mut self.arguments
     }
+
+    pub fn named(&self) -> &[(Identifier, Expressions)] {
+        &self.named
+    }
+
+    pub fn named_mut(&mut self) -> &mut [(Identifier, Expressions)] {
+        &mut self.named
+    }
 }
 
 impl Expression for CallExpression {
@@ -62,16 +90,26 @@ impl Display for CallExpression {
             Expressions::PrefixExpression(x) => String::from(x.token_literal()),
             Expressions::CallExpression(x) => String::from(x.token_literal()),
             Expressions::StringLiteral(x) => String::from(x.token_literal()),
+            Expressions::TryExpression(x) => String::from(x.token_literal()),
+            Expressions::FieldAccess(x) => x.to_string(),
+            Expressions::StructLiteral(x) => String::from(x.token_literal()),
+            Expressions::SpreadExpression(x) => String::from(x.token_literal()),
+            Expressions::MatchExpression(x) => String::from(x.token_literal()),
+            Expressions::HashLiteral(x) => String::from(x.token_literal()),
+            Expressions::InterpolatedString(x) => String::from(x.token_literal()),
+            Expressions::ArrayLiteral(x) => String::from(x.token_literal()),
+            Expressions::CharLiteral(x) => String::from(x.token_literal()),
+            Expressions::IndexExpression(x) => String::from(x.token_literal()),
+            Expressions::FloatLiteral(x) => String::from(x.token_literal()),
             Expressions::FunctionLiteral(x) => String::from(x.token_literal()),
         };
         write!(f, "{}(", context)?;
-        let n = self.arguments.len();
-        for (i, param) in self.arguments().iter().enumerate() {
-            write!(f, "{}", param)?;
-            if i < n - 1 {
-                write!(f, ", ")?;
-            }
-        }
-        write!(f, ")")
+        let mut args: Vec<String> = self.arguments.iter().map(|a| a.to_string()).collect();
+        args.extend(
+            self.named
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, value)),
+        );
+        write!(f, "{})", args.join(", "))
     }
 }
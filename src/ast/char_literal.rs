@@ -0,0 +1,50 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct CharLiteral {
+    token: Token,
+    value: char,
+}
+
+impl CharLiteral {
+    pub fn new(token: Token) -> Self {
+        match token {
+            Token::Char(_) => {
+                let value = token
+                    .literal()
+                    .chars()
+                    .next()
+                    .expect("character literal was empty");
+                Self { token, value }
+            }
+            _ => panic!("Tried to make a char literal with {}", token),
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn value(&self) -> char {
+        self.value
+    }
+}
+
+impl Display for CharLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.value)
+    }
+}
+
+impl Expression for CharLiteral {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for CharLiteral {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
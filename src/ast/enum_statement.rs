@@ -0,0 +1,71 @@
+use crate::ast::{Identifier, Node, Statement, Token};
+use std::fmt::Display;
+
+// `enum Shape { Circle(r), Rect(w, h), Empty }`
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct EnumStatement {
+    token: Token,
+    name: Identifier,
+    variants: Vec<(Identifier, Vec<Identifier>)>,
+}
+
+impl EnumStatement {
+    pub fn new(
+        token: Token,
+        name: Identifier,
+        variants: Vec<(Identifier, Vec<Identifier>)>,
+    ) -> Self {
+        Self {
+            token,
+            name,
+            variants,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn variants(&self) -> &[(Identifier, Vec<Identifier>)] {
+        &self.variants
+    }
+}
+
+impl Display for EnumStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let variants: Vec<String> = self
+            .variants
+            .iter()
+            .map(|(name, fields)| {
+                if fields.is_empty() {
+                    return name.to_string();
+                }
+                let fields: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+                format!("{}({})", name, fields.join(", "))
+            })
+            .collect();
+        write!(
+            f,
+            "{} {} {{ {} }}",
+            self.token_literal(),
+            self.name,
+            variants.join(", ")
+        )
+    }
+}
+
+impl Statement for EnumStatement {
+    fn statement_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for EnumStatement {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
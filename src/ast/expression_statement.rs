@@ -21,6 +21,17 @@ impl ExpressionStatement {
             Expressions::CallExpression(x) => x.token(),
             Expressions::FunctionLiteral(x) => x.token(),
             Expressions::StringLiteral(x) => x.token(),
+            Expressions::TryExpression(x) => x.token(),
+            Expressions::FieldAccess(x) => x.token(),
+            Expressions::StructLiteral(x) => x.token(),
+            Expressions::SpreadExpression(x) => x.token(),
+            Expressions::MatchExpression(x) => x.token(),
+            Expressions::HashLiteral(x) => x.token(),
+            Expressions::InterpolatedString(x) => x.token(),
+            Expressions::ArrayLiteral(x) => x.token(),
+            Expressions::CharLiteral(x) => x.token(),
+            Expressions::IndexExpression(x) => x.token(),
+            Expressions::FloatLiteral(x) => x.token(),
             Expressions::Empty => panic!("Token was empty"),
         }
     }
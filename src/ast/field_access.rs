@@ -0,0 +1,55 @@
+use crate::ast::{Expression, Identifier, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct FieldAccess {
+    token: Token,
+    object: Expressions,
+    field: Identifier,
+}
+
+impl FieldAccess {
+    pub fn new(token: Token, object: Expressions, field: Identifier) -> Self {
+        Self {
+            token,
+            object,
+            field,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn object(&self) -> &Expressions {
+        &self.object
+    }
+
+    pub fn object_mut(&mut self) -> &mut Expressions {
+        &mut self.object
+    }
+
+    pub fn field(&self) -> &Identifier {
+        &self.field
+    }
+}
+
+impl Expression for FieldAccess {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for FieldAccess {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for FieldAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}.{})", self.object, self.field)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct FloatLiteral {
+    token: Token,
+    value: f64,
+}
+
+impl FloatLiteral {
+    pub fn new(token: Token) -> Self {
+        match token {
+            Token::Float(_) => {
+                let value = token
+                    .literal()
+                    .replace('_', "")
+                    .parse::<f64>()
+                    .expect("value was not an f64");
+                Self { token, value }
+            }
+            _ => panic!("Tried to make a float literal with {}", token),
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+impl Display for FloatLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.token_literal())?;
+        Ok(())
+    }
+}
+
+impl Expression for FloatLiteral {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for FloatLiteral {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
@@ -1,22 +1,45 @@
-use super::Identifier;
-use crate::ast::{BlockStatement, Expression, Node, Token};
+use super::Parameter;
+use crate::ast::{BlockStatement, Expression, Node, Position, Token, TypeAnnotation};
 use std::fmt::Display;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct FunctionLiteral {
     token: Token,
-    parameters: Vec<Identifier>,
+    parameters: Vec<Parameter>,
     body: BlockStatement,
+    return_type: Option<TypeAnnotation>,
+    position: Position,
 }
 
 impl FunctionLiteral {
-    pub fn new(token: Token, parameters: Vec<Identifier>, body: BlockStatement) -> Self {
+    pub fn new(token: Token, parameters: Vec<Parameter>, body: BlockStatement) -> Self {
         Self {
             token,
             parameters,
             body,
+            return_type: None,
+            position: Position::default(),
         }
     }
+
+    pub fn with_return_type(mut self, return_type: Option<TypeAnnotation>) -> Self {
+        self.return_type = return_type;
+        self
+    }
+
+    pub fn return_type(&self) -> Option<&TypeAnnotation> {
+        self.return_type.as_ref()
+    }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
     pub fn token(&self) -> &Token {
         &self.token
     }
@@ -28,7 +51,7 @@ impl FunctionLiteral {
         &mut self.body
     }
 
-    pub fn parameters(&self) -> &[Identifier] {
+    pub fn parameters(&self) -> &[Parameter] {
         self.parameters.as_ref()
     }
 }
@@ -55,7 +78,11 @@ impl Display for FunctionLiteral {
                 write!(f, ", ")?;
             }
         }
-        write!(f, ") {{ {} ", self.body)?;
+        write!(f, ")")?;
+        if let Some(return_type) = &self.return_type {
+            write!(f, " -> {}", return_type)?;
+        }
+        write!(f, " {{ {} ", self.body)?;
         write!(f, "}}")
     }
 }
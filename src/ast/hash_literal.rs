@@ -0,0 +1,53 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct HashLiteral {
+    token: Token,
+    pairs: Vec<(Expressions, Expressions)>,
+}
+
+impl HashLiteral {
+    pub fn new(token: Token, pairs: Vec<(Expressions, Expressions)>) -> Self {
+        Self { token, pairs }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn pairs(&self) -> &[(Expressions, Expressions)] {
+        &self.pairs
+    }
+
+    pub fn pairs_mut(&mut self) -> &mut [(Expressions, Expressions)] {
+        &mut self.pairs
+    }
+}
+
+impl Expression for HashLiteral {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for HashLiteral {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for HashLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", key, value)?;
+        }
+        write!(f, "}}")
+    }
+}
@@ -0,0 +1,73 @@
+use crate::ast::{FunctionLiteral, Identifier, Node, Statement, Token};
+use std::fmt::Display;
+
+// `impl Point { fn area(self) { ... } }`
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ImplStatement {
+    token: Token,
+    name: Identifier,
+    methods: Vec<(Identifier, FunctionLiteral)>,
+}
+
+impl ImplStatement {
+    pub fn new(
+        token: Token,
+        name: Identifier,
+        methods: Vec<(Identifier, FunctionLiteral)>,
+    ) -> Self {
+        Self {
+            token,
+            name,
+            methods,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn methods(&self) -> &[(Identifier, FunctionLiteral)] {
+        &self.methods
+    }
+
+    pub fn methods_mut(&mut self) -> &mut [(Identifier, FunctionLiteral)] {
+        &mut self.methods
+    }
+}
+
+impl Display for ImplStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {{ ", self.token_literal(), self.name)?;
+        for (name, method) in &self.methods {
+            let params: Vec<String> = method.parameters().iter().map(|p| p.to_string()).collect();
+            write!(
+                f,
+                "{} {}({})",
+                method.token_literal(),
+                name,
+                params.join(", ")
+            )?;
+            if let Some(return_type) = method.return_type() {
+                write!(f, " -> {}", return_type)?;
+            }
+            write!(f, " {{ {} }} ", method.body())?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Statement for ImplStatement {
+    fn statement_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for ImplStatement {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
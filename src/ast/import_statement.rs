@@ -0,0 +1,52 @@
+use crate::ast::{Identifier, Node, Statement, Token};
+use std::fmt::Display;
+
+// `import "path/to/mod.monkey" as m;`
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ImportStatement {
+    token: Token,
+    path: String,
+    alias: Identifier,
+}
+
+impl ImportStatement {
+    pub fn new(token: Token, path: String, alias: Identifier) -> Self {
+        Self { token, path, alias }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn alias(&self) -> &Identifier {
+        &self.alias
+    }
+}
+
+impl Display for ImportStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:?} as {};",
+            self.token_literal(),
+            self.path,
+            self.alias
+        )
+    }
+}
+
+impl Statement for ImportStatement {
+    fn statement_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for ImportStatement {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
@@ -0,0 +1,55 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct IndexExpression {
+    token: Token,
+    left: Expressions,
+    index: Expressions,
+}
+
+impl IndexExpression {
+    pub fn new(token: Token, left: Expressions, index: Expressions) -> Self {
+        Self { token, left, index }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn left(&self) -> &Expressions {
+        &self.left
+    }
+
+    pub fn left_mut(&mut self) -> &mut Expressions {
+        &mut self.left
+    }
+
+    pub fn index(&self) -> &Expressions {
+        &self.index
+    }
+
+    pub fn index_mut(&mut self) -> &mut Expressions {
+        &mut self.index
+    }
+}
+
+impl Expression for IndexExpression {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for IndexExpression {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for IndexExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}[{}])", self.left, self.index)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::ast::{Expression, Node, Token};
+use crate::ast::{Expression, Node, Position, Token};
 use std::fmt::Display;
 
 use super::Expressions;
@@ -8,6 +8,8 @@ pub struct InfixExpression {
     operator: String,
     expression_left: Expressions,
     expression_right: Expressions,
+    // Where the operator is
+    position: Position,
 }
 
 impl InfixExpression {
@@ -20,8 +22,18 @@ impl InfixExpression {
             operator,
             expression_left,
             expression_right,
+            position: Position::default(),
         }
     }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
     pub fn token(&self) -> &Token {
         match &self.expression_left {
             Expressions::Identifier(x) => x.token(),
@@ -33,6 +45,17 @@ impl InfixExpression {
             Expressions::CallExpression(x) => x.token(),
             Expressions::FunctionLiteral(x) => x.token(),
             Expressions::StringLiteral(x) => x.token(),
+            Expressions::TryExpression(x) => x.token(),
+            Expressions::FieldAccess(x) => x.token(),
+            Expressions::StructLiteral(x) => x.token(),
+            Expressions::SpreadExpression(x) => x.token(),
+            Expressions::MatchExpression(x) => x.token(),
+            Expressions::HashLiteral(x) => x.token(),
+            Expressions::InterpolatedString(x) => x.token(),
+            Expressions::ArrayLiteral(x) => x.token(),
+            Expressions::CharLiteral(x) => x.token(),
+            Expressions::IndexExpression(x) => x.token(),
+            Expressions::FloatLiteral(x) => x.token(),
             Expressions::Empty => panic!("Tried to get token from empty expression"),
         }
     }
@@ -76,6 +99,17 @@ impl Node for InfixExpression {
             Expressions::CallExpression(x) => x.token_literal(),
             Expressions::FunctionLiteral(x) => x.token_literal(),
             Expressions::StringLiteral(x) => x.token_literal(),
+            Expressions::TryExpression(x) => x.token_literal(),
+            Expressions::FieldAccess(x) => x.token_literal(),
+            Expressions::StructLiteral(x) => x.token_literal(),
+            Expressions::SpreadExpression(x) => x.token_literal(),
+            Expressions::MatchExpression(x) => x.token_literal(),
+            Expressions::HashLiteral(x) => x.token_literal(),
+            Expressions::InterpolatedString(x) => x.token_literal(),
+            Expressions::ArrayLiteral(x) => x.token_literal(),
+            Expressions::CharLiteral(x) => x.token_literal(),
+            Expressions::IndexExpression(x) => x.token_literal(),
+            Expressions::FloatLiteral(x) => x.token_literal(),
             Expressions::Empty => panic!("Tried to print token of empty expression"),
         }
     }
@@ -1,19 +1,28 @@
 use crate::ast::{Expression, Node, Token};
+use crate::bigint::BigInt;
 use std::fmt::Display;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct IntegerLiteral {
     token: Token,
-    value: isize
+    value: Option<isize>,
+    big_value: Option<BigInt>,
 }
 
 impl IntegerLiteral {
     pub fn new(token: Token) -> Self {
         match token {
+            // Literals too large for an isize are evaluated as a BigInt
             Token::Integer(_) => {
-                let value = token.literal().parse::<isize>().expect("value was not an isize");
-                Self { token, value}
-            },
+                let parsed = parse_integer(token.literal());
+                let value = parsed.as_ref().and_then(|v| v.to_isize());
+                let big_value = parsed.filter(|_| value.is_none());
+                Self {
+                    token,
+                    value,
+                    big_value,
+                }
+            }
             _ => panic!("Tried to make an integer literal with {}", token),
         }
     }
@@ -22,14 +31,38 @@ impl IntegerLiteral {
         &self.token
     }
 
-    pub fn value(&self) -> isize {
+    pub fn value(&self) -> Option<isize> {
         self.value
     }
+
+    pub fn big_value(&self) -> BigInt {
+        match (&self.big_value, self.value) {
+            (Some(big), _) => big.clone(),
+            (None, value) => BigInt::from_isize(value.unwrap_or(0)),
+        }
+    }
+
+    // The lexer only produces well formed literals, this catches hand built tokens
+    pub fn is_valid(&self) -> bool {
+        self.value.is_some() || self.big_value.is_some()
+    }
+}
+
+// Handles the 0x, 0o and 0b prefixes and `_` digit separators
+fn parse_integer(literal: &str) -> Option<BigInt> {
+    let digits = literal.replace('_', "");
+    match digits.get(..2) {
+        Some("0x") => BigInt::parse_radix(&digits[2..], 16),
+        Some("0o") => BigInt::parse_radix(&digits[2..], 8),
+        Some("0b") => BigInt::parse_radix(&digits[2..], 2),
+        _ if digits.starts_with('-') => None,
+        _ => BigInt::parse(&digits),
+    }
 }
 
 impl Display for IntegerLiteral {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.value())?;
+        write!(f, "{}", self.token_literal())?;
         Ok(())
     }
 }
@@ -0,0 +1,58 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum InterpolationPart {
+    Text(String),
+    Expression(Expressions),
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct InterpolatedString {
+    token: Token,
+    parts: Vec<InterpolationPart>,
+}
+
+impl InterpolatedString {
+    pub fn new(token: Token, parts: Vec<InterpolationPart>) -> Self {
+        Self { token, parts }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn parts(&self) -> &[InterpolationPart] {
+        &self.parts
+    }
+
+    pub fn parts_mut(&mut self) -> &mut [InterpolationPart] {
+        &mut self.parts
+    }
+}
+
+impl Expression for InterpolatedString {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for InterpolatedString {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for InterpolatedString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in &self.parts {
+            match part {
+                InterpolationPart::Text(text) => write!(f, "{}", text)?,
+                InterpolationPart::Expression(expression) => write!(f, "${{{}}}", expression)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
-use crate::ast::{identifier::Identifier, Node, Statement, Token};
+use crate::ast::{
+    identifier::Identifier, Node, Pattern, Position, Statement, Token, TypeAnnotation,
+};
 use std::fmt::Display;
 
 use super::Expressions;
@@ -6,20 +8,81 @@ use super::Expressions;
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct LetStatement {
     token: Token,
-    name: Identifier,
+    pattern: Pattern,
     value: Expressions,
+    docs: Vec<String>,
+    exported: bool,
+    annotation: Option<TypeAnnotation>,
+    position: Position,
 }
 
 impl LetStatement {
-    pub fn new(token: Token, name: Identifier, value: Expressions) -> Self {
-        LetStatement { token, name, value }
+    pub fn new(token: Token, pattern: Pattern, value: Expressions) -> Self {
+        LetStatement {
+            token,
+            pattern,
+            value,
+            docs: Vec::new(),
+            exported: false,
+            annotation: None,
+            position: Position::default(),
+        }
     }
+
+    // `let x: int = ...`, checked by the typecheck pass and ignored when evaluating
+    pub fn with_annotation(mut self, annotation: Option<TypeAnnotation>) -> Self {
+        self.annotation = annotation;
+        self
+    }
+
+    pub fn annotation(&self) -> Option<&TypeAnnotation> {
+        self.annotation.as_ref()
+    }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    // Lines of the `///` comments written above the binding
+    pub fn with_docs(mut self, docs: Vec<String>) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    pub fn docs(&self) -> &[String] {
+        &self.docs
+    }
+
+    // Marks the bindings as visible to modules that import this one
+    pub fn with_export(mut self) -> Self {
+        self.exported = true;
+        self
+    }
+
+    pub fn is_exported(&self) -> bool {
+        self.exported
+    }
+
     pub fn token(&self) -> &Token {
         &self.token
     }
 
-    pub fn name(&self) -> &Identifier {
-        &self.name
+    // The bound name, when the statement does not destructure
+    pub fn name(&self) -> Option<&Identifier> {
+        self.pattern.as_binding()
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    pub fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
     }
 
     pub fn value(&self) -> &Expressions {
@@ -33,13 +96,14 @@ impl LetStatement {
 
 impl Display for LetStatement {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} {} = {};",
-            self.token_literal(),
-            self.name,
-            self.value
-        )?;
+        if self.exported {
+            write!(f, "export ")?;
+        }
+        write!(f, "{} {}", self.token_literal(), self.pattern)?;
+        if let Some(annotation) = &self.annotation {
+            write!(f, ": {}", annotation)?;
+        }
+        write!(f, " = {};", self.value)?;
         Ok(())
     }
 }
@@ -0,0 +1,114 @@
+use crate::ast::{block_statement::BlockStatement, Expression, Node, Token};
+use std::fmt::Display;
+
+use super::{Expressions, Pattern};
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct MatchArm {
+    pattern: Pattern,
+    guard: Option<Expressions>,
+    body: BlockStatement,
+}
+
+impl MatchArm {
+    pub fn new(pattern: Pattern, guard: Option<Expressions>, body: BlockStatement) -> Self {
+        Self {
+            pattern,
+            guard,
+            body,
+        }
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    pub fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+
+    pub fn guard(&self) -> Option<&Expressions> {
+        self.guard.as_ref()
+    }
+
+    pub fn guard_mut(&mut self) -> Option<&mut Expressions> {
+        self.guard.as_mut()
+    }
+
+    pub fn body(&self) -> &BlockStatement {
+        &self.body
+    }
+
+    pub fn body_mut(&mut self) -> &mut BlockStatement {
+        &mut self.body
+    }
+}
+
+impl Display for MatchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.pattern)?;
+        if let Some(guard) = &self.guard {
+            write!(f, " if {}", guard)?;
+        }
+        write!(f, " => {{ {} }}", self.body)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct MatchExpression {
+    token: Token,
+    subject: Box<Expressions>,
+    arms: Vec<MatchArm>,
+}
+
+impl MatchExpression {
+    pub fn new(token: Token, subject: Expressions, arms: Vec<MatchArm>) -> Self {
+        Self {
+            token,
+            subject: Box::new(subject),
+            arms,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn subject(&self) -> &Expressions {
+        &self.subject
+    }
+
+    pub fn subject_mut(&mut self) -> &mut Expressions {
+        self.subject.as_mut()
+    }
+
+    pub fn arms(&self) -> &[MatchArm] {
+        &self.arms
+    }
+
+    pub fn arms_mut(&mut self) -> &mut [MatchArm] {
+        &mut self.arms
+    }
+}
+
+impl Expression for MatchExpression {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for MatchExpression {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for MatchExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "match {} {{ ", self.subject)?;
+        for arm in &self.arms {
+            write!(f, "{}, ", arm)?;
+        }
+        write!(f, "}}")
+    }
+}
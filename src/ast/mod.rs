@@ -1,45 +1,92 @@
-use crate::lexer::Token;
+use crate::lexer::{Position, Token};
 use std::fmt::{Debug, Display};
 
+pub mod array_literal;
 pub mod block_statement;
 pub mod boolean_literal;
 pub mod call_expression;
+pub mod char_literal;
+pub mod enum_statement;
 pub mod expression_statement;
+pub mod field_access;
+pub mod float_literal;
 pub mod function_literal;
+pub mod hash_literal;
 pub mod identifier;
 pub mod if_expression;
+pub mod impl_statement;
+pub mod import_statement;
+pub mod index_expression;
 pub mod infix_expression;
 pub mod integer_literal;
+pub mod interpolated_string;
 pub mod let_statement;
+pub mod match_expression;
+pub mod parameter;
+pub mod pattern;
 pub mod prefix_expression;
 pub mod return_statement;
+pub mod spread_expression;
 pub mod string_literal;
+pub mod struct_literal;
+pub mod struct_statement;
+pub mod try_expression;
+pub mod type_annotation;
 
+pub use array_literal::ArrayLiteral;
 pub use block_statement::BlockStatement;
 pub use boolean_literal::BooleanLiteral;
 pub use call_expression::CallExpression;
+pub use char_literal::CharLiteral;
+pub use enum_statement::EnumStatement;
 pub use expression_statement::ExpressionStatement;
+pub use field_access::FieldAccess;
+pub use float_literal::FloatLiteral;
 pub use function_literal::FunctionLiteral;
+pub use hash_literal::HashLiteral;
 pub use identifier::Identifier;
 pub use if_expression::IfExpression;
+pub use impl_statement::ImplStatement;
+pub use import_statement::ImportStatement;
+pub use index_expression::IndexExpression;
 pub use infix_expression::InfixExpression;
 pub use integer_literal::IntegerLiteral;
+pub use interpolated_string::{InterpolatedString, InterpolationPart};
 pub use let_statement::LetStatement;
+pub use match_expression::{MatchArm, MatchExpression};
+pub use parameter::Parameter;
+pub use pattern::Pattern;
 pub use prefix_expression::PrefixExpression;
 pub use return_statement::ReturnStatement;
+pub use spread_expression::SpreadExpression;
 pub use string_literal::StringLiteral;
+pub use struct_literal::StructLiteral;
+pub use struct_statement::StructStatement;
+pub use try_expression::TryExpression;
+pub use type_annotation::TypeAnnotation;
 
 #[derive(Default, Debug, Clone, PartialEq, PartialOrd)]
 pub enum Expressions {
     Identifier(Identifier),
     BooleanLiteral(BooleanLiteral),
     IntegerLiteral(IntegerLiteral),
+    FloatLiteral(FloatLiteral),
     IfExpression(IfExpression),
+    MatchExpression(MatchExpression),
     InfixExpression(Box<InfixExpression>),
     PrefixExpression(Box<PrefixExpression>),
     CallExpression(Box<CallExpression>),
+    IndexExpression(Box<IndexExpression>),
+    SpreadExpression(Box<SpreadExpression>),
+    FieldAccess(Box<FieldAccess>),
+    TryExpression(Box<TryExpression>),
     FunctionLiteral(FunctionLiteral),
     StringLiteral(StringLiteral),
+    InterpolatedString(InterpolatedString),
+    CharLiteral(CharLiteral),
+    ArrayLiteral(ArrayLiteral),
+    HashLiteral(HashLiteral),
+    StructLiteral(Box<StructLiteral>),
     #[default]
     Empty,
 }
@@ -59,6 +106,20 @@ impl Expressions {
         }
     }
 
+    pub fn as_index_expression(self) -> Option<Box<IndexExpression>> {
+        match self {
+            Expressions::IndexExpression(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_array_literal(self) -> Option<ArrayLiteral> {
+        match self {
+            Expressions::ArrayLiteral(x) => Some(x),
+            _ => None,
+        }
+    }
+
     pub fn as_function_literal(self) -> Option<FunctionLiteral> {
         match self {
             Expressions::FunctionLiteral(x) => Some(x),
@@ -84,6 +145,12 @@ impl Expressions {
             _ => None,
         }
     }
+    pub fn as_float_literal(self) -> Option<FloatLiteral> {
+        match self {
+            Expressions::FloatLiteral(x) => Some(x),
+            _ => None,
+        }
+    }
     pub fn as_boolean_literal(self) -> Option<BooleanLiteral> {
         match self {
             Expressions::BooleanLiteral(x) => Some(x),
@@ -116,6 +183,17 @@ impl Display for Expressions {
             Expressions::CallExpression(x) => write!(f, "{}", x),
             Expressions::FunctionLiteral(x) => write!(f, "{}", x),
             Expressions::StringLiteral(x) => write!(f, "{}", x),
+            Expressions::TryExpression(x) => write!(f, "{}", x),
+            Expressions::FieldAccess(x) => write!(f, "{}", x),
+            Expressions::StructLiteral(x) => write!(f, "{}", x),
+            Expressions::SpreadExpression(x) => write!(f, "{}", x),
+            Expressions::MatchExpression(x) => write!(f, "{}", x),
+            Expressions::HashLiteral(x) => write!(f, "{}", x),
+            Expressions::InterpolatedString(x) => write!(f, "{}", x),
+            Expressions::ArrayLiteral(x) => write!(f, "{}", x),
+            Expressions::CharLiteral(x) => write!(f, "{}", x),
+            Expressions::IndexExpression(x) => write!(f, "{}", x),
+            Expressions::FloatLiteral(x) => write!(f, "{}", x),
             Expressions::Empty => panic!("Cannot display an empty expression"),
         }
     }
@@ -127,6 +205,10 @@ pub enum Statements {
     ReturnStatement(ReturnStatement),
     ExpressionStatement(ExpressionStatement),
     BlockStatement(BlockStatement),
+    StructStatement(StructStatement),
+    ImplStatement(ImplStatement),
+    EnumStatement(EnumStatement),
+    ImportStatement(ImportStatement),
     #[default]
     Empty,
 }
@@ -165,6 +247,10 @@ impl Display for Statements {
             Statements::ReturnStatement(x) => write!(f, "{}", x),
             Statements::ExpressionStatement(x) => write!(f, "{}", x),
             Statements::BlockStatement(x) => write!(f, "{}", x),
+            Statements::StructStatement(x) => write!(f, "{}", x),
+            Statements::ImplStatement(x) => write!(f, "{}", x),
+            Statements::EnumStatement(x) => write!(f, "{}", x),
+            Statements::ImportStatement(x) => write!(f, "{}", x),
             Statements::Empty => panic!("Cannot display an empty statement"),
         }
     }
@@ -210,6 +296,10 @@ impl Node for Program {
                 Statements::ReturnStatement(stmt) => stmt.token_literal(),
                 Statements::ExpressionStatement(stmt) => stmt.token_literal(),
                 Statements::BlockStatement(stmt) => stmt.token_literal(),
+                Statements::StructStatement(stmt) => stmt.token_literal(),
+                Statements::ImplStatement(stmt) => stmt.token_literal(),
+                Statements::EnumStatement(stmt) => stmt.token_literal(),
+                Statements::ImportStatement(stmt) => stmt.token_literal(),
                 Statements::Empty => panic!("Program encountered empty statement"),
             }
         } else {
@@ -237,7 +327,11 @@ mod tests {
         let mut v: Vec<Statements> = Vec::new();
         let name = Identifier::new(Token::Ident("my_var".into()));
         let value = Identifier::new(Token::Ident("another_var".into()));
-        let let_statement = LetStatement::new(Token::Let, name, Expressions::Identifier(value));
+        let let_statement = LetStatement::new(
+            Token::Let,
+            Pattern::Binding(name),
+            Expressions::Identifier(value),
+        );
         v.push(Statements::LetStatement(let_statement));
 
         let mut test_str = String::new();
@@ -0,0 +1,82 @@
+use std::fmt::Display;
+
+use super::{Expressions, Identifier, Pattern, TypeAnnotation};
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Parameter {
+    pattern: Pattern,
+    default: Option<Expressions>,
+    annotation: Option<TypeAnnotation>,
+    // `...name` collects the remaining positional arguments into an array
+    rest: bool,
+}
+
+impl Parameter {
+    pub fn new(pattern: Pattern, default: Option<Expressions>) -> Self {
+        Self {
+            pattern,
+            default,
+            annotation: None,
+            rest: false,
+        }
+    }
+
+    pub fn rest(name: Identifier) -> Self {
+        Self {
+            pattern: Pattern::Binding(name),
+            default: None,
+            annotation: None,
+            rest: true,
+        }
+    }
+
+    pub fn with_annotation(mut self, annotation: Option<TypeAnnotation>) -> Self {
+        self.annotation = annotation;
+        self
+    }
+
+    pub fn annotation(&self) -> Option<&TypeAnnotation> {
+        self.annotation.as_ref()
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    pub fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+
+    // Named arguments can only target parameters that are a plain name
+    pub fn name(&self) -> Option<&Identifier> {
+        self.pattern.as_binding()
+    }
+
+    pub fn default(&self) -> Option<&Expressions> {
+        self.default.as_ref()
+    }
+
+    pub fn default_mut(&mut self) -> Option<&mut Expressions> {
+        self.default.as_mut()
+    }
+
+    pub fn is_rest(&self) -> bool {
+        self.rest
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.rest {
+            write!(f, "...")?;
+        }
+        write!(f, "{}", self.pattern)?;
+        if let Some(annotation) = &self.annotation {
+            write!(f, ": {}", annotation)?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, " = {}", default)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use super::{Expressions, Identifier};
+
+// Patterns are shared by `match` arms, destructuring `let` and parameters
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum Pattern {
+    Wildcard,
+    Literal(Box<Expressions>),
+    Binding(Identifier),
+    // `[a, ..rest, z]`, rest is Some(None) for a bare `..`
+    Array {
+        head: Vec<Pattern>,
+        rest: Option<Option<Identifier>>,
+        tail: Vec<Pattern>,
+    },
+    Hash(Vec<(Expressions, Pattern)>),
+    // `Circle(r)`, a bare `None` stays a Binding and is resolved when matching
+    Variant(Identifier, Vec<Pattern>),
+    Alternative(Vec<Pattern>),
+}
+
+impl Pattern {
+    pub fn as_binding(&self) -> Option<&Identifier> {
+        match self {
+            Pattern::Binding(ident) => Some(ident),
+            _ => None,
+        }
+    }
+
+    // Every name the pattern binds when it matches
+    pub fn bindings(&self) -> Vec<&Identifier> {
+        let mut names = Vec::new();
+        self.collect_bindings(&mut names);
+        names
+    }
+
+    fn collect_bindings<'a>(&'a self, names: &mut Vec<&'a Identifier>) {
+        match self {
+            Pattern::Wildcard | Pattern::Literal(_) => {}
+            Pattern::Binding(ident) => names.push(ident),
+            Pattern::Array { head, rest, tail } => {
+                head.iter().for_each(|p| p.collect_bindings(names));
+                if let Some(Some(ident)) = rest {
+                    names.push(ident);
+                }
+                tail.iter().for_each(|p| p.collect_bindings(names));
+            }
+            Pattern::Hash(pairs) => pairs.iter().for_each(|(_, p)| p.collect_bindings(names)),
+            Pattern::Variant(_, fields) => fields.iter().for_each(|p| p.collect_bindings(names)),
+            Pattern::Alternative(options) => options.iter().for_each(|p| p.collect_bindings(names)),
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Literal(literal) => write!(f, "{}", literal),
+            Pattern::Binding(ident) => write!(f, "{}", ident),
+            Pattern::Array { head, rest, tail } => {
+                let mut parts: Vec<String> = head.iter().map(|p| p.to_string()).collect();
+                match rest {
+                    Some(Some(ident)) => parts.push(format!("..{}", ident)),
+                    Some(None) => parts.push(String::from("..")),
+                    None => {}
+                }
+                parts.extend(tail.iter().map(|p| p.to_string()));
+                write!(f, "[{}]", parts.join(", "))
+            }
+            Pattern::Hash(pairs) => {
+                let parts: Vec<String> = pairs
+                    .iter()
+                    .map(|(key, pattern)| format!("{}: {}", key, pattern))
+                    .collect();
+                write!(f, "{{{}}}", parts.join(", "))
+            }
+            Pattern::Variant(name, fields) => {
+                let parts: Vec<String> = fields.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}({})", name, parts.join(", "))
+            }
+            Pattern::Alternative(patterns) => {
+                let parts: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+                write!(f, "{}", parts.join(" | "))
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::ast::{Expression, Node, Token};
+use crate::ast::{Expression, Node, Position, Token};
 use std::fmt::Display;
 
 use super::Expressions;
@@ -8,6 +8,7 @@ pub struct PrefixExpression {
     token: Token,
     operator: String,
     expression_right: Expressions,
+    position: Position,
 }
 
 impl PrefixExpression {
@@ -17,8 +18,18 @@ impl PrefixExpression {
             token,
             operator,
             expression_right,
+            position: Position::default(),
         }
     }
+
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
     pub fn token(&self) -> &Token {
         &self.token
     }
@@ -1,4 +1,4 @@
-use crate::ast::{Node, Statement, Token};
+use crate::ast::{Node, Position, Statement, Token};
 use std::fmt::Display;
 
 use super::Expressions;
@@ -7,6 +7,7 @@ use super::Expressions;
 pub struct ReturnStatement {
     token: Token,
     return_value: Expressions,
+    position: Position,
 }
 
 impl ReturnStatement {
@@ -14,9 +15,19 @@ impl ReturnStatement {
         ReturnStatement {
             token,
             return_value,
+            position: Position::default(),
         }
     }
 
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
     pub fn token(&self) -> &Token {
         &self.token
     }
@@ -0,0 +1,47 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+// `...value`, expanded in place inside call arguments and array literals
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct SpreadExpression {
+    token: Token,
+    value: Expressions,
+}
+
+impl SpreadExpression {
+    pub fn new(token: Token, value: Expressions) -> Self {
+        Self { token, value }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn value(&self) -> &Expressions {
+        &self.value
+    }
+
+    pub fn value_mut(&mut self) -> &mut Expressions {
+        &mut self.value
+    }
+}
+
+impl Expression for SpreadExpression {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for SpreadExpression {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for SpreadExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "...{}", self.value)
+    }
+}
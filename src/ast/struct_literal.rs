@@ -0,0 +1,78 @@
+use crate::ast::{Expression, Identifier, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+// `Point { x: 1, y }`, `..base` copies the fields that are not listed
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct StructLiteral {
+    token: Token,
+    name: Identifier,
+    fields: Vec<(Identifier, Expressions)>,
+    base: Option<Expressions>,
+}
+
+impl StructLiteral {
+    pub fn new(
+        token: Token,
+        fields: Vec<(Identifier, Expressions)>,
+        base: Option<Expressions>,
+    ) -> Self {
+        Self {
+            name: Identifier::new(token.clone()),
+            token,
+            fields,
+            base,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[(Identifier, Expressions)] {
+        &self.fields
+    }
+
+    pub fn fields_mut(&mut self) -> &mut [(Identifier, Expressions)] {
+        &mut self.fields
+    }
+
+    pub fn base(&self) -> Option<&Expressions> {
+        self.base.as_ref()
+    }
+
+    pub fn base_mut(&mut self) -> Option<&mut Expressions> {
+        self.base.as_mut()
+    }
+}
+
+impl Expression for StructLiteral {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for StructLiteral {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for StructLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect();
+        if let Some(base) = &self.base {
+            fields.push(format!("..{}", base));
+        }
+        write!(f, "{} {{ {} }}", self.name, fields.join(", "))
+    }
+}
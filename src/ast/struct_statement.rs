@@ -0,0 +1,57 @@
+use crate::ast::{Identifier, Node, Statement, Token};
+use std::fmt::Display;
+
+// `struct Point { x, y }`
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct StructStatement {
+    token: Token,
+    name: Identifier,
+    fields: Vec<Identifier>,
+}
+
+impl StructStatement {
+    pub fn new(token: Token, name: Identifier, fields: Vec<Identifier>) -> Self {
+        Self {
+            token,
+            name,
+            fields,
+        }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[Identifier] {
+        &self.fields
+    }
+}
+
+impl Display for StructStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields: Vec<String> = self.fields.iter().map(|f| f.to_string()).collect();
+        write!(
+            f,
+            "{} {} {{ {} }}",
+            self.token_literal(),
+            self.name,
+            fields.join(", ")
+        )
+    }
+}
+
+impl Statement for StructStatement {
+    fn statement_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for StructStatement {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
@@ -0,0 +1,47 @@
+use crate::ast::{Expression, Node, Token};
+use std::fmt::Display;
+
+use super::Expressions;
+
+// `value?` unwraps an Ok or Some and returns an Err or None from the function
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct TryExpression {
+    token: Token,
+    value: Expressions,
+}
+
+impl TryExpression {
+    pub fn new(token: Token, value: Expressions) -> Self {
+        Self { token, value }
+    }
+
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    pub fn value(&self) -> &Expressions {
+        &self.value
+    }
+
+    pub fn value_mut(&mut self) -> &mut Expressions {
+        &mut self.value
+    }
+}
+
+impl Expression for TryExpression {
+    fn expression_node(&self) {
+        todo!()
+    }
+}
+
+impl Node for TryExpression {
+    fn token_literal(&self) -> &str {
+        self.token.literal()
+    }
+}
+
+impl Display for TryExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}?)", self.value)
+    }
+}
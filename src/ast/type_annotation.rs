@@ -0,0 +1,23 @@
+use std::fmt::Display;
+
+// A type written after `:` or `->`. Names cover the built-in types (`int`,
+// `string`, `any`, ...) as well as structs and enums.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum TypeAnnotation {
+    Named(String),
+    Array(Box<TypeAnnotation>),
+    Function(Vec<TypeAnnotation>, Box<TypeAnnotation>),
+}
+
+impl Display for TypeAnnotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeAnnotation::Named(name) => write!(f, "{}", name),
+            TypeAnnotation::Array(element) => write!(f, "[{}]", element),
+            TypeAnnotation::Function(parameters, result) => {
+                let parameters: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
+                write!(f, "fn({}) -> {}", parameters.join(", "), result)
+            }
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::ops::{Add, Mul, Neg, Sub};
+
+// Each limb holds nine decimal digits which keeps printing and parsing trivial
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+// Largest number the operators build, multiplying two of them is already slow
+pub const MAX_DIGITS: usize = 20_000;
+
+// Arbitrary precision signed integer. Limbs are stored least significant first
+// and zero is always represented as a positive number without limbs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self {
+            negative: false,
+            limbs: Vec::new(),
+        }
+    }
+
+    pub fn from_isize(value: isize) -> Self {
+        let negative = value < 0;
+        let mut magnitude = (value as i128).unsigned_abs();
+        let mut limbs = Vec::new();
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE as u128) as u32);
+            magnitude /= BASE as u128;
+        }
+        BigInt::from_parts(negative, limbs)
+    }
+
+    pub fn parse(input: &str) -> Option<Self> {
+        let (negative, digits) = match input.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, input),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut limbs = Vec::new();
+        let mut end = digits.len();
+        while end > 0 {
+            let start = end.saturating_sub(BASE_DIGITS);
+            limbs.push(digits[start..end].parse::<u32>().ok()?);
+            end = start;
+        }
+        Some(BigInt::from_parts(negative, limbs))
+    }
+
+    // Parses unsigned digits in the given radix (2 to 36)
+    pub fn parse_radix(digits: &str, radix: u32) -> Option<Self> {
+        if digits.is_empty() {
+            return None;
+        }
+        let mut limbs = Vec::new();
+        for ch in digits.chars() {
+            let digit = ch.to_digit(radix)?;
+            limbs = add_magnitude(&mul_small(&limbs, radix as u64), &[digit]);
+        }
+        Some(BigInt::from_parts(false, limbs))
+    }
+
+    // Truncates towards zero, None for infinities and NaN
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        let value = value.trunc();
+        if value.abs() < (1u64 << 53) as f64 {
+            return Some(BigInt::from_isize(value as isize));
+        }
+        // Floats this large are an integer mantissa times a power of two
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as u32 - 1075;
+        let mantissa = (bits & ((1 << 52) - 1)) | (1 << 52);
+        let magnitude =
+            &BigInt::from_isize(mantissa as isize) * &BigInt::from_isize(2).pow(exponent);
+        Some(if value < 0.0 { -&magnitude } else { magnitude })
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        let negative = negative && !limbs.is_empty();
+        Self { negative, limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn to_isize(&self) -> Option<isize> {
+        let mut magnitude: i128 = 0;
+        for limb in self.limbs.iter().rev() {
+            magnitude = magnitude
+                .checked_mul(BASE as i128)?
+                .checked_add(*limb as i128)?;
+        }
+        if self.negative {
+            magnitude = -magnitude;
+        }
+        isize::try_from(magnitude).ok()
+    }
+
+    // Approximate number of decimal digits, negative infinity for zero
+    pub fn log10(&self) -> f64 {
+        match self.limbs.last() {
+            Some(top) => (self.limbs.len() - 1) as f64 * BASE_DIGITS as f64 + (*top as f64).log10(),
+            None => f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self
+            .limbs
+            .iter()
+            .rev()
+            .fold(0.0, |acc, limb| acc * BASE as f64 + *limb as f64);
+        if self.negative {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> BigInt {
+        let mut result = BigInt::from_isize(1);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    // Truncating division like the operators on isize, returns None for a zero divisor
+    pub fn div_rem(&self, divisor: &BigInt) -> Option<(BigInt, BigInt)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder: Vec<u32> = Vec::new();
+        for (i, limb) in self.limbs.iter().enumerate().rev() {
+            remainder.insert(0, *limb);
+            trim(&mut remainder);
+            // Largest digit d with divisor * d <= remainder
+            let (mut low, mut high) = (0, BASE - 1);
+            while low < high {
+                let mid = (low + high).div_ceil(2);
+                if cmp_magnitude(&mul_small(&divisor.limbs, mid), &remainder) == Ordering::Greater {
+                    high = mid - 1;
+                } else {
+                    low = mid;
+                }
+            }
+            if low > 0 {
+                remainder = sub_magnitude(&remainder, &mul_small(&divisor.limbs, low));
+            }
+            quotient[i] = low as u32;
+        }
+        Some((
+            BigInt::from_parts(self.negative != divisor.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+}
+
+fn trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn cmp_magnitude(left: &[u32], right: &[u32]) -> Ordering {
+    left.len()
+        .cmp(&right.len())
+        .then_with(|| left.iter().rev().cmp(right.iter().rev()))
+}
+
+fn add_magnitude(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(left.len().max(right.len()) + 1);
+    let mut carry = 0;
+    for i in 0..left.len().max(right.len()) {
+        let sum = *left.get(i).unwrap_or(&0) as u64 + *right.get(i).unwrap_or(&0) as u64 + carry;
+        result.push((sum % BASE) as u32);
+        carry = sum / BASE;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+// Expects left >= right
+fn sub_magnitude(left: &[u32], right: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(left.len());
+    let mut borrow = 0;
+    for (i, limb) in left.iter().enumerate() {
+        let mut diff = *limb as i64 - *right.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if diff < 0 {
+            diff += BASE as i64;
+            borrow = 1;
+        }
+        result.push(diff as u32);
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_small(limbs: &[u32], factor: u64) -> Vec<u32> {
+    let mut result = Vec::with_capacity(limbs.len() + 1);
+    let mut carry = 0;
+    for limb in limbs {
+        let product = *limb as u64 * factor + carry;
+        result.push((product % BASE) as u32);
+        carry = product / BASE;
+    }
+    while carry > 0 {
+        result.push((carry % BASE) as u32);
+        carry /= BASE;
+    }
+    trim(&mut result);
+    result
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, add_magnitude(&self.limbs, &other.limbs));
+        }
+        match cmp_magnitude(&self.limbs, &other.limbs) {
+            Ordering::Less => {
+                BigInt::from_parts(other.negative, sub_magnitude(&other.limbs, &self.limbs))
+            }
+            _ => BigInt::from_parts(self.negative, sub_magnitude(&self.limbs, &other.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        let mut result = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let current = result[i + j] + *a as u64 * *b as u64 + carry;
+                result[i + j] = current % BASE;
+                carry = current / BASE;
+            }
+            result[i + other.limbs.len()] += carry;
+        }
+        let limbs = result.into_iter().map(|limb| limb as u32).collect();
+        BigInt::from_parts(self.negative != other.negative, limbs)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_magnitude(&self.limbs, &other.limbs),
+            (true, true) => cmp_magnitude(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        match self.limbs.split_last() {
+            Some((most, rest)) => {
+                write!(f, "{}", most)?;
+                for limb in rest.iter().rev() {
+                    write!(f, "{:09}", limb)?;
+                }
+                Ok(())
+            }
+            None => write!(f, "0"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(input: &str) -> BigInt {
+        BigInt::parse(input).expect("could not parse BigInt")
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let inputs = [
+            "0",
+            "-0",
+            "7",
+            "-123",
+            "1000000000",
+            "123456789012345678901234567890",
+        ];
+        let expected = [
+            "0",
+            "0",
+            "7",
+            "-123",
+            "1000000000",
+            "123456789012345678901234567890",
+        ];
+        for (input, exp) in inputs.iter().zip(expected) {
+            assert_eq!(exp, big(input).to_string());
+        }
+        assert_eq!(None, BigInt::parse("12a"));
+        assert_eq!(None, BigInt::parse("-"));
+        assert_eq!(Some(big("255")), BigInt::parse_radix("ff", 16));
+        assert_eq!(Some(big("15")), BigInt::parse_radix("17", 8));
+        assert_eq!(Some(big("10")), BigInt::parse_radix("1010", 2));
+        assert_eq!(
+            Some(big("340282366920938463463374607431768211455")),
+            BigInt::parse_radix("ffffffffffffffffffffffffffffffff", 16)
+        );
+        assert_eq!(None, BigInt::parse_radix("12", 2));
+        assert_eq!(None, BigInt::parse_radix("", 16));
+        assert_eq!(
+            "-9223372036854775808",
+            BigInt::from_isize(isize::MIN).to_string()
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = big("99999999999999999999");
+        let b = big("-12345678901234567890");
+        assert_eq!("87654321098765432109", (&a + &b).to_string());
+        assert_eq!("112345678901234567889", (&a - &b).to_string());
+        assert_eq!(
+            "-1234567890123456788987654321098765432110",
+            (&a * &b).to_string()
+        );
+        let (q, r) = a.div_rem(&b).unwrap();
+        assert_eq!("-8", q.to_string());
+        assert_eq!("1234568790123456879", r.to_string());
+        let (q, r) = big("-7").div_rem(&big("2")).unwrap();
+        assert_eq!(
+            ("-3".to_string(), "-1".to_string()),
+            (q.to_string(), r.to_string())
+        );
+        assert_eq!(None, a.div_rem(&BigInt::zero()));
+        assert_eq!(
+            "1267650600228229401496703205376",
+            big("2").pow(100).to_string()
+        );
+        assert_eq!("-27", big("-3").pow(3).to_string());
+        assert_eq!("1", big("12").pow(0).to_string());
+    }
+
+    #[test]
+    fn conversions_and_ordering() {
+        assert_eq!(Some(isize::MAX), BigInt::from_isize(isize::MAX).to_isize());
+        assert_eq!(None, big("9223372036854775808").to_isize());
+        assert_eq!(Some(isize::MIN), big("-9223372036854775808").to_isize());
+        assert_eq!(1e20, big("100000000000000000000").to_f64());
+        assert!(big("-5") < big("3"));
+        assert!(big("-50000000000") < big("-5"));
+        assert!(big("10000000000") > big("9999999999"));
+    }
+}
@@ -1,4 +1,9 @@
+use crate::bigint::BigInt;
+use crate::object::Array;
+use crate::object::BigInteger;
+use crate::object::Char;
 use crate::object::ErrorObject;
+use crate::object::Float;
 use crate::object::Integer;
 use crate::object::Object;
 use crate::object::Objects;
@@ -15,7 +20,11 @@ impl BuiltinFunctions {
         }
         let obj = &args[0];
         match obj {
-            Objects::String(s) => Objects::Integer(Integer::new(s.value().len() as isize)),
+            Objects::String(s) => {
+                Objects::Integer(Integer::new(s.value().chars().count() as isize))
+            }
+            Objects::Array(a) => Objects::Integer(Integer::new(a.elements().len() as isize)),
+            Objects::Hash(h) => Objects::Integer(Integer::new(h.len() as isize)),
             _ => {
                 return Objects::Error(ErrorObject::new(format!(
                     "argument to 'len' not supported, got {}",
@@ -24,4 +33,156 @@ impl BuiltinFunctions {
             }
         }
     }
+
+    pub fn int(args: &[Objects]) -> Objects {
+        if args.len() != 1 {
+            return Objects::Error(ErrorObject::new(format!(
+                "expected 1 argument but received {}",
+                args.len()
+            )));
+        }
+        let obj = &args[0];
+        match obj {
+            Objects::Integer(_) | Objects::BigInteger(_) => obj.clone(),
+            Objects::Float(f) => match BigInt::from_f64(*f.value()) {
+                Some(value) => BigInteger::normalize(value),
+                None => Objects::Error(ErrorObject::new(format!(
+                    "cannot convert {} to INTEGER",
+                    f.inspect()
+                ))),
+            },
+            Objects::String(s) => match BigInt::parse(s.value().trim()) {
+                Some(value) => BigInteger::normalize(value),
+                None => Objects::Error(ErrorObject::new(format!(
+                    "cannot convert \"{}\" to INTEGER",
+                    s.value()
+                ))),
+            },
+            _ => Objects::Error(ErrorObject::new(format!(
+                "argument to 'int' not supported, got {}",
+                obj.obj_type()
+            ))),
+        }
+    }
+
+    pub fn float(args: &[Objects]) -> Objects {
+        if args.len() != 1 {
+            return Objects::Error(ErrorObject::new(format!(
+                "expected 1 argument but received {}",
+                args.len()
+            )));
+        }
+        let obj = &args[0];
+        match obj {
+            Objects::Integer(i) => Objects::Float(Float::new(*i.value() as f64)),
+            Objects::BigInteger(i) => Objects::Float(Float::new(i.value().to_f64())),
+            Objects::Float(_) => obj.clone(),
+            Objects::String(s) => match s.value().trim().parse::<f64>() {
+                Ok(value) => Objects::Float(Float::new(value)),
+                Err(_) => Objects::Error(ErrorObject::new(format!(
+                    "cannot convert \"{}\" to FLOAT",
+                    s.value()
+                ))),
+            },
+            _ => Objects::Error(ErrorObject::new(format!(
+                "argument to 'float' not supported, got {}",
+                obj.obj_type()
+            ))),
+        }
+    }
+
+    pub fn ord(args: &[Objects]) -> Objects {
+        if args.len() != 1 {
+            return Objects::Error(ErrorObject::new(format!(
+                "expected 1 argument but received {}",
+                args.len()
+            )));
+        }
+        match &args[0] {
+            Objects::Char(c) => Objects::Integer(Integer::new(c.value() as isize)),
+            obj => Objects::Error(ErrorObject::new(format!(
+                "argument to 'ord' not supported, got {}",
+                obj.obj_type()
+            ))),
+        }
+    }
+
+    pub fn chr(args: &[Objects]) -> Objects {
+        if args.len() != 1 {
+            return Objects::Error(ErrorObject::new(format!(
+                "expected 1 argument but received {}",
+                args.len()
+            )));
+        }
+        match &args[0] {
+            Objects::Integer(i) => match u32::try_from(*i.value()).ok().and_then(char::from_u32) {
+                Some(c) => Objects::Char(Char::new(c)),
+                None => Objects::Error(ErrorObject::new(format!(
+                    "invalid code point: {}",
+                    i.value()
+                ))),
+            },
+            obj => Objects::Error(ErrorObject::new(format!(
+                "argument to 'chr' not supported, got {}",
+                obj.obj_type()
+            ))),
+        }
+    }
+
+    // Splits a string into its characters, which is how scripts iterate over one
+    pub fn chars(args: &[Objects]) -> Objects {
+        if args.len() != 1 {
+            return Objects::Error(ErrorObject::new(format!(
+                "expected 1 argument but received {}",
+                args.len()
+            )));
+        }
+        match &args[0] {
+            Objects::String(s) => Objects::Array(Array::new(
+                s.value()
+                    .chars()
+                    .map(|c| Objects::Char(Char::new(c)))
+                    .collect(),
+            )),
+            obj => Objects::Error(ErrorObject::new(format!(
+                "argument to 'chars' not supported, got {}",
+                obj.obj_type()
+            ))),
+        }
+    }
+
+    pub fn invoke(args: &[Objects]) -> Objects {
+        if args.len() < 2 {
+            return Objects::Error(ErrorObject::new(format!(
+                "expected at least 2 arguments but received {}",
+                args.len()
+            )));
+        }
+        let native = match &args[0] {
+            Objects::Native(n) => n,
+            obj => {
+                return Objects::Error(ErrorObject::new(format!(
+                    "argument to 'invoke' not supported, got {}",
+                    obj.obj_type()
+                )));
+            }
+        };
+        let name = match &args[1] {
+            Objects::String(s) => s.value(),
+            obj => {
+                return Objects::Error(ErrorObject::new(format!(
+                    "method name must be STRING, got {}",
+                    obj.obj_type()
+                )));
+            }
+        };
+        match native.call_method(name, &args[2..]) {
+            Some(result) => result,
+            None => Objects::Error(ErrorObject::new(format!(
+                "undefined method '{}' for {}",
+                name,
+                native.type_name()
+            ))),
+        }
+    }
 }
@@ -1,23 +1,49 @@
 use crate::ast::BlockStatement;
+use crate::ast::CallExpression;
 use crate::ast::Expressions;
 use crate::ast::Identifier;
 use crate::ast::IfExpression;
+use crate::ast::ImportStatement;
+use crate::ast::InterpolationPart;
+use crate::ast::MatchExpression;
+use crate::ast::Parameter;
+use crate::ast::Pattern;
 use crate::ast::Program;
 use crate::ast::Statements;
+use crate::ast::StructLiteral;
+use crate::bigint::{BigInt, MAX_DIGITS};
+use crate::object::Array;
+use crate::object::BigInteger;
 use crate::object::Boolean;
 use crate::object::BuiltinFunction;
 use crate::object::BuiltinWrapper;
+use crate::object::Char;
+use crate::object::EnumType;
+use crate::object::EnumValue;
 use crate::object::Environment;
+use crate::object::ErrorKind;
 use crate::object::ErrorObject;
+use crate::object::Float;
 use crate::object::Function;
+use crate::object::GcStats;
+use crate::object::Hash;
+use crate::object::HashKey;
 use crate::object::Integer;
+use crate::object::Limit;
+use crate::object::Module;
+use crate::object::Native;
 use crate::object::Null;
 use crate::object::Object;
 use crate::object::ObjectTypes;
 use crate::object::Objects;
 use crate::object::Return;
 use crate::object::StringObject;
+use crate::object::Struct;
+use crate::object::StructType;
+use crate::stdlib;
+use crate::{lexer::Lexer, parser::Parser};
 use std::cell::RefCell;
+use std::path::Path;
 use std::rc::Rc;
 
 const TRUE: Boolean = Boolean { value: true };
@@ -41,7 +67,7 @@ fn eval_identifier(ident: &Identifier, env: Rc<RefCell<Environment>>) -> Objects
         _ => {}
     }
     let env = env.borrow();
-    if let Some(obj) = env.get(ident.value().into()) {
+    if let Some(obj) = try_member(ident.value(), |name| env.get(name.into())) {
         return obj;
     } else {
         return Objects::Error(ErrorObject::new(format!(
@@ -51,13 +77,22 @@ fn eval_identifier(ident: &Identifier, env: Rc<RefCell<Environment>>) -> Objects
     }
 }
 
+// The lexer reads `x?` as one name, without a binding of that name it applies
+// the ? operator to `x`
+fn try_member(name: &str, get: impl Fn(&str) -> Option<Objects>) -> Option<Objects> {
+    get(name).or_else(|| get(name.strip_suffix('?')?).map(eval_try))
+}
+
 fn eval_block_statement(
     block: &mut BlockStatement,
     env: Rc<RefCell<Environment>>,
 ) -> Option<Objects> {
     let mut final_result: Option<Objects> = None;
     for stmt in block.statements_mut().iter_mut() {
-        let result = eval_statement(stmt, Rc::clone(&env))?;
+        let result = match eval_statement(stmt, Rc::clone(&env)) {
+            Some(result) => result,
+            None => continue,
+        };
 
         if result.obj_type() == ObjectTypes::Error {
             return Some(result);
@@ -76,19 +111,47 @@ fn eval_statement(statement: &mut Statements, env: Rc<RefCell<Environment>>) ->
     let mut result: Option<Objects> = None;
     match statement {
         Statements::LetStatement(value) => {
-            let word = &value.name().to_string()[..];
-            if KEYWORDS.contains(&word) {
-                return Some(Objects::Error(ErrorObject::new(format!("Cannot overwrite keyword: {}", word))));
+            if let Some(name) = value.name() {
+                let word = name.value();
+                if KEYWORDS.contains(&word) {
+                    return Some(Objects::Error(ErrorObject::new(format!(
+                        "Cannot overwrite keyword: {}",
+                        word
+                    ))));
+                }
             }
             let let_value = eval_expression(&mut value.value_mut(), Rc::clone(&env));
-            if let_value.is_err() {
+            if let_value.is_unwinding() {
                 return Some(let_value);
             }
-            env.borrow_mut().set(value.name().value().into(), let_value);
+            let mut bindings = Vec::new();
+            match match_pattern(value.pattern_mut(), &let_value, &env, &mut bindings) {
+                Ok(()) => {}
+                Err(PatternError::Mismatch(msg)) => {
+                    return Some(Objects::Error(ErrorObject::new(format!(
+                        "cannot bind {}: {}",
+                        value.pattern(),
+                        msg
+                    ))));
+                }
+                Err(PatternError::Error(err)) => return Some(err),
+            }
+            for (name, bound) in bindings {
+                if KEYWORDS.contains(&name.as_str()) {
+                    return Some(Objects::Error(ErrorObject::new(format!(
+                        "Cannot overwrite keyword: {}",
+                        name
+                    ))));
+                }
+                if value.is_exported() {
+                    env.borrow_mut().export(name.clone());
+                }
+                env.borrow_mut().set(name, bound);
+            }
         }
         Statements::ReturnStatement(value) => {
             let return_value = eval_expression(&mut value.return_value_mut(), env);
-            if return_value.is_err() {
+            if return_value.is_unwinding() {
                 return Some(return_value);
             }
             result = Some(Objects::Return(Return::new(return_value)));
@@ -100,12 +163,73 @@ fn eval_statement(statement: &mut Statements, env: Rc<RefCell<Environment>>) ->
             let block_statement = eval_block_statement(value, env)?;
             result = Some(block_statement);
         }
+        Statements::ImplStatement(value) => {
+            let name = value.name().value().to_string();
+            let definition = env.borrow().get(name.clone());
+            match &definition {
+                Some(Objects::StructType(_)) | Some(Objects::EnumType(_)) => {}
+                Some(other) => {
+                    return Some(Objects::Error(ErrorObject::new(format!(
+                        "cannot impl {}, only structs and enums have methods",
+                        other.obj_type()
+                    ))))
+                }
+                None => {
+                    return Some(Objects::Error(ErrorObject::new(format!(
+                        "unknown type: {}",
+                        name
+                    ))))
+                }
+            };
+            for (method, literal) in value.methods() {
+                let params = literal.parameters().to_vec();
+                let func = Function::new(params, literal.body().clone(), Rc::clone(&env));
+                let func = track_allocation(Objects::Function(func), &env);
+                if func.is_unwinding() {
+                    return Some(func);
+                }
+                match &definition {
+                    Some(Objects::StructType(d)) => d.add_method(method.value().into(), func),
+                    Some(Objects::EnumType(d)) => d.add_method(method.value().into(), func),
+                    _ => unreachable!(),
+                }
+            }
+        }
+        Statements::EnumStatement(value) => {
+            let variants = value
+                .variants()
+                .iter()
+                .map(|(variant, fields)| {
+                    let fields = fields.iter().map(|f| f.value().to_string()).collect();
+                    (variant.value().to_string(), fields)
+                })
+                .collect();
+            let definition = EnumType::new(value.name().value(), variants);
+            env.borrow_mut().declare_enum(definition);
+        }
+        Statements::StructStatement(value) => {
+            let fields = value
+                .fields()
+                .iter()
+                .map(|f| f.value().to_string())
+                .collect();
+            let definition = StructType::new(value.name().value(), fields);
+            env.borrow_mut()
+                .set(value.name().value().into(), Objects::StructType(definition));
+        }
+        Statements::ImportStatement(value) => {
+            let module = eval_import(value, &env);
+            if module.is_unwinding() {
+                return Some(module);
+            }
+            env.borrow_mut().set(value.alias().value().into(), module);
+        }
         Statements::Empty => panic!("Reached an empty statement"),
     }
     result
 }
 
-fn eval_statements(
+pub fn eval_statements(
     statements: &mut [Statements],
     env: Rc<RefCell<Environment>>,
 ) -> Option<Objects> {
@@ -114,19 +238,37 @@ fn eval_statements(
     for stmt in statements.iter_mut() {
         result = eval_statement(stmt, Rc::clone(&env));
         if let Some(ref result) = result {
-            if result.is_err() || result.is_return() {
+            if result.is_unwinding() {
                 is_returnable = true;
             }
         }
         if is_returnable {
             return result;
         }
-
     }
     result
 }
 
+// Strings, arrays, hashes, functions and environments own heap memory and count towards the allocation limit
+fn track_allocation(obj: Objects, env: &Rc<RefCell<Environment>>) -> Objects {
+    match obj {
+        Objects::String(_)
+        | Objects::Array(_)
+        | Objects::Hash(_)
+        | Objects::Struct(_)
+        | Objects::Enum(_)
+        | Objects::Function(_) => match env.borrow().sandbox().allocate() {
+            Ok(()) => obj,
+            Err(err) => Objects::Error(err),
+        },
+        _ => obj,
+    }
+}
+
 fn eval_expression(node: &mut Expressions, env: Rc<RefCell<Environment>>) -> Objects {
+    if let Err(err) = env.borrow().sandbox().step() {
+        return Objects::Error(err);
+    }
     match node {
         Expressions::Identifier(value) => eval_identifier(value, env),
         Expressions::BooleanLiteral(value) => {
@@ -136,34 +278,107 @@ fn eval_expression(node: &mut Expressions, env: Rc<RefCell<Environment>>) -> Obj
                 return Objects::Boolean(FALSE);
             }
         }
-        Expressions::IntegerLiteral(value) => Objects::Integer(Integer::new(value.value())),
+        Expressions::IntegerLiteral(value) => match value.value() {
+            Some(small) => Objects::Integer(Integer::new(small)),
+            None => Objects::BigInteger(BigInteger::new(value.big_value())),
+        },
+        Expressions::FloatLiteral(value) => Objects::Float(Float::new(value.value())),
         Expressions::IfExpression(value) => {
             eval_if_expression(value, env).expect("could not evaluate if expression")
         }
+        Expressions::MatchExpression(value) => eval_match_expression(value, env),
         Expressions::InfixExpression(value) => {
             let left = eval_expression(&mut value.expression_left_mut(), Rc::clone(&env));
-            if left.is_err() {
+            if left.is_unwinding() {
                 return left;
             }
             let right = eval_expression(&mut value.expression_right_mut(), Rc::clone(&env));
-            if right.is_err() {
+            if right.is_unwinding() {
                 return right;
             }
-            return eval_infix_expression(value.operator(), left, right);
+            let result = eval_infix_expression(value.operator(), left, right);
+            return track_allocation(result, &env);
         }
         Expressions::PrefixExpression(value) => {
             let right = eval_expression(&mut value.expression_right_mut(), Rc::clone(&env));
-            if right.is_err() {
+            if right.is_unwinding() {
                 return right;
             }
             return eval_prefix_expression(value.operator(), &right);
         }
+        Expressions::StructLiteral(value) => {
+            let result = eval_struct_literal(value, &env);
+            track_allocation(result, &env)
+        }
+        Expressions::FieldAccess(value) => {
+            let object = eval_expression(value.object_mut(), Rc::clone(&env));
+            match &object {
+                Objects::Error(_) => object,
+                Objects::Struct(s) => {
+                    match try_member(value.field().value(), |f| s.get(f).cloned()) {
+                        Some(field) => field,
+                        None => Objects::Error(ErrorObject::new(format!(
+                            "{} has no field '{}'",
+                            s.name(),
+                            value.field()
+                        ))),
+                    }
+                }
+                Objects::Module(m) => match try_member(value.field().value(), |name| m.get(name)) {
+                    Some(member) => member,
+                    None => Objects::Error(ErrorObject::new(format!(
+                        "module {} has no export '{}'",
+                        m.path(),
+                        value.field()
+                    ))),
+                },
+                Objects::EnumType(t) => match t.variant(value.field().value()) {
+                    Some(variant) => variant,
+                    None => Objects::Error(ErrorObject::new(format!(
+                        "{} has no variant '{}'",
+                        t.name(),
+                        value.field()
+                    ))),
+                },
+                _ => Objects::Error(ErrorObject::new(format!(
+                    "cannot access field '{}' on {}",
+                    value.field(),
+                    object.obj_type()
+                ))),
+            }
+        }
+        Expressions::TryExpression(value) => {
+            let result = eval_expression(value.value_mut(), Rc::clone(&env));
+            if result.is_unwinding() {
+                return result;
+            }
+            eval_try(result)
+        }
+        Expressions::SpreadExpression(_) => Objects::Error(ErrorObject::new(String::from(
+            "spread is only allowed in call arguments and array literals",
+        ))),
+        Expressions::IndexExpression(value) => {
+            let left = eval_expression(value.left_mut(), Rc::clone(&env));
+            if left.is_unwinding() {
+                return left;
+            }
+            let index = eval_expression(value.index_mut(), Rc::clone(&env));
+            if index.is_unwinding() {
+                return index;
+            }
+            let result = eval_index_expression(left, index);
+            track_allocation(result, &env)
+        }
         Expressions::FunctionLiteral(value) => {
-            let params: Vec<Identifier> = value.parameters().to_vec();
+            let params: Vec<Parameter> = value.parameters().to_vec();
             let body = std::mem::replace(value.body_mut(), BlockStatement::empty());
-            return Objects::Function(Function::new(params, body, Rc::clone(&env)));
+            let func = Objects::Function(Function::new(params, body, Rc::clone(&env)));
+            return track_allocation(func, &env);
         }
         Expressions::CallExpression(value) => {
+            if let Expressions::FieldAccess(_) = value.function() {
+                return eval_method_call(value, &env);
+            }
             let mut func = eval_expression(&mut value.function_mut(), Rc::clone(&env));
             match func {
                 Objects::Error(_) => return func,
@@ -190,76 +405,670 @@ fn eval_expression(node: &mut Expressions, env: Rc<RefCell<Environment>>) -> Obj
                                 }
                             }
                         }
-                        BuiltinFunction::Default(func) => {
+                        BuiltinFunction::Gc => {
+                            if !value.arguments().is_empty() {
+                                return Objects::Error(ErrorObject::new(String::from(
+                                    "Invalid number of arguments to function",
+                                )));
+                            }
+                            let heap = Rc::clone(env.borrow().heap());
+                            let freed = heap.collect();
+                            return Objects::Integer(Integer::new(freed as isize));
+                        }
+                        BuiltinFunction::GcStats => {
+                            if !value.arguments().is_empty() {
+                                return Objects::Error(ErrorObject::new(String::from(
+                                    "Invalid number of arguments to function",
+                                )));
+                            }
+                            let GcStats {
+                                collections,
+                                tracked,
+                                freed,
+                            } = env.borrow().heap().stats();
+                            let mut hash = Hash::new();
+                            for (key, count) in [
+                                ("collections", collections),
+                                ("tracked", tracked),
+                                ("freed", freed),
+                            ] {
+                                let key = Objects::String(StringObject::new(key.to_string()));
+                                let _ = hash
+                                    .insert(key, Objects::Integer(Integer::new(count as isize)));
+                            }
+                            return Objects::Hash(hash);
+                        }
+                        BuiltinFunction::Default(_) | BuiltinFunction::Sandboxed(_) => {
+                            if !value.named().is_empty() {
+                                return Objects::Error(ErrorObject::new(String::from(
+                                    "builtin functions do not accept named arguments",
+                                )));
+                            }
                             let mut arguments =
                                 eval_expressions(&mut value.arguments_mut(), Rc::clone(&env));
-                            if arguments.len() == 1 && arguments[0].is_err() {
+                            if arguments.len() == 1 && arguments[0].is_unwinding() {
                                 return arguments.remove(0);
                             }
-                            let evaluated = func(&arguments);
-                            return evaluated;
+                            let mut func = Objects::Builtin(Rc::clone(&b));
+                            return apply_tracked(&mut func, &mut arguments, &[], &env);
                         }
                     }
                 }
                 _ => {
                     let mut arguments =
                         eval_expressions(&mut value.arguments_mut(), Rc::clone(&env));
-                    if arguments.len() == 1 && arguments[0].is_err() {
+                    if arguments.len() == 1 && arguments[0].is_unwinding() {
                         return arguments.remove(0);
                     }
-                    return apply_function(&mut func, &mut arguments);
+                    let named = match eval_named_arguments(value.named_mut(), &env) {
+                        Ok(named) => named,
+                        Err(err) => return err,
+                    };
+                    return apply_tracked(&mut func, &mut arguments, &named, &env);
                 }
             }
         }
         Expressions::StringLiteral(value) => {
-            Objects::String(StringObject::new(value.value().into()))
+            let string = Objects::String(StringObject::new(value.value().into()));
+            track_allocation(string, &env)
+        }
+        Expressions::InterpolatedString(value) => {
+            let mut string = String::new();
+            for part in value.parts_mut() {
+                match part {
+                    InterpolationPart::Text(text) => string.push_str(text),
+                    InterpolationPart::Expression(expression) => {
+                        let evaluated = eval_expression(expression, Rc::clone(&env));
+                        if evaluated.is_unwinding() {
+                            return evaluated;
+                        }
+                        string.push_str(&evaluated.inspect());
+                    }
+                }
+            }
+            track_allocation(Objects::String(StringObject::new(string)), &env)
+        }
+        Expressions::CharLiteral(value) => Objects::Char(Char::new(value.value())),
+        Expressions::ArrayLiteral(value) => {
+            let mut elements = eval_expressions(value.elements_mut(), Rc::clone(&env));
+            if elements.len() == 1 && elements[0].is_unwinding() {
+                return elements.remove(0);
+            }
+            track_allocation(Objects::Array(Array::new(elements)), &env)
+        }
+        Expressions::HashLiteral(value) => {
+            let mut hash = Hash::new();
+            for (key, value) in value.pairs_mut() {
+                let key = eval_expression(key, Rc::clone(&env));
+                if key.is_unwinding() {
+                    return key;
+                }
+                let value = eval_expression(value, Rc::clone(&env));
+                if value.is_unwinding() {
+                    return value;
+                }
+                if let Err(key) = hash.insert(key, value) {
+                    return Objects::Error(ErrorObject::new(format!(
+                        "unusable as hash key: {}",
+                        key.obj_type()
+                    )));
+                }
+            }
+            track_allocation(Objects::Hash(hash), &env)
         }
         _ => Objects::Null(NULL),
     }
 }
 
+// Builtins have no environment of their own, their caller's sandbox runs the
+// sandboxed ones and counts what any of them return
+fn apply_tracked(
+    func: &mut Objects,
+    arguments: &mut Vec<Objects>,
+    named: &[(String, Objects)],
+    env: &Rc<RefCell<Environment>>,
+) -> Objects {
+    let sandboxed = match func {
+        Objects::Builtin(b) => match b.func() {
+            BuiltinFunction::Sandboxed(sandboxed) if named.is_empty() => Some(*sandboxed),
+            _ => None,
+        },
+        _ => None,
+    };
+    let builtin = matches!(func, Objects::Builtin(_));
+    let evaluated = match sandboxed {
+        Some(sandboxed) => sandboxed(arguments, env.borrow().sandbox()),
+        None => apply_function(func, arguments, named),
+    };
+    if builtin {
+        return track_allocation(evaluated, env);
+    }
+    evaluated
+}
 
-fn apply_function(func: &mut Objects, arguments: &mut Vec<Objects>) -> Objects {
+fn apply_function(
+    func: &mut Objects,
+    arguments: &mut Vec<Objects>,
+    named: &[(String, Objects)],
+) -> Objects {
     match func {
         Objects::Builtin(b) => {
             let func = b.func();
             match func {
+                BuiltinFunction::Default(_) | BuiltinFunction::Sandboxed(_)
+                    if !named.is_empty() =>
+                {
+                    Objects::Error(ErrorObject::new(String::from(
+                        "builtin functions do not accept named arguments",
+                    )))
+                }
                 BuiltinFunction::Default(func) => {
                     let evaluated = func(&arguments);
                     return evaluated;
                 }
-                _ => {
-                    unreachable!()
-                }
+                _ => Objects::Error(ErrorObject::new(format!(
+                    "{} cannot be applied to evaluated arguments",
+                    b.inspect()
+                ))),
             }
         }
         Objects::Function(func) => {
-            let extended_env = extend_function_env(&func, arguments);
-            if extended_env.is_none() {
-                return Objects::Error(ErrorObject::new(
-                    "Invalid number of arguments to function".into(),
-                ));
+            let sandbox = Rc::clone(func.environment().borrow().sandbox());
+            if let Err(err) = sandbox.allocate() {
+                return Objects::Error(err);
+            }
+            let extended_env = match extend_function_env(func, arguments, named) {
+                Ok(env) => env,
+                Err(err) => return err,
+            };
+            if let Err(err) = sandbox.enter_call() {
+                return Objects::Error(err);
             }
-            let extended_env = extended_env.unwrap();
             let evaluated = eval_block_statement(&mut func.body_mut(), extended_env);
-            return evaluated.expect("Expected an Objects value but evaluated to None");
+            sandbox.exit_call();
+            let heap = Rc::clone(func.environment().borrow().heap());
+            if heap.should_collect() {
+                heap.collect();
+            }
+            return match evaluated {
+                Some(Objects::Return(value)) => *value.value().clone(),
+                Some(evaluated) => evaluated,
+                None => Objects::Null(NULL),
+            };
         }
+        Objects::Constructor(_) if !named.is_empty() => Objects::Error(ErrorObject::new(
+            String::from("enum variants do not accept named arguments"),
+        )),
+        Objects::Constructor(c) => c.construct(arguments),
         _ => Objects::Error(ErrorObject::new(
             format!("not a function: {}", func.obj_type()).into(),
         )),
     }
 }
 
+// Binds positional and named arguments, fills in defaults and collects `...rest`
 fn extend_function_env(
-    func: &Function,
-    args: &mut Vec<Objects>,
-) -> Option<Rc<RefCell<Environment>>> {
-    let mut extended_env = Environment::new_enclosed_environment(Rc::clone(&func.environment()));
-    for (_, p) in func.parameters().iter().enumerate() {
-        let next = args.remove(0);
-        extended_env.set(p.value().into(), next);
+    func: &mut Function,
+    args: &[Objects],
+    named: &[(String, Objects)],
+) -> Result<Rc<RefCell<Environment>>, Objects> {
+    check_arguments(func.parameters(), args.len(), named)?;
+    let outer = Rc::clone(func.environment());
+    let extended_env = Rc::new(RefCell::new(Environment::new_enclosed_environment(
+        Rc::clone(&outer),
+    )));
+    outer.borrow().heap().track(&extended_env);
+    let mut positional = args.iter();
+    for (i, param) in func.parameters_mut().iter_mut().enumerate() {
+        let given = param
+            .name()
+            .and_then(|name| named.iter().find(|(n, _)| n == name.value()));
+        let value = if param.is_rest() {
+            let rest = Objects::Array(Array::new(positional.by_ref().cloned().collect()));
+            track_allocation(rest, &extended_env)
+        } else if let Some(arg) = positional.next() {
+            arg.clone()
+        } else if let Some((_, arg)) = given {
+            arg.clone()
+        } else if let Some(default) = param.default_mut() {
+            // Defaults see the parameters bound before them
+            eval_expression(default, Rc::clone(&extended_env))
+        } else {
+            unreachable!("check_arguments lets no parameter go without a value")
+        };
+        if value.is_unwinding() {
+            return Err(value);
+        }
+        let mut bindings = Vec::new();
+        match match_pattern(param.pattern_mut(), &value, &extended_env, &mut bindings) {
+            Ok(()) => {}
+            Err(PatternError::Mismatch(msg)) => {
+                return Err(Objects::Error(ErrorObject::new(format!(
+                    "cannot bind argument {} to {}: {}",
+                    i + 1,
+                    param,
+                    msg
+                ))));
+            }
+            Err(PatternError::Error(err)) => return Err(err),
+        }
+        for (name, value) in bindings {
+            extended_env.borrow_mut().set(name, value);
+        }
+    }
+    Ok(extended_env)
+}
+
+fn check_arguments(
+    params: &[Parameter],
+    positional: usize,
+    named: &[(String, Objects)],
+) -> Result<(), Objects> {
+    for (i, (name, _)) in named.iter().enumerate() {
+        let index = params
+            .iter()
+            .position(|p| !p.is_rest() && p.name().is_some_and(|n| n.value() == name));
+        let message = match index {
+            None => format!("unknown named argument '{}'", name),
+            Some(index) if index < positional || named[..i].iter().any(|(n, _)| n == name) => {
+                format!("argument '{}' was given more than once", name)
+            }
+            Some(_) => continue,
+        };
+        return Err(Objects::Error(ErrorObject::new(message)));
+    }
+    let has_rest = params.iter().any(|p| p.is_rest());
+    let max = params.iter().filter(|p| !p.is_rest()).count();
+    let min = params
+        .iter()
+        .filter(|p| !p.is_rest() && p.default().is_none())
+        .count();
+    let missing = params.iter().enumerate().any(|(i, p)| {
+        i >= positional
+            && !p.is_rest()
+            && p.default().is_none()
+            && !p
+                .name()
+                .is_some_and(|n| named.iter().any(|(name, _)| name == n.value()))
+    });
+    if !missing && (has_rest || positional <= max) {
+        return Ok(());
+    }
+    let expected = if has_rest {
+        format!("at least {}", min)
+    } else if min == max {
+        min.to_string()
+    } else {
+        format!("{} to {}", min, max)
+    };
+    Err(Objects::Error(ErrorObject::new(format!(
+        "wrong number of arguments: expected {} but received {}",
+        expected,
+        positional + named.len()
+    ))))
+}
+
+// Evaluates the imported file once and hands out the same module afterwards
+fn eval_import(import: &ImportStatement, env: &Rc<RefCell<Environment>>) -> Objects {
+    if let Some(module) = stdlib::module(import.path()) {
+        return Objects::Module(module);
+    }
+    let (dir, modules) = {
+        let env = env.borrow();
+        (env.base_dir().to_path_buf(), Rc::clone(env.modules()))
+    };
+    let path = match dir.join(import.path()).canonicalize() {
+        Ok(path) => path,
+        Err(err) => {
+            return Objects::Error(ErrorObject::new(format!(
+                "cannot import {:?}: {}",
+                import.path(),
+                err
+            )))
+        }
+    };
+    if let Some(module) = modules.get(&path) {
+        return module;
+    }
+    if let Err(cycle) = modules.begin(&path) {
+        let chain: Vec<String> = cycle.iter().map(|p| p.display().to_string()).collect();
+        return Objects::Error(ErrorObject::new(format!(
+            "import cycle: {}",
+            chain.join(" -> ")
+        )));
+    }
+    let module = load_module(&path, env);
+    modules.finish(&path);
+    if !module.is_err() {
+        modules.insert(path, module.clone());
+    }
+    module
+}
+
+fn load_module(path: &Path, env: &Rc<RefCell<Environment>>) -> Objects {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            return Objects::Error(ErrorObject::new(format!(
+                "cannot import {}: {}",
+                path.display(),
+                err
+            )))
+        }
+    };
+    let mut parser = Parser::new(Lexer::new(source));
+    let mut program = match parser.parse_program() {
+        Ok(program) if parser.errors().is_empty() => program,
+        _ => {
+            return Objects::Error(ErrorObject::new(format!(
+                "cannot parse {}: {}",
+                path.display(),
+                parser.errors().join("; ")
+            )))
+        }
+    };
+    let dir = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let module_env = Rc::new(RefCell::new(Environment::new_module_environment(
+        &env.borrow(),
+        dir,
+    )));
+    // Not eval_program, the importer's sandbox counters keep running
+    match eval_statements(&mut program.statements, Rc::clone(&module_env)) {
+        Some(Objects::Error(err)) if err.kind() == ErrorKind::Runtime => {
+            return Objects::Error(ErrorObject::new(format!(
+                "in module {}: {}",
+                path.display(),
+                err.message()
+            )))
+        }
+        Some(err @ Objects::Error(_)) => return err,
+        _ => {}
+    }
+    let exports = module_env.borrow().exported();
+    Objects::Module(Module::new(&path.display().to_string(), exports))
+}
+
+// Unwraps Ok and Some, anything else ends the enclosing function with a Return
+fn eval_try(value: Objects) -> Objects {
+    let builtin = match &value {
+        Objects::Enum(e) => matches!(e.definition().name(), "Option" | "Result"),
+        _ => false,
+    };
+    match value {
+        Objects::Enum(e) if builtin && matches!(e.variant(), "Ok" | "Some") => {
+            e.values()[0].clone()
+        }
+        Objects::Enum(_) if builtin => Objects::Return(Return::new(value)),
+        _ => Objects::Error(ErrorObject::new(format!(
+            "the ? operator expects an Option or Result, got {}",
+            value.obj_type()
+        ))),
+    }
+}
+
+fn eval_named_arguments(
+    named: &mut [(Identifier, Expressions)],
+    env: &Rc<RefCell<Environment>>,
+) -> Result<Vec<(String, Objects)>, Objects> {
+    let mut evaluated = Vec::new();
+    for (name, arg) in named {
+        let value = eval_expression(arg, Rc::clone(env));
+        if value.is_unwinding() {
+            return Err(value);
+        }
+        evaluated.push((name.value().to_string(), value));
+    }
+    Ok(evaluated)
+}
+
+// `receiver.name(args)` tries the methods of the receiver's type first and
+// then falls back to calling `name(receiver, args)`
+fn eval_method_call(call: &mut CallExpression, env: &Rc<RefCell<Environment>>) -> Objects {
+    let (receiver, name) = match call.function_mut() {
+        Expressions::FieldAccess(access) => {
+            let receiver = eval_expression(access.object_mut(), Rc::clone(env));
+            (receiver, access.field().value().to_string())
+        }
+        _ => unreachable!("method calls are made on a field access"),
+    };
+    if receiver.is_unwinding() {
+        return receiver;
+    }
+    let mut args = eval_expressions(call.arguments_mut(), Rc::clone(env));
+    if args.len() == 1 && args[0].is_unwinding() {
+        return args.remove(0);
+    }
+    let named = match eval_named_arguments(call.named_mut(), env) {
+        Ok(named) => named,
+        Err(err) => return err,
+    };
+    match &receiver {
+        Objects::Struct(s) => {
+            if let Some(mut method) = s.definition().method(&name) {
+                args.insert(0, receiver.clone());
+                return apply_function(&mut method, &mut args, &named);
+            }
+            // A field holding a function is called without the receiver
+            if let Some(field) = s.get(&name) {
+                return apply_tracked(&mut field.clone(), &mut args, &named, env);
+            }
+        }
+        Objects::StructType(t) => {
+            if let Some(mut method) = t.method(&name) {
+                return apply_function(&mut method, &mut args, &named);
+            }
+        }
+        Objects::Enum(e) => {
+            if let Some(mut method) = e.definition().method(&name) {
+                args.insert(0, receiver.clone());
+                return apply_function(&mut method, &mut args, &named);
+            }
+        }
+        Objects::EnumType(t) => {
+            if let Some(mut method) = t.method(&name).or_else(|| t.variant(&name)) {
+                return apply_function(&mut method, &mut args, &named);
+            }
+        }
+        // Exported functions are called as they are, the module is not an argument
+        Objects::Module(m) => {
+            if let Some(mut member) = m.get(&name) {
+                return apply_tracked(&mut member, &mut args, &named, env);
+            }
+            return Objects::Error(ErrorObject::new(format!(
+                "module {} has no export '{}'",
+                m.path(),
+                name
+            )));
+        }
+        Objects::Native(n) if named.is_empty() => {
+            if let Some(result) = n.call_method(&name, &args) {
+                return result;
+            }
+        }
+        _ if named.is_empty() => {
+            if let Some(mut method) = stdlib::method(&receiver, &name) {
+                args.insert(0, receiver);
+                return apply_tracked(&mut method, &mut args, &named, env);
+            }
+            if let Some(result) = call_builtin_method(&receiver, &name, &args, env) {
+                return track_allocation(result, env);
+            }
+        }
+        _ => {}
+    }
+    let func = env.borrow().get(name.clone());
+    match func {
+        Some(mut func @ (Objects::Function(_) | Objects::Builtin(_))) => {
+            args.insert(0, receiver);
+            apply_tracked(&mut func, &mut args, &named, env)
+        }
+        _ => Objects::Error(ErrorObject::new(format!(
+            "undefined method '{}' for {}",
+            name,
+            receiver.obj_type()
+        ))),
+    }
+}
+
+fn expect_method_args(name: &str, args: &[Objects], count: usize) -> Result<(), Objects> {
+    if args.len() == count {
+        return Ok(());
+    }
+    Err(Objects::Error(ErrorObject::new(format!(
+        "{} expects {} argument(s) but received {}",
+        name,
+        count,
+        args.len()
+    ))))
+}
+
+pub fn is_equal(left: &Objects, right: &Objects) -> bool {
+    matches!(
+        eval_infix_expression("==", left.clone(), right.clone()),
+        Objects::Boolean(b) if *b.value()
+    )
+}
+
+// Methods that call back into the evaluator or have no module of their own,
+// None when the receiver has no such method
+fn call_builtin_method(
+    receiver: &Objects,
+    name: &str,
+    args: &[Objects],
+    env: &Rc<RefCell<Environment>>,
+) -> Option<Objects> {
+    let arity = match (receiver, name) {
+        (Objects::Array(_), "map" | "filter") => 1,
+        (Objects::Array(_), "reduce") => 2,
+        (Objects::Hash(_), "keys" | "values") => 0,
+        (Objects::Hash(_), "contains") => 1,
+        _ => return None,
+    };
+    if let Err(err) = expect_method_args(name, args, arity) {
+        return Some(err);
+    }
+    let result = match receiver {
+        Objects::Array(a) => {
+            let elements = a.elements();
+            match name {
+                "map" | "filter" => {
+                    let mut result = Vec::new();
+                    for element in elements {
+                        let mapped = apply_tracked(
+                            &mut args[0].clone(),
+                            &mut vec![element.clone()],
+                            &[],
+                            env,
+                        );
+                        if mapped.is_unwinding() {
+                            return Some(mapped);
+                        }
+                        if name == "map" {
+                            result.push(mapped);
+                        } else if is_truthy(&mapped) {
+                            result.push(element.clone());
+                        }
+                    }
+                    Objects::Array(Array::new(result))
+                }
+                _ => {
+                    let mut acc = args[0].clone();
+                    for element in elements {
+                        acc = apply_tracked(
+                            &mut args[1].clone(),
+                            &mut vec![acc, element.clone()],
+                            &[],
+                            env,
+                        );
+                        if acc.is_unwinding() {
+                            return Some(acc);
+                        }
+                    }
+                    acc
+                }
+            }
+        }
+        Objects::Hash(h) => match name {
+            "keys" => Objects::Array(Array::new(
+                h.pairs().iter().map(|(k, _)| k.clone()).collect(),
+            )),
+            "values" => Objects::Array(Array::new(
+                h.pairs().iter().map(|(_, v)| v.clone()).collect(),
+            )),
+            _ => Objects::Boolean(bool_helper(h.get(&args[0]).is_some())),
+        },
+        _ => unreachable!(),
+    };
+    Some(result)
+}
+
+fn eval_struct_literal(literal: &mut StructLiteral, env: &Rc<RefCell<Environment>>) -> Objects {
+    let name = literal.name().value().to_string();
+    let definition = match env.borrow().get(name.clone()) {
+        Some(Objects::StructType(definition)) => definition,
+        Some(other) => {
+            return Objects::Error(ErrorObject::new(format!(
+                "{} is not a struct but {}",
+                name,
+                other.obj_type()
+            )))
+        }
+        None => return Objects::Error(ErrorObject::new(format!("unknown struct: {}", name))),
+    };
+    let mut values: Vec<Option<Objects>> = vec![None; definition.fields().len()];
+    for (field, expression) in literal.fields_mut() {
+        let index = match definition.fields().iter().position(|f| f == field.value()) {
+            Some(index) => index,
+            None => {
+                return Objects::Error(ErrorObject::new(format!(
+                    "{} has no field '{}'",
+                    name, field
+                )))
+            }
+        };
+        if values[index].is_some() {
+            return Objects::Error(ErrorObject::new(format!(
+                "field '{}' is specified more than once",
+                field
+            )));
+        }
+        let value = eval_expression(expression, Rc::clone(env));
+        if value.is_unwinding() {
+            return value;
+        }
+        values[index] = Some(value);
+    }
+    if let Some(base) = literal.base_mut() {
+        let base = eval_expression(base, Rc::clone(env));
+        match &base {
+            Objects::Error(_) => return base,
+            Objects::Struct(s) if s.name() == name => {
+                for (slot, value) in values.iter_mut().zip(s.values()) {
+                    slot.get_or_insert_with(|| value.clone());
+                }
+            }
+            _ => {
+                return Objects::Error(ErrorObject::new(format!(
+                    "cannot update {} from {}",
+                    name,
+                    base.obj_type()
+                )))
+            }
+        }
+    }
+    let mut fields = Vec::new();
+    for (field, value) in definition.fields().iter().zip(values) {
+        match value {
+            Some(value) => fields.push(value),
+            None => {
+                return Objects::Error(ErrorObject::new(format!(
+                    "missing field '{}' in {}",
+                    field, name
+                )))
+            }
+        }
     }
-    Some(Rc::new(RefCell::new(extended_env)))
+    Objects::Struct(Struct::new(definition, fields))
 }
 
 fn eval_expressions(
@@ -268,8 +1077,23 @@ fn eval_expressions(
 ) -> Vec<Objects> {
     let mut v = Vec::new();
     for ex in expressions.iter_mut() {
+        if let Expressions::SpreadExpression(spread) = ex {
+            let evaluated = eval_expression(spread.value_mut(), Rc::clone(&env));
+            match evaluated {
+                Objects::Array(array) => v.extend(array.elements().iter().cloned()),
+                _ if evaluated.is_unwinding() => return [evaluated].to_vec(),
+                other => {
+                    return [Objects::Error(ErrorObject::new(format!(
+                        "cannot spread {}, expected an array",
+                        other.obj_type()
+                    )))]
+                    .to_vec()
+                }
+            }
+            continue;
+        }
         let evaluated = eval_expression(ex, Rc::clone(&env));
-        if evaluated.is_err() {
+        if evaluated.is_unwinding() {
             return [evaluated].to_vec();
         }
         v.push(evaluated);
@@ -292,19 +1116,66 @@ fn eval_bang_operator_expression(exp: &Objects) -> Objects {
 }
 
 fn eval_minus_operator_expression(exp: &Objects) -> Objects {
-    match exp.obj_type() {
-        ObjectTypes::Integer => {
-            let v: isize = exp.inspect().parse().expect("Value was not an isize");
-            Objects::Integer(Integer::new(-v))
-        }
+    match exp {
+        Objects::Integer(v) => match v.value().checked_neg() {
+            Some(negated) => Objects::Integer(Integer::new(negated)),
+            None => BigInteger::normalize(-&BigInt::from_isize(*v.value())),
+        },
+        Objects::BigInteger(v) => BigInteger::normalize(-v.value()),
+        Objects::Float(v) => Objects::Float(Float::new(-v.value())),
         _ => Objects::Error(ErrorObject::new(format!("unknown operator: -{}", exp))),
     }
 }
 
+fn eval_tilde_operator_expression(exp: &Objects) -> Objects {
+    match exp {
+        Objects::Integer(v) => Objects::Integer(Integer::new(!v.value())),
+        Objects::BigInteger(v) => BigInteger::normalize(&(-v.value()) - &BigInt::from_isize(1)),
+        _ => Objects::Error(ErrorObject::new(format!("unknown operator: ~{}", exp))),
+    }
+}
+
+fn eval_index_expression(left: Objects, index: Objects) -> Objects {
+    match (&left, &index) {
+        (Objects::String(s), Objects::Integer(i)) => {
+            // Strings are indexed by Unicode scalar value rather than by byte
+            match usize::try_from(*i.value())
+                .ok()
+                .and_then(|i| s.value().chars().nth(i))
+            {
+                Some(ch) => Objects::Char(Char::new(ch)),
+                None => Objects::Null(NULL),
+            }
+        }
+        (Objects::Array(a), Objects::Integer(i)) => {
+            match usize::try_from(*i.value())
+                .ok()
+                .and_then(|i| a.elements().get(i))
+            {
+                Some(element) => element.clone(),
+                None => Objects::Null(NULL),
+            }
+        }
+        (Objects::Hash(h), _) => match HashKey::from_object(&index) {
+            Some(_) => h.get(&index).cloned().unwrap_or(Objects::Null(NULL)),
+            None => Objects::Error(ErrorObject::new(format!(
+                "unusable as hash key: {}",
+                index.obj_type()
+            ))),
+        },
+        _ => Objects::Error(ErrorObject::new(format!(
+            "index operator not supported: {}[{}]",
+            left.obj_type(),
+            index.obj_type()
+        ))),
+    }
+}
+
 fn eval_prefix_expression(operator: &str, right: &Objects) -> Objects {
     match operator {
         "!" => eval_bang_operator_expression(right),
         "-" => eval_minus_operator_expression(right),
+        "~" => eval_tilde_operator_expression(right),
         _ => Objects::Error(ErrorObject::new(format!(
             "unknown operator: {}{}",
             operator, right
@@ -313,22 +1184,35 @@ fn eval_prefix_expression(operator: &str, right: &Objects) -> Objects {
 }
 
 fn eval_infix_expression(operator: &str, left: Objects, right: Objects) -> Objects {
-    if left.obj_type() == ObjectTypes::Integer && right.obj_type() == ObjectTypes::Integer {
-        let left = left.as_integer().unwrap();
-        let right = right.as_integer().unwrap();
-        return eval_integer_infix_expression(operator, &left, &right);
+    if let (Objects::Integer(l), Objects::Integer(r)) = (&left, &right) {
+        return eval_integer_infix_expression(operator, l, r);
+    } else if left.obj_type() == ObjectTypes::Integer && right.obj_type() == ObjectTypes::Integer {
+        let l = as_big_integer(&left).expect("Could not cast to BigInt");
+        let r = as_big_integer(&right).expect("Could not cast to BigInt");
+        return eval_big_integer_infix_expression(operator, &l, &r, &left, &right);
+    } else if let (Some(l), Some(r)) = (as_number(&left), as_number(&right)) {
+        return eval_float_infix_expression(operator, l, r, &left, &right);
     } else if left.obj_type() == ObjectTypes::String && right.obj_type() == ObjectTypes::String {
         let left = left.as_str().expect("Could not cast to String");
         let right = right.as_str().expect("Could not cast to String");
         return eval_string_infix_expression(operator, &left, &right);
-    } else if operator == "==" {
-        let left = left.as_boolean().expect("Could not cast to Boolean");
-        let right = right.as_boolean().expect("Could not cast to Boolean");
-        Objects::Boolean(bool_helper(left == right))
-    } else if operator == "!=" {
-        let left = left.as_boolean().expect("Could not cast to Boolean");
-        let right = right.as_boolean().expect("Could not cast to Boolean");
-        Objects::Boolean(bool_helper(left != right))
+    } else if let (Objects::Char(l), Objects::Char(r)) = (&left, &right) {
+        return eval_char_infix_expression(operator, l, r);
+    } else if let (Some(l), Some(r), "+") = (as_text(&left), as_text(&right), operator) {
+        return Objects::String(StringObject::new(format!("{}{}", l, r)));
+    } else if let (Objects::Native(l), Objects::Native(r)) = (&left, &right) {
+        return eval_native_infix_expression(operator, l, r);
+    } else if let (Objects::Enum(l), Objects::Enum(r)) = (&left, &right) {
+        return eval_enum_infix_expression(operator, l, r);
+    } else if let (Objects::Struct(l), Objects::Struct(r)) = (&left, &right) {
+        return eval_struct_infix_expression(operator, l, r);
+    } else if let (Objects::Boolean(l), Objects::Boolean(r)) = (&left, &right) {
+        return eval_boolean_infix_expression(operator, l, r);
+    } else if left.is_null() && right.is_null() && (operator == "==" || operator == "!=") {
+        Objects::Boolean(bool_helper(operator == "=="))
+    } else if left.obj_type() != right.obj_type() && (operator == "==" || operator == "!=") {
+        // Values of different types are never equal
+        Objects::Boolean(bool_helper(operator == "!="))
     } else if left.obj_type() != right.obj_type() {
         Objects::Error(ErrorObject::new(format!(
             "type mismatch: {} {} {}",
@@ -342,6 +1226,51 @@ fn eval_infix_expression(operator: &str, left: Objects, right: Objects) -> Objec
     }
 }
 
+fn eval_enum_infix_expression(operator: &str, left: &EnumValue, right: &EnumValue) -> Objects {
+    let equal = || {
+        left.definition().same_type(right.definition())
+            && left.variant() == right.variant()
+            && left.values().len() == right.values().len()
+            && left
+                .values()
+                .iter()
+                .zip(right.values())
+                .all(|(l, r)| is_equal(l, r))
+    };
+    match operator {
+        "==" => Objects::Boolean(bool_helper(equal())),
+        "!=" => Objects::Boolean(bool_helper(!equal())),
+        _ => Objects::Error(ErrorObject::new(format!(
+            "unknown operator: {} {} {}",
+            left.obj_type(),
+            operator,
+            right.obj_type()
+        ))),
+    }
+}
+
+// Instances of the same declaration are equal when all their fields are
+fn eval_struct_infix_expression(operator: &str, left: &Struct, right: &Struct) -> Objects {
+    let equal = || {
+        left.definition() == right.definition()
+            && left
+                .values()
+                .iter()
+                .zip(right.values())
+                .all(|(l, r)| is_equal(l, r))
+    };
+    match operator {
+        "==" => Objects::Boolean(bool_helper(equal())),
+        "!=" => Objects::Boolean(bool_helper(!equal())),
+        _ => Objects::Error(ErrorObject::new(format!(
+            "unknown operator: {} {} {}",
+            left.obj_type(),
+            operator,
+            right.obj_type()
+        ))),
+    }
+}
+
 fn eval_string_infix_expression(
     operator: &str,
     left: &StringObject,
@@ -353,6 +1282,12 @@ fn eval_string_infix_expression(
             &left.value(),
             &right.value()
         ))),
+        "<" => Objects::Boolean(bool_helper(left.value() < right.value())),
+        ">" => Objects::Boolean(bool_helper(left.value() > right.value())),
+        "<=" => Objects::Boolean(bool_helper(left.value() <= right.value())),
+        ">=" => Objects::Boolean(bool_helper(left.value() >= right.value())),
+        "==" => Objects::Boolean(bool_helper(left.value() == right.value())),
+        "!=" => Objects::Boolean(bool_helper(left.value() != right.value())),
         _ => Objects::Error(ErrorObject::new(format!(
             "unknown operator: {} {} {}",
             left, operator, right
@@ -360,16 +1295,19 @@ fn eval_string_infix_expression(
     }
 }
 
-fn eval_integer_infix_expression(operator: &str, left: &Integer, right: &Integer) -> Objects {
+fn eval_char_infix_expression(operator: &str, left: &Char, right: &Char) -> Objects {
     match operator {
-        "+" => Objects::Integer(Integer::new(left.value() + right.value())),
-        "-" => Objects::Integer(Integer::new(left.value() - right.value())),
-        "*" => Objects::Integer(Integer::new(left.value() * right.value())),
-        "/" => Objects::Integer(Integer::new(left.value() / right.value())),
-        "<" => Objects::Boolean(Boolean::new(left.value() < right.value())),
-        ">" => Objects::Boolean(Boolean::new(left.value() > right.value())),
-        "==" => Objects::Boolean(Boolean::new(left.value() == right.value())),
-        "!=" => Objects::Boolean(Boolean::new(left.value() != right.value())),
+        "+" => Objects::String(StringObject::new(format!(
+            "{}{}",
+            left.value(),
+            right.value()
+        ))),
+        "<" => Objects::Boolean(bool_helper(left.value() < right.value())),
+        ">" => Objects::Boolean(bool_helper(left.value() > right.value())),
+        "<=" => Objects::Boolean(bool_helper(left.value() <= right.value())),
+        ">=" => Objects::Boolean(bool_helper(left.value() >= right.value())),
+        "==" => Objects::Boolean(bool_helper(left.value() == right.value())),
+        "!=" => Objects::Boolean(bool_helper(left.value() != right.value())),
         _ => Objects::Error(ErrorObject::new(format!(
             "unknown operator: {} {} {}",
             left, operator, right
@@ -377,46 +1315,481 @@ fn eval_integer_infix_expression(operator: &str, left: &Integer, right: &Integer
     }
 }
 
-fn eval_if_expression(exp: &mut IfExpression, env: Rc<RefCell<Environment>>) -> Option<Objects> {
-    let condition = eval_expression(&mut exp.condition_mut(), Rc::clone(&env));
-    if condition.is_err() {
-        return Some(condition);
-    }
-    if is_truthy(&condition) {
-        return eval_block_statement(&mut exp.consequence_mut(), Rc::clone(&env));
-    } else if exp.alternative().is_some() {
-        return eval_block_statement(&mut exp.alternative_mut().unwrap(), Rc::clone(&env));
-    } else {
-        return Some(Objects::Null(NULL));
+// Chars concatenate with strings
+fn as_text(obj: &Objects) -> Option<String> {
+    match obj {
+        Objects::String(x) => Some(x.value().to_string()),
+        Objects::Char(x) => Some(x.value().to_string()),
+        _ => None,
     }
 }
 
-fn is_truthy(obj: &Objects) -> bool {
-    match obj.obj_type() {
-        ObjectTypes::Integer => true,
-        ObjectTypes::Null => false,
-        ObjectTypes::Boolean => obj
-            .inspect()
-            .parse::<bool>()
-            .expect("object was not a Boolean"),
-        ObjectTypes::Return => false,
-        _ => false,
+// Integers are promoted to floats when mixed with one
+fn as_number(obj: &Objects) -> Option<f64> {
+    match obj {
+        Objects::Integer(x) => Some(*x.value() as f64),
+        Objects::BigInteger(x) => Some(x.value().to_f64()),
+        Objects::Float(x) => Some(*x.value()),
+        _ => None,
     }
 }
 
-pub fn eval_program(node: &mut Program, env: Rc<RefCell<Environment>>) -> Option<Objects> {
-    eval_statements(&mut node.statements, env)
+fn eval_float_infix_expression(
+    operator: &str,
+    left: f64,
+    right: f64,
+    left_obj: &Objects,
+    right_obj: &Objects,
+) -> Objects {
+    match operator {
+        "+" => Objects::Float(Float::new(left + right)),
+        "-" => Objects::Float(Float::new(left - right)),
+        "*" => Objects::Float(Float::new(left * right)),
+        "/" => Objects::Float(Float::new(left / right)),
+        "%" => Objects::Float(Float::new(left % right)),
+        "**" => Objects::Float(Float::new(left.powf(right))),
+        "<" => Objects::Boolean(bool_helper(left < right)),
+        ">" => Objects::Boolean(bool_helper(left > right)),
+        "<=" => Objects::Boolean(bool_helper(left <= right)),
+        ">=" => Objects::Boolean(bool_helper(left >= right)),
+        "==" => Objects::Boolean(bool_helper(left == right)),
+        "!=" => Objects::Boolean(bool_helper(left != right)),
+        _ => Objects::Error(ErrorObject::new(format!(
+            "unknown operator: {} {} {}",
+            left_obj, operator, right_obj
+        ))),
+    }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::{lexer::Lexer, parser::Parser};
-
-    enum Types {
-        Integer(isize),
-        String(String),
-        Null,
+fn eval_boolean_infix_expression(operator: &str, left: &Boolean, right: &Boolean) -> Objects {
+    match operator {
+        "==" => Objects::Boolean(bool_helper(left == right)),
+        "!=" => Objects::Boolean(bool_helper(left != right)),
+        _ => Objects::Error(ErrorObject::new(format!(
+            "unknown operator: {} {} {}",
+            left, operator, right
+        ))),
+    }
+}
+
+fn eval_native_infix_expression(operator: &str, left: &Native, right: &Native) -> Objects {
+    match operator {
+        "==" => Objects::Boolean(bool_helper(left == right)),
+        "!=" => Objects::Boolean(bool_helper(left != right)),
+        _ => Objects::Error(ErrorObject::new(format!(
+            "unknown operator: {} {} {}",
+            left, operator, right
+        ))),
+    }
+}
+
+fn as_big_integer(obj: &Objects) -> Option<BigInt> {
+    match obj {
+        Objects::Integer(x) => Some(BigInt::from_isize(*x.value())),
+        Objects::BigInteger(x) => Some(x.value().clone()),
+        _ => None,
+    }
+}
+
+fn eval_big_integer_infix_expression(
+    operator: &str,
+    left: &BigInt,
+    right: &BigInt,
+    left_obj: &Objects,
+    right_obj: &Objects,
+) -> Objects {
+    match operator {
+        "+" => BigInteger::normalize(left + right),
+        "-" => BigInteger::normalize(left - right),
+        "*" => match check_digits(left.log10() + right.log10()) {
+            Ok(()) => BigInteger::normalize(left * right),
+            Err(err) => err,
+        },
+        "/" => match left.div_rem(right) {
+            Some((quotient, _)) => BigInteger::normalize(quotient),
+            None => Objects::Error(ErrorObject::new(String::from("division by zero"))),
+        },
+        "%" => match left.div_rem(right) {
+            Some((_, remainder)) => BigInteger::normalize(remainder),
+            None => Objects::Error(ErrorObject::new(String::from("modulo by zero"))),
+        },
+        "**" if right.is_negative() => {
+            Objects::Float(Float::new(left.to_f64().powf(right.to_f64())))
+        }
+        "**" => match right.to_isize().and_then(|r| u32::try_from(r).ok()) {
+            Some(exponent) => match check_digits(left.log10() * exponent as f64) {
+                Ok(()) => BigInteger::normalize(left.pow(exponent)),
+                Err(err) => err,
+            },
+            None => Objects::Error(ErrorObject::new(format!("exponent too large: {}", right))),
+        },
+        "<<" | ">>" if right.is_negative() => Objects::Error(ErrorObject::new(format!(
+            "negative shift amount: {}",
+            right
+        ))),
+        "<<" if left.is_zero() => Objects::Integer(Integer::new(0)),
+        "<<" => match right.to_isize().and_then(|r| u32::try_from(r).ok()) {
+            Some(shift) => match check_digits(left.log10() + shift as f64 * 2f64.log10()) {
+                Ok(()) => BigInteger::normalize(left * &BigInt::from_isize(2).pow(shift)),
+                Err(err) => err,
+            },
+            None => Objects::Error(ErrorObject::new(format!(
+                "shift amount too large: {}",
+                right
+            ))),
+        },
+        // Arithmetic shift, rounds towards negative infinity
+        ">>" => match right
+            .to_isize()
+            .and_then(|r| u32::try_from(r).ok())
+            // Shifting out every digit, the power of two is never built
+            .filter(|shift| (*shift as f64 * 2f64.log10()) <= left.log10() + 1.0)
+        {
+            Some(shift) => {
+                let (quotient, remainder) = left
+                    .div_rem(&BigInt::from_isize(2).pow(shift))
+                    .expect("power of two was zero");
+                if remainder.is_negative() {
+                    BigInteger::normalize(&quotient - &BigInt::from_isize(1))
+                } else {
+                    BigInteger::normalize(quotient)
+                }
+            }
+            None if left.is_negative() => Objects::Integer(Integer::new(-1)),
+            None => Objects::Integer(Integer::new(0)),
+        },
+        "&" | "|" | "^" => Objects::Error(ErrorObject::new(format!(
+            "integer too large for bitwise operator: {} {} {}",
+            left, operator, right
+        ))),
+        "<" => Objects::Boolean(bool_helper(left < right)),
+        ">" => Objects::Boolean(bool_helper(left > right)),
+        "<=" => Objects::Boolean(bool_helper(left <= right)),
+        ">=" => Objects::Boolean(bool_helper(left >= right)),
+        "==" => Objects::Boolean(bool_helper(left == right)),
+        "!=" => Objects::Boolean(bool_helper(left != right)),
+        _ => Objects::Error(ErrorObject::new(format!(
+            "unknown operator: {} {} {}",
+            left_obj, operator, right_obj
+        ))),
+    }
+}
+
+// Checked before an operator builds a number with about this many digits
+fn check_digits(digits: f64) -> Result<(), Objects> {
+    if digits > MAX_DIGITS as f64 {
+        return Err(Objects::Error(ErrorObject::limit_exceeded(
+            Limit::Allocations,
+            format!("integer too large: over {} digits", MAX_DIGITS),
+        )));
+    }
+    Ok(())
+}
+
+fn eval_integer_infix_expression(operator: &str, left: &Integer, right: &Integer) -> Objects {
+    let (l, r) = (*left.value(), *right.value());
+    let result = match operator {
+        "+" => l.checked_add(r),
+        "-" => l.checked_sub(r),
+        "*" => l.checked_mul(r),
+        "/" => l.checked_div(r),
+        "%" => l.checked_rem(r),
+        "**" => u32::try_from(r).ok().and_then(|r| l.checked_pow(r)),
+        "<<" => u32::try_from(r)
+            .ok()
+            .and_then(|r| l.checked_shl(r))
+            .filter(|shifted| shifted >> r == l),
+        ">>" => u32::try_from(r).ok().map(|r| l >> r.min(isize::BITS - 1)),
+        "&" => return Objects::Integer(Integer::new(l & r)),
+        "|" => return Objects::Integer(Integer::new(l | r)),
+        "^" => return Objects::Integer(Integer::new(l ^ r)),
+        "<" => return Objects::Boolean(Boolean::new(l < r)),
+        ">" => return Objects::Boolean(Boolean::new(l > r)),
+        "<=" => return Objects::Boolean(Boolean::new(l <= r)),
+        ">=" => return Objects::Boolean(Boolean::new(l >= r)),
+        "==" => return Objects::Boolean(Boolean::new(l == r)),
+        "!=" => return Objects::Boolean(Boolean::new(l != r)),
+        _ => {
+            return Objects::Error(ErrorObject::new(format!(
+                "unknown operator: {} {} {}",
+                left, operator, right
+            )))
+        }
+    };
+    match result {
+        Some(value) => Objects::Integer(Integer::new(value)),
+        // Overflowed, divided by zero or needs a negative exponent or shift,
+        // the arbitrary precision version handles all of them
+        None => eval_big_integer_infix_expression(
+            operator,
+            &BigInt::from_isize(l),
+            &BigInt::from_isize(r),
+            &Objects::Integer(left.clone()),
+            &Objects::Integer(right.clone()),
+        ),
+    }
+}
+
+fn eval_if_expression(exp: &mut IfExpression, env: Rc<RefCell<Environment>>) -> Option<Objects> {
+    let condition = eval_expression(&mut exp.condition_mut(), Rc::clone(&env));
+    if condition.is_unwinding() {
+        return Some(condition);
+    }
+    if is_truthy(&condition) {
+        return eval_block_statement(&mut exp.consequence_mut(), Rc::clone(&env));
+    } else if exp.alternative().is_some() {
+        return eval_block_statement(&mut exp.alternative_mut().unwrap(), Rc::clone(&env));
+    } else {
+        return Some(Objects::Null(NULL));
+    }
+}
+
+fn eval_match_expression(exp: &mut MatchExpression, env: Rc<RefCell<Environment>>) -> Objects {
+    let subject = eval_expression(exp.subject_mut(), Rc::clone(&env));
+    if subject.is_unwinding() {
+        return subject;
+    }
+    for arm in exp.arms_mut() {
+        let mut bindings = Vec::new();
+        match match_pattern(arm.pattern_mut(), &subject, &env, &mut bindings) {
+            Ok(()) => {}
+            Err(PatternError::Mismatch(_)) => continue,
+            Err(PatternError::Error(err)) => return err,
+        }
+        let arm_env = bind_all(bindings, &env);
+        if let Some(guard) = arm.guard_mut() {
+            let guard = eval_expression(guard, Rc::clone(&arm_env));
+            if guard.is_unwinding() {
+                return guard;
+            }
+            if !is_truthy(&guard) {
+                continue;
+            }
+        }
+        return eval_block_statement(arm.body_mut(), arm_env).unwrap_or(Objects::Null(NULL));
+    }
+    Objects::Error(ErrorObject::new(format!(
+        "no match arm matched: {}",
+        subject.inspect()
+    )))
+}
+
+// Creates the enclosed environment holding the names bound by a pattern
+fn bind_all(
+    bindings: Vec<(String, Objects)>,
+    env: &Rc<RefCell<Environment>>,
+) -> Rc<RefCell<Environment>> {
+    let mut enclosed = Environment::new_enclosed_environment(Rc::clone(env));
+    for (name, value) in bindings {
+        enclosed.set(name, value);
+    }
+    let enclosed = Rc::new(RefCell::new(enclosed));
+    env.borrow().heap().track(&enclosed);
+    enclosed
+}
+
+enum PatternError {
+    // The value has a different shape, the message explains how
+    Mismatch(String),
+    // Evaluating a literal or key inside the pattern failed
+    Error(Objects),
+}
+
+fn match_pattern(
+    pattern: &mut Pattern,
+    value: &Objects,
+    env: &Rc<RefCell<Environment>>,
+    bindings: &mut Vec<(String, Objects)>,
+) -> Result<(), PatternError> {
+    match pattern {
+        Pattern::Wildcard => Ok(()),
+        Pattern::Binding(ident) => {
+            // A name that refers to a unit variant like `None` matches that variant
+            if let Some(Objects::Enum(unit)) = env.borrow().get(ident.value().into()) {
+                if unit.values().is_empty() && unit.variant() == ident.value() {
+                    return match value {
+                        Objects::Enum(v)
+                            if v.definition().same_type(unit.definition())
+                                && v.variant() == unit.variant() =>
+                        {
+                            Ok(())
+                        }
+                        _ => Err(PatternError::Mismatch(format!(
+                            "expected {} but got {}",
+                            unit.variant(),
+                            value.inspect()
+                        ))),
+                    };
+                }
+            }
+            bindings.push((ident.value().into(), value.clone()));
+            Ok(())
+        }
+        Pattern::Variant(name, fields) => {
+            let declared = match env.borrow().get(name.value().into()) {
+                Some(Objects::Constructor(c)) => Some(c.definition().clone()),
+                _ => None,
+            };
+            let variant = match value {
+                Objects::Enum(v)
+                    if v.variant() == name.value()
+                        && declared
+                            .as_ref()
+                            .is_none_or(|d| d.same_type(v.definition())) =>
+                {
+                    v
+                }
+                _ => {
+                    return Err(PatternError::Mismatch(format!(
+                        "expected {} but got {}",
+                        name,
+                        value.inspect()
+                    )))
+                }
+            };
+            if variant.values().len() != fields.len() {
+                return Err(PatternError::Mismatch(format!(
+                    "{} has {} field(s) but the pattern has {}",
+                    name,
+                    variant.values().len(),
+                    fields.len()
+                )));
+            }
+            for (pattern, field) in fields.iter_mut().zip(variant.values()) {
+                match_pattern(pattern, field, env, bindings)?;
+            }
+            Ok(())
+        }
+        Pattern::Literal(literal) => {
+            let expected = eval_expression(literal, Rc::clone(env));
+            if expected.is_unwinding() {
+                return Err(PatternError::Error(expected));
+            }
+            let equal = eval_infix_expression("==", expected.clone(), value.clone());
+            if is_truthy(&equal) {
+                Ok(())
+            } else {
+                Err(PatternError::Mismatch(format!(
+                    "expected {} but got {}",
+                    expected.inspect(),
+                    value.inspect()
+                )))
+            }
+        }
+        Pattern::Array { head, rest, tail } => {
+            let elements = match value {
+                Objects::Array(array) => array.elements(),
+                _ => {
+                    return Err(PatternError::Mismatch(format!(
+                        "cannot destructure {} as an array",
+                        value.obj_type()
+                    )))
+                }
+            };
+            let fixed = head.len() + tail.len();
+            if rest.is_none() && elements.len() != fixed {
+                return Err(PatternError::Mismatch(format!(
+                    "expected an array of {} elements but got {}",
+                    fixed,
+                    elements.len()
+                )));
+            }
+            if elements.len() < fixed {
+                return Err(PatternError::Mismatch(format!(
+                    "expected an array of at least {} elements but got {}",
+                    fixed,
+                    elements.len()
+                )));
+            }
+            let tail_start = elements.len() - tail.len();
+            for (pattern, element) in head.iter_mut().zip(elements) {
+                match_pattern(pattern, element, env, bindings)?;
+            }
+            if let Some(Some(ident)) = rest {
+                let rest = Array::new(elements[head.len()..tail_start].to_vec());
+                bindings.push((ident.value().into(), Objects::Array(rest)));
+            }
+            for (pattern, element) in tail.iter_mut().zip(&elements[tail_start..]) {
+                match_pattern(pattern, element, env, bindings)?;
+            }
+            Ok(())
+        }
+        Pattern::Hash(pairs) => {
+            let hash = match value {
+                Objects::Hash(hash) => hash,
+                _ => {
+                    return Err(PatternError::Mismatch(format!(
+                        "cannot destructure {} as a hash",
+                        value.obj_type()
+                    )))
+                }
+            };
+            for (key, pattern) in pairs.iter_mut() {
+                let key = eval_expression(key, Rc::clone(env));
+                if key.is_unwinding() {
+                    return Err(PatternError::Error(key));
+                }
+                match hash.get(&key) {
+                    Some(value) => match_pattern(pattern, value, env, bindings)?,
+                    None => {
+                        return Err(PatternError::Mismatch(format!(
+                            "missing key '{}' in hash",
+                            key.inspect()
+                        )))
+                    }
+                }
+            }
+            Ok(())
+        }
+        Pattern::Alternative(patterns) => {
+            let mut mismatch = None;
+            for pattern in patterns.iter_mut() {
+                let mut attempt = Vec::new();
+                match match_pattern(pattern, value, env, &mut attempt) {
+                    Ok(()) => {
+                        bindings.extend(attempt);
+                        return Ok(());
+                    }
+                    Err(PatternError::Mismatch(msg)) => mismatch = Some(msg),
+                    Err(err) => return Err(err),
+                }
+            }
+            Err(PatternError::Mismatch(mismatch.unwrap_or_default()))
+        }
+    }
+}
+
+fn is_truthy(obj: &Objects) -> bool {
+    match obj.obj_type() {
+        ObjectTypes::Integer => true,
+        ObjectTypes::Float => true,
+        ObjectTypes::Null => false,
+        ObjectTypes::Boolean => obj
+            .inspect()
+            .parse::<bool>()
+            .expect("object was not a Boolean"),
+        ObjectTypes::Return => false,
+        _ => false,
+    }
+}
+
+pub fn eval_program(node: &mut Program, env: Rc<RefCell<Environment>>) -> Option<Objects> {
+    env.borrow().sandbox().start();
+    eval_statements(&mut node.statements, env)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::object::{ErrorKind, Limit};
+    use crate::sandbox::Limits;
+    use crate::{lexer::Lexer, parser::Parser};
+    use std::time::Duration;
+
+    enum Types {
+        Integer(isize),
+        String(String),
+        Null,
     }
 
     fn test_eval(input: &str) -> Option<Objects> {
@@ -774,6 +2147,8 @@ mod test {
             (r#"len("")"#, Types::Integer(0)),
             (r#"len("hello")"#, Types::Integer(5)),
             (r#"len("    ")"#, Types::Integer(4)),
+            (r#"len("héllo")"#, Types::Integer(5)),
+            (r#"len("\u{1F600}\n")"#, Types::Integer(2)),
             (
                 r#"len(1)"#,
                 Types::String("argument to 'len' not supported, got INTEGER".into()),
@@ -799,7 +2174,6 @@ mod test {
                 r#"let len = fn(x) { 5 };"#,
                 Types::String("Cannot overwrite keyword: len".into()),
             ),
-
         ];
 
         for (input, exp) in inputs {
@@ -819,4 +2193,1204 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_native_objects() {
+        struct Counter {
+            count: RefCell<isize>,
+        }
+
+        fn increment(this: &dyn std::any::Any, _args: &[Objects]) -> Objects {
+            let counter = this.downcast_ref::<Counter>().unwrap();
+            *counter.count.borrow_mut() += 1;
+            Objects::Integer(Integer::new(*counter.count.borrow()))
+        }
+
+        fn add(this: &dyn std::any::Any, args: &[Objects]) -> Objects {
+            let counter = this.downcast_ref::<Counter>().unwrap();
+            match args.first() {
+                Some(Objects::Integer(i)) => {
+                    Objects::Integer(Integer::new(*counter.count.borrow() + i.value()))
+                }
+                _ => Objects::Error(ErrorObject::new("expected an INTEGER".into())),
+            }
+        }
+
+        fn inspect(this: &dyn std::any::Any) -> String {
+            let counter = this.downcast_ref::<Counter>().unwrap();
+            format!("Counter({})", counter.count.borrow())
+        }
+
+        let inputs = [
+            (r#"invoke(counter, "increment")"#, "1"),
+            (
+                r#"invoke(counter, "increment"); invoke(counter, "increment")"#,
+                "2",
+            ),
+            (r#"invoke(counter, "add", 10)"#, "10"),
+            ("counter.increment(); counter.add(5)", "6"),
+            ("counter.reset()", "undefined method 'reset' for Counter"),
+            ("counter", "Counter(0)"),
+            ("counter == counter", "true"),
+            ("counter == other", "false"),
+            ("counter != other", "true"),
+            (
+                r#"invoke(counter, "missing")"#,
+                "undefined method 'missing' for Counter",
+            ),
+            ("counter + 1", "type mismatch: Counter + INTEGER"),
+            (
+                r#"invoke(1, "increment")"#,
+                "argument to 'invoke' not supported, got INTEGER",
+            ),
+        ];
+
+        for (input, exp) in inputs {
+            let native = Native::new(
+                "Counter",
+                Counter {
+                    count: RefCell::new(0),
+                },
+            )
+            .with_method("increment", increment)
+            .with_method("add", add)
+            .with_inspect(inspect);
+            let env = Rc::new(RefCell::new(Environment::new()));
+            env.borrow_mut()
+                .set("counter".into(), Objects::Native(native.clone()));
+            env.borrow_mut()
+                .set("other".into(), Objects::Native(Native::new("Counter", 0)));
+            let l = Lexer::new(input.into());
+            let mut p = Parser::new(l);
+            let mut program = p.parse_program().expect("Program did not parse properly");
+            match eval_program(&mut program, env) {
+                Some(ev) => assert_eq!(exp, ev.inspect()),
+                None => assert!(false, "No output"),
+            }
+            assert_eq!(ObjectTypes::Native("Counter".into()), native.obj_type());
+        }
+    }
+
+    fn test_eval_with_limits(input: &str, limits: Limits) -> Option<Objects> {
+        let l = Lexer::new(input.into());
+        let mut p = Parser::new(l);
+        let mut program = p.parse_program().expect("Program did not parse properly");
+        let env = Rc::new(RefCell::new(Environment::with_limits(limits)));
+        eval_program(&mut program, env)
+    }
+
+    #[test]
+    fn test_sandbox_limits() {
+        let runaway = "let f = fn(n) { f(n + 1) }; f(0)";
+        let deep = format!("1{}", " + 1".repeat(200));
+        let inputs = [
+            (
+                runaway,
+                Limits {
+                    max_call_depth: Some(50),
+                    ..Limits::unlimited()
+                },
+                Limit::CallDepth,
+                "maximum call depth exceeded: 50",
+            ),
+            (
+                runaway,
+                Limits {
+                    max_steps: Some(200),
+                    ..Limits::unlimited()
+                },
+                Limit::Steps,
+                "step limit exceeded: 200 steps",
+            ),
+            (
+                r#""a" + "b" + "c" + "d""#,
+                Limits {
+                    max_allocations: Some(3),
+                    ..Limits::unlimited()
+                },
+                Limit::Allocations,
+                "allocation limit exceeded: 3 objects",
+            ),
+            (
+                runaway,
+                Limits {
+                    timeout: Some(Duration::from_nanos(1)),
+                    max_call_depth: Some(50),
+                    ..Limits::unlimited()
+                },
+                Limit::Timeout,
+                "evaluation timed out after 1ns",
+            ),
+            (
+                runaway,
+                Limits::default(),
+                Limit::Stack,
+                "stack limit exceeded: 1572864 bytes",
+            ),
+            (
+                &deep,
+                Limits {
+                    max_stack: Some(64 * 1024),
+                    ..Limits::unlimited()
+                },
+                Limit::Stack,
+                "stack limit exceeded: 65536 bytes",
+            ),
+            (
+                "time.sleep(60000)",
+                Limits {
+                    timeout: Some(Duration::from_millis(20)),
+                    ..Limits::unlimited()
+                },
+                Limit::Timeout,
+                "evaluation timed out after 20ms",
+            ),
+            (
+                "[60000].map(time.sleep)",
+                Limits {
+                    timeout: Some(Duration::from_millis(20)),
+                    ..Limits::unlimited()
+                },
+                Limit::Timeout,
+                "evaluation timed out after 20ms",
+            ),
+            (
+                r#"string.upper("a")"#,
+                Limits {
+                    max_allocations: Some(1),
+                    ..Limits::unlimited()
+                },
+                Limit::Allocations,
+                "allocation limit exceeded: 1 objects",
+            ),
+            (
+                r#"string.repeat("x", 4611686018427387904)"#,
+                Limits::default(),
+                Limit::Allocations,
+                "allocation too large: over 1073741824 bytes",
+            ),
+        ];
+
+        for (input, limits, limit, exp) in inputs {
+            match test_eval_with_limits(input, limits) {
+                Some(Objects::Error(e)) => {
+                    assert_eq!(ErrorKind::LimitExceeded(limit), e.kind());
+                    assert_eq!(exp, e.message());
+                }
+                other => panic!("Expected limit error but received: {:?}", other),
+            }
+        }
+
+        match test_eval_with_limits("let a = 1; a + 1", Limits::default()) {
+            Some(ev) => test_int(&ev, &2),
+            None => assert!(false, "No output"),
+        }
+    }
+
+    #[test]
+    fn test_sandbox_cancellation() {
+        let env = Rc::new(RefCell::new(Environment::new()));
+        let handle = env.borrow().cancel_handle();
+        std::thread::spawn(move || handle.cancel())
+            .join()
+            .expect("cancel thread panicked");
+
+        let l = Lexer::new("1 + 1".into());
+        let mut p = Parser::new(l);
+        let mut program = p.parse_program().expect("Program did not parse properly");
+        match eval_program(&mut program, Rc::clone(&env)) {
+            Some(Objects::Error(e)) => {
+                assert_eq!(ErrorKind::LimitExceeded(Limit::Cancelled), e.kind());
+                assert_eq!("evaluation cancelled", e.message());
+            }
+            other => panic!("Expected cancellation but received: {:?}", other),
+        }
+        // Starting the next program does not clear the cancel
+        assert!(eval_program(&mut program, Rc::clone(&env)).is_some_and(|ev| ev.is_err()));
+
+        env.borrow().cancel_handle().reset();
+        match eval_program(&mut program, Rc::clone(&env)) {
+            Some(ev) => test_int(&ev, &2),
+            None => assert!(false, "No output"),
+        }
+
+        // A sleeping script wakes up when it is cancelled
+        let handle = env.borrow().cancel_handle();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            handle.cancel();
+        });
+        let mut program = Parser::new(Lexer::new("time.sleep(60000)".into()))
+            .parse_program()
+            .expect("Program did not parse properly");
+        match eval_program(&mut program, env) {
+            Some(Objects::Error(e)) => {
+                assert_eq!(ErrorKind::LimitExceeded(Limit::Cancelled), e.kind())
+            }
+            other => panic!("Expected cancellation but received: {:?}", other),
+        }
+        canceller.join().expect("cancel thread panicked");
+    }
+
+    #[test]
+    fn test_garbage_collection() {
+        let inputs = [
+            ("gc()", 0),
+            ("let f = fn(n) { let g = fn(x) { x }; g(n) }; f(1); f(2); f(3); gc()", 3),
+            ("let f = fn(n) { let g = fn(x) { x }; g(n) }; f(1); gc(); gc()", 0),
+            (
+                "let newAdder = fn(x) { let add = fn(y) { x + y }; add }; let addTwo = newAdder(2); gc(); addTwo(3)",
+                5,
+            ),
+            (
+                "let newAdder = fn(x) { let add = fn(y) { x + y }; add }; let addTwo = newAdder(2); drop(addTwo); gc()",
+                1,
+            ),
+        ];
+
+        for (input, exp) in inputs {
+            match test_eval(input) {
+                Some(ev) => test_int(&ev, &exp),
+                None => assert!(false, "No output"),
+            }
+        }
+
+        // Methods live in a table shared by every value of the type, the
+        // environment they close over stays alive as long as one of them does
+        let make = "let make = fn() { let k = 7; struct P { x } impl P { fn get(self) { self.x + k } } P { x: 1 } }; let v = make();";
+        let inputs = [
+            ("gc(); v.get()", "8"),
+            ("let w = v; gc(); [v.get(), w.get()]", "[8, 8]"),
+            // An automatic collection after COLLECTION_THRESHOLD calls
+            ("map(range(0, 10001), fn(x) { x }); v.get()", "8"),
+            (
+                "gc(); gc(); gc_stats()",
+                "{collections: 2, tracked: 1, freed: 0}",
+            ),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(&format!("{} {}", make, input)).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+
+        let env = Rc::new(RefCell::new(Environment::new()));
+        let l = Lexer::new("let f = fn(n) { let g = fn(x) { x }; g(n) }; f(1); f(2);".into());
+        let mut p = Parser::new(l);
+        let mut program = p.parse_program().expect("Program did not parse properly");
+        eval_program(&mut program, Rc::clone(&env));
+        let heap = Rc::clone(env.borrow().heap());
+        assert_eq!(2, heap.collect());
+        assert_eq!(
+            GcStats {
+                collections: 1,
+                tracked: 0,
+                freed: 2,
+            },
+            heap.stats()
+        );
+    }
+
+    #[test]
+    fn test_float_expressions() {
+        let inputs = [
+            ("3.14", "3.14"),
+            ("-2.5", "-2.5"),
+            ("1e-3", "0.001"),
+            ("1.5 + 1.5", "3.0"),
+            ("1 + 0.5", "1.5"),
+            ("0.5 + 1", "1.5"),
+            ("10 / 4.0", "2.5"),
+            ("10 / 4", "2"),
+            ("3 * 1.5 - 1", "3.5"),
+            ("1.0 / 0", "inf"),
+            ("1.5 < 2", "true"),
+            ("2 > 1.5", "true"),
+            ("1 == 1.0", "true"),
+            ("1.5 != 1.5", "false"),
+            ("-(1.5 + 1)", "-2.5"),
+            ("int(3.99)", "3"),
+            ("int(-3.99)", "-3"),
+            (r#"int("42")"#, "42"),
+            ("float(2)", "2.0"),
+            (r#"float("0.25")"#, "0.25"),
+            ("float(1) / 4", "0.25"),
+            ("int(1.0 / 0)", "cannot convert inf to INTEGER"),
+            (r#"float("abc")"#, "cannot convert \"abc\" to FLOAT"),
+            ("int(true)", "argument to 'int' not supported, got BOOLEAN"),
+            ("1.5 + true", "type mismatch: FLOAT + BOOLEAN"),
+        ];
+
+        for (input, exp) in inputs {
+            match test_eval(input) {
+                Some(ev) => assert_eq!(exp, ev.inspect()),
+                None => assert!(false, "No output"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_big_integers() {
+        let inputs = [
+            ("9223372036854775807 + 1", "9223372036854775808"),
+            ("-9223372036854775807 - 2", "-9223372036854775809"),
+            ("-(-9223372036854775807 - 1)", "9223372036854775808"),
+            (
+                "9223372036854775807 * 9223372036854775807",
+                "85070591730234615847396907784232501249",
+            ),
+            (
+                "123456789012345678901234567890",
+                "123456789012345678901234567890",
+            ),
+            (
+                "123456789012345678901234567890 / 1234567890",
+                "100000000010000000001",
+            ),
+            ("(9223372036854775807 + 1) - 1", "9223372036854775807"),
+            ("(9223372036854775807 + 10) / -2", "-4611686018427387908"),
+            ("100000000000000000000 > 99999999999999999999", "true"),
+            ("100000000000000000000 == 100000000000000000000", "true"),
+            ("100000000000000000000 < 5", "false"),
+            ("100000000000000000000 * 0.5", "5e19"),
+            ("float(100000000000000000000)", "1e20"),
+            (r#"int("100000000000000000000")"#, "100000000000000000000"),
+            ("10 / 0", "division by zero"),
+            ("100000000000000000000 / 0", "division by zero"),
+            (
+                "let fact = fn(n) { if (n < 2) { 1 } else { n * fact(n - 1) } }; fact(30)",
+                "265252859812191058636308480000000",
+            ),
+            ("int(1e20)", "100000000000000000000"),
+            ("int(-1e20)", "-100000000000000000000"),
+            ("math.round(2.0 ** 100)", "1267650600228229401496703205376"),
+            ("math.floor(-1e19)", "-10000000000000000000"),
+            ("math.abs(-9223372036854775808)", "9223372036854775808"),
+            ("math.abs(-100000000000000000000)", "100000000000000000000"),
+            ("1 << 4000000000", "integer too large: over 20000 digits"),
+            ("2 ** 3000000", "integer too large: over 20000 digits"),
+            (
+                "(10 ** 15000) * (10 ** 15000)",
+                "integer too large: over 20000 digits",
+            ),
+            ("0 << 4000000000", "0"),
+            ("1 ** 4000000000", "1"),
+            ("(2 ** 100) >> 4000000000", "0"),
+            ("-(2 ** 100) >> 4000000000", "-1"),
+            ("(2 ** 100) >> 99", "2"),
+        ];
+
+        for (input, exp) in inputs {
+            match test_eval(input) {
+                Some(ev) => assert_eq!(exp, ev.inspect()),
+                None => assert!(false, "No output"),
+            }
+        }
+
+        let big = test_eval("9223372036854775807 + 1").expect("No output");
+        assert_eq!(ObjectTypes::Integer, big.obj_type());
+        assert_eq!("INTEGER", big.to_string());
+    }
+
+    #[test]
+    fn test_comparison_operators() {
+        let inputs = [
+            ("1 <= 2", true),
+            ("2 <= 2", true),
+            ("3 <= 2", false),
+            ("2 >= 3", false),
+            ("2 >= 2", true),
+            ("1.5 >= 1", true),
+            ("100000000000000000000 >= 100000000000000000000", true),
+            (r#""abc" < "abd""#, true),
+            (r#""abc" > "abd""#, false),
+            (r#""ab" < "abc""#, true),
+            (r#""b" >= "abc""#, true),
+            (r#""a" <= "a""#, true),
+            (r#""a" == "a""#, true),
+            (r#""a" != "a""#, false),
+            (r#""a" == "b""#, false),
+            ("true == true", true),
+            ("true != false", true),
+            (r#"1 == "1""#, false),
+            (r#"1 != "1""#, true),
+            ("true == 1", false),
+            (r#""true" != true"#, true),
+            ("if (false) { 1 } == 1", false),
+            ("if (false) { 1 } == if (false) { 2 }", true),
+        ];
+
+        for (input, exp) in inputs {
+            match test_eval(input) {
+                Some(ev) => test_bool(&ev, &exp),
+                None => assert!(false, "No output"),
+            }
+        }
+
+        let inputs = [
+            ("true < false", "unknown operator: BOOLEAN < BOOLEAN"),
+            (r#""a" < 1"#, "type mismatch: STRING < INTEGER"),
+        ];
+
+        for (input, exp) in inputs {
+            match test_eval(input) {
+                Some(ev) => assert_eq!(exp, ev.inspect()),
+                None => assert!(false, "No output"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_and_bitwise_operators() {
+        let inputs = [
+            ("7 % 3", "1"),
+            ("-7 % 3", "-1"),
+            ("7.5 % 2", "1.5"),
+            ("2 ** 10", "1024"),
+            ("2 ** 3 ** 2", "512"),
+            ("2 ** 100", "1267650600228229401496703205376"),
+            ("2 ** -1", "0.5"),
+            ("2.0 ** 0.5 > 1.41", "true"),
+            ("(2 ** 100) % 7", "2"),
+            ("6 & 3", "2"),
+            ("6 | 3", "7"),
+            ("6 ^ 3", "5"),
+            ("~5", "-6"),
+            ("~(2 ** 70)", "-1180591620717411303425"),
+            ("1 << 4", "16"),
+            ("1 << 64", "18446744073709551616"),
+            ("-16 >> 2", "-4"),
+            ("-1 >> 100", "-1"),
+            ("(2 ** 70) >> 68", "4"),
+            ("-(2 ** 70) >> 69", "-2"),
+            ("1 + 2 * 3 % 4 ** 2", "7"),
+            ("7 % 0", "modulo by zero"),
+            ("(2 ** 70) % 0", "modulo by zero"),
+            ("1 << -1", "negative shift amount: -1"),
+            (
+                "(2 ** 70) & 1",
+                "integer too large for bitwise operator: 1180591620717411303424 & 1",
+            ),
+            ("1.5 & 1", "unknown operator: FLOAT & INTEGER"),
+            ("~true", "unknown operator: ~BOOLEAN"),
+        ];
+
+        for (input, exp) in inputs {
+            match test_eval(input) {
+                Some(ev) => assert_eq!(exp, ev.inspect(), "{}", input),
+                None => assert!(false, "No output"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_string_indexing() {
+        let inputs = [
+            (r#""héllo"[1] == 'é'"#, Some("true")),
+            (r#""héllo"[4]"#, Some("o")),
+            (r#"let s = "a😀b"; s[1] + s[2]"#, Some("😀b")),
+            (r#""abc"[3]"#, None),
+            (r#""abc"[-1]"#, None),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            match expected {
+                Some(expected) => assert_eq!(expected, ev.inspect()),
+                None => assert!(ev.is_null(), "expected null for {}, got {}", input, ev),
+            }
+        }
+
+        let ev = test_eval(r#""abc"[true]"#).expect("No output");
+        assert_eq!(
+            "index operator not supported: STRING[BOOLEAN]",
+            ev.as_err().expect("expected an error").message()
+        );
+    }
+
+    #[test]
+    fn test_chars() {
+        let inputs = [
+            ("'a'", "a"),
+            (r"'\n' == chr(10)", "true"),
+            ("ord('A')", "65"),
+            ("ord('😀')", "128512"),
+            ("chr(233)", "é"),
+            ("'a' < 'b'", "true"),
+            ("'z' <= 'a'", "false"),
+            ("'a' == 'a'", "true"),
+            (r#"'a' == "a""#, "false"),
+            ("'a' + 'b'", "ab"),
+            (r#""ab" + 'c'"#, "abc"),
+            (r#"'x' + "yz""#, "xyz"),
+            (r#"chars("héllo")"#, "[h, é, l, l, o]"),
+            (r#"len(chars("a😀"))"#, "2"),
+            (r#"chars("abc")[2] == 'c'"#, "true"),
+            (r#"chars("")"#, "[]"),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+
+        let errors = [
+            ("chr(-1)", "invalid code point: -1"),
+            ("chr(55296)", "invalid code point: 55296"),
+            (r#"ord("a")"#, "argument to 'ord' not supported, got STRING"),
+            ("'a' - 'b'", "unknown operator: CHAR - CHAR"),
+            ("'a' + 1", "type mismatch: CHAR + INTEGER"),
+        ];
+        for (input, expected) in errors {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.as_err().expect("expected an error").message());
+        }
+    }
+
+    #[test]
+    fn test_array_literals() {
+        let ev = test_eval("let a = [1, 2 * 2, \"three\"]; a").expect("No output");
+        assert_eq!("[1, 4, three]", ev.inspect());
+
+        let inputs = [
+            ("[1, 2, 3][0]", Some(1)),
+            ("[1, 2, 3][2]", Some(3)),
+            ("let i = 0; [1][i]", Some(1)),
+            ("[[1, 2], [3]][0][1]", Some(2)),
+            ("len([1, 2, 3])", Some(3)),
+            ("[1, 2, 3][3]", None),
+            ("[1, 2, 3][-1]", None),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            match expected {
+                Some(expected) => test_int(&ev, &expected),
+                None => assert!(ev.is_null()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_string_interpolation() {
+        let inputs = [
+            (
+                r#"let name = "Ann"; let age = 41; "hello ${name}, you are ${age + 1}""#,
+                "hello Ann, you are 42",
+            ),
+            (
+                r#""${1.5 * 2} ${true} ${'c'} ${[1, "a"]}""#,
+                "3.0 true c [1, a]",
+            ),
+            (r#"let f = fn(x) { "<${x}>" }; "${f("${1 + 1}")}""#, "<2>"),
+            (r#""${2 ** 70}""#, "1180591620717411303424"),
+            (r#""no ${"nested"} braces { }""#, "no nested braces { }"),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.as_str().expect("not a string").value());
+        }
+
+        let ev = test_eval(r#""${missing}""#).expect("No output");
+        assert_eq!(
+            "identifier not found: missing",
+            ev.as_err().expect("expected an error").message()
+        );
+    }
+
+    #[test]
+    fn test_identifier_forms() {
+        let input = r#"
+        let value1 = 2;
+        let größe = value1 * 3;
+        let empty? = fn(s) { len(s) == 0 };
+        if (empty?("")) { größe } else { 0 }
+        "#;
+        test_int(&test_eval(input).expect("No output"), &6);
+    }
+
+    #[test]
+    fn test_integer_literal_forms() {
+        let inputs = [
+            ("0xFF", 255),
+            ("0xff + 0o17 + 0b1010", 280),
+            ("1_000_000", 1_000_000),
+            ("-0x7fff_ffff_ffff_ffff", -0x7fff_ffff_ffff_ffff),
+            ("0b1111_0000 >> 4", 15),
+        ];
+        for (input, expected) in inputs {
+            test_int(&test_eval(input).expect("No output"), &expected);
+        }
+
+        let big = test_eval("0xffff_ffff_ffff_ffff_ffff").expect("No output");
+        assert_eq!("1208925819614629174706175", big.inspect());
+        let float = test_eval("1_000.5").expect("No output");
+        assert_eq!("1000.5", float.inspect());
+    }
+
+    #[test]
+    fn test_hash_literals() {
+        let input = r#"
+        let two = "two";
+        let h = {"one": 10 - 9, two: 1 + 1, 3: 3, true: 4, 'c': 5};
+        [h["one"], h["two"], h[3], h[true], h['c'], h["missing"], len(h)]
+        "#;
+        let ev = test_eval(input).expect("No output");
+        assert_eq!("[1, 2, 3, 4, 5, null, 5]", ev.inspect());
+
+        let ev = test_eval(r#"{"a": 1, "a": 2}"#).expect("No output");
+        assert_eq!("{a: 2}", ev.inspect());
+
+        let errors = [
+            ("{[1]: 2}", "unusable as hash key: ARRAY"),
+            (r#"{"a": 1}[fn(x) { x }]"#, "unusable as hash key: FUNCTION"),
+        ];
+        for (input, expected) in errors {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.as_err().expect("expected an error").message());
+        }
+    }
+
+    #[test]
+    fn test_match_expression() {
+        let describe = r#"
+        let describe = fn(value) {
+            match (value) {
+                0 => "zero",
+                "x" | "y" => "axis",
+                'c' => "char",
+                [] => "empty",
+                [only] => "one: ${only}",
+                [first, ..rest] => "first ${first}, ${len(rest)} more",
+                {"k": v} => "k is ${v}",
+                {name, age} if age >= 18 => "${name} (adult)",
+                {name} => "${name} (minor)",
+                n if n > 10 => "big ${n}",
+                -1 | 1 => "unit",
+                _ => "other"
+            }
+        };
+        "#;
+        let inputs = [
+            ("0", "zero"),
+            (r#""y""#, "axis"),
+            ("'c'", "char"),
+            ("[]", "empty"),
+            ("[7]", "one: 7"),
+            ("[1, 2, 3]", "first 1, 2 more"),
+            (r#"{"k": true}"#, "k is true"),
+            (r#"{"name": "Ann", "age": 30}"#, "Ann (adult)"),
+            (r#"{"name": "Bo", "age": 3}"#, "Bo (minor)"),
+            ("42", "big 42"),
+            ("-1", "unit"),
+            ("5", "other"),
+            (r#"{"age": 3}"#, "type mismatch: HASH > INTEGER"),
+        ];
+        for (value, expected) in inputs {
+            let input = format!("{} describe({})", describe, value);
+            let ev = test_eval(&input).expect("No output");
+            assert_eq!(expected, ev.inspect(), "value: {}", value);
+        }
+
+        let inputs = [
+            ("match ([1, 2, 3, 4]) { [a, .., y, z] => a + y + z }", "8"),
+            ("match ([1, 2, 3]) { [_, ..mid, _] => mid }", "[2]"),
+            (
+                "let f = fn(x) { match (x) { n => fn() { n + x } } }; f(2)()",
+                "4",
+            ),
+            ("match (3) { 1 => 1 }", "no match arm matched: 3"),
+            ("match (1) { n => { let m = n + 1; m } }", "2"),
+            ("let n = 5; match (1) { n => n }; n", "5"),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_destructuring_let() {
+        let inputs = [
+            ("let [a, b, ..rest] = [1, 2, 3, 4]; a + b + len(rest)", "5"),
+            ("let [first, .., last] = [1, 2, 3]; [first, last]", "[1, 3]"),
+            (
+                r#"let {name, age} = {"name": "Ann", "age": 30}; "${name} ${age}""#,
+                "Ann 30",
+            ),
+            (
+                r#"let [p, {"tags": [t, ..]}] = [1, {"tags": ["x", "y"]}]; [p, t]"#,
+                "[1, x]",
+            ),
+            ("let _ = 5; let [_, b] = [1, 2]; b", "2"),
+            (
+                "let [a, b] = [1];",
+                "cannot bind [a, b]: expected an array of 2 elements but got 1",
+            ),
+            (
+                "let [a] = 5;",
+                "cannot bind [a]: cannot destructure INTEGER as an array",
+            ),
+            (
+                r#"let {name} = {"age": 1};"#,
+                "cannot bind {name: name}: missing key 'name' in hash",
+            ),
+            ("let [len] = [1];", "Cannot overwrite keyword: len"),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_pattern_parameters() {
+        let inputs = [
+            ("let add = fn([a, b]) { a + b }; add([1, 2])", "3"),
+            (
+                r#"let greet = fn({name}, punct) { name + punct }; greet({"name": "Bo"}, "!")"#,
+                "Bo!",
+            ),
+            ("let f = fn(x, [y, ..]) { x + y }; f(1, [2, 3])", "3"),
+            (
+                "let f = fn([a, b]) { a }; f([1])",
+                "cannot bind argument 1 to [a, b]: expected an array of 2 elements but got 1",
+            ),
+            (
+                "let f = fn(x, {k}) { k }; f(1, 2)",
+                "cannot bind argument 2 to {k: k}: cannot destructure INTEGER as a hash",
+            ),
+            (
+                "let f = fn(a, b) { a }; f(1)",
+                "wrong number of arguments: expected 2 but received 1",
+            ),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_default_rest_and_named_arguments() {
+        let inputs = [
+            (
+                "let f = fn(x, y = 10) { x + y }; [f(1), f(1, 2)]",
+                "[11, 3]",
+            ),
+            ("let f = fn(x, y = x * 2) { y }; f(4)", "8"),
+            (
+                "let f = fn(first, ...rest) { [first, rest] }; f(1, 2, 3)",
+                "[1, [2, 3]]",
+            ),
+            ("let f = fn(...all) { len(all) }; f()", "0"),
+            (
+                "let f = fn(a, b, c) { a + b + c }; let args = [2, 3]; f(1, ...args)",
+                "6",
+            ),
+            ("let xs = [2, 3]; [1, ...xs, 4]", "[1, 2, 3, 4]"),
+            ("let f = fn(x, y) { x - y }; f(y: 2, x: 10)", "8"),
+            (
+                "let f = fn(x, y = 1, z = 2) { [x, y, z] }; f(0, z: 5)",
+                "[0, 1, 5]",
+            ),
+            (
+                "let f = fn(x, y) { x }; f(1, 2, 3)",
+                "wrong number of arguments: expected 2 but received 3",
+            ),
+            (
+                "let f = fn(x, y = 1) { x }; f()",
+                "wrong number of arguments: expected 1 to 2 but received 0",
+            ),
+            (
+                "let f = fn(x, ...r) { x }; f()",
+                "wrong number of arguments: expected at least 1 but received 0",
+            ),
+            ("let f = fn(x) { x }; f(z: 1)", "unknown named argument 'z'"),
+            (
+                "let f = fn(x) { x }; f(1, x: 2)",
+                "argument 'x' was given more than once",
+            ),
+            (
+                "let f = fn(x) { x }; f(...5)",
+                "cannot spread INTEGER, expected an array",
+            ),
+            (
+                "len(s: \"abc\")",
+                "builtin functions do not accept named arguments",
+            ),
+            (
+                "...[1]",
+                "spread is only allowed in call arguments and array literals",
+            ),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_structs() {
+        let point = "struct Point { x, y }";
+        let inputs = [
+            ("let p = Point { y: 2, x: 1 }; p", "Point { x: 1, y: 2 }"),
+            ("let x = 3; let y = 4; let p = Point { x, y }; p.x * p.y", "12"),
+            ("let p = Point { x: 1, y: 2 }; let q = Point { y: 5, ..p }; [p.y, q.x, q.y]", "[2, 1, 5]"),
+            ("struct Line { from, to } let l = Line { from: Point { x: 0, y: 0 }, to: Point { x: 3, y: 4 } }; l.to.y", "4"),
+            ("Point", "struct Point { x, y }"),
+            ("Point { x: 1 }", "missing field 'y' in Point"),
+            ("Point { x: 1, y: 2, z: 3 }", "Point has no field 'z'"),
+            ("Point { x: 1, x: 2, y: 3 }", "field 'x' is specified more than once"),
+            ("Point { x: 1, y: 2 }.z", "Point has no field 'z'"),
+            ("Point { x: 1, ..5 }", "cannot update Point from INTEGER"),
+            ("Circle { r: 1 }", "unknown struct: Circle"),
+            ("5.x", "cannot access field 'x' on INTEGER"),
+            ("let p = Point { x: 1, y: 2 }; [p == Point { y: 2, x: 1 }, p != Point { x: 1, y: 2 }]", "[true, false]"),
+            ("Point { x: 1, y: 2.0 } == Point { x: 1, y: 2 }", "true"),
+            ("Point { x: 1, y: [1] } == Point { x: 1, y: [2] }", "false"),
+            ("struct Pair { x, y } Point { x: 1, y: 2 } == Pair { x: 1, y: 2 }", "false"),
+            ("Point { x: 1, y: 2 } < Point { x: 1, y: 3 }", "unknown operator: Point < Point"),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(&format!("{} {}", point, input)).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+
+        let p = test_eval(&format!("{} Point {{ x: 1, y: 2 }}", point)).expect("No output");
+        assert_eq!("Point", p.obj_type().to_string());
+    }
+
+    #[test]
+    fn test_method_calls() {
+        let prelude = r#"
+        struct Point { x, y }
+        impl Point {
+            fn area(self) { self.x * self.y }
+            fn scale(self, k) { Point { x: self.x * k, y: self.y * k } }
+            fn origin() { Point { x: 0, y: 0 } }
+        }
+        let double = fn(n) { n * 2 };
+        "#;
+        let inputs = [
+            ("Point { x: 2, y: 3 }.area()", "6"),
+            ("let p = Point { x: 1, y: 2 }; p.scale(3).area()", "18"),
+            ("Point.origin()", "Point { x: 0, y: 0 }"),
+            ("struct Box { open } Box { open: fn() { 1 } }.open()", "1"),
+            (r#""abc".len()"#, "3"),
+            (r#""MiXed".lower() + " ".trim() + "x".upper()"#, "mixedX"),
+            (r#""a,b,c".split(",")"#, "[a, b, c]"),
+            (r#""hello".starts_with("he")"#, "true"),
+            ("[1, 2, 3].map(double)", "[2, 4, 6]"),
+            ("[1, 2, 3, 4].filter(fn(n) { n > 2 }).push(9)", "[3, 4, 9]"),
+            ("[1, 2, 3].reduce(0, fn(acc, n) { acc + n })", "6"),
+            (r#"[1, 2].join("-")"#, "1-2"),
+            ("[1, 2].contains(2)", "true"),
+            ("[].first()", "null"),
+            (r#"{"a": 1, "b": 2}.keys()"#, "[a, b]"),
+            (r#"{"a": 1}.contains("b")"#, "false"),
+            ("21.double()", "42"),
+            ("let add = fn(a, b) { a + b }; 1.add(2)", "3"),
+            (
+                "[3, 4].chars()",
+                "argument to 'chars' not supported, got ARRAY",
+            ),
+            ("5.missing()", "undefined method 'missing' for INTEGER"),
+            (
+                "Point { x: 1, y: 1 }.norm()",
+                "undefined method 'norm' for Point",
+            ),
+            (
+                r#""abc".split()"#,
+                "split expects 2 argument(s) but received 1",
+            ),
+            ("[3, 1, 2].sort().reverse()", "[3, 2, 1]"),
+            (r#""ab".repeat(2).split("")"#, "[a, b, a, b]"),
+            ("impl Nothing { fn f() { 1 } }", "unknown type: Nothing"),
+            (
+                "impl double { fn f() { 1 } }",
+                "cannot impl FUNCTION, only structs and enums have methods",
+            ),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(&format!("{} {}", prelude, input)).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_enums() {
+        let prelude = r#"
+        enum Shape { Circle(r), Rect(w, h), Empty }
+        impl Shape {
+            fn area(self) {
+                match (self) {
+                    Circle(r) => 3 * r * r,
+                    Rect(w, h) => w * h,
+                    Empty => 0
+                }
+            }
+        }
+        let safe_div = fn(a, b) { if (b == 0) { Err("division by zero") } else { Ok(a / b) } };
+        let describe = fn(opt) { match (opt) { Some(x) => "some ${x}", None => "nothing" } };
+        "#;
+        let inputs = [
+            ("Circle(2)", "Circle(2)"),
+            ("Rect(2, 3).area() + Circle(1).area() + Empty.area()", "9"),
+            ("Shape.Rect(1, 2)", "Rect(1, 2)"),
+            ("Shape.Empty", "Empty"),
+            ("Shape", "enum Shape { Circle(r), Rect(w, h), Empty }"),
+            ("Circle", "Shape::Circle"),
+            ("[describe(Some(1)), describe(None)]", "[some 1, nothing]"),
+            (
+                "match (safe_div(6, 0)) { Ok(v) => v, Err(e) => e }",
+                "division by zero",
+            ),
+            ("match (safe_div(6, 3)) { Ok(v) => v, Err(e) => e }", "2"),
+            ("let Some(x) = Some(5); x", "5"),
+            (
+                "let Some(x) = None;",
+                "cannot bind Some(x): expected Some but got None",
+            ),
+            ("let n = None; match (Some(1)) { n => n }", "Some(1)"),
+            (
+                "[Some(1) == Some(1), Some(1) == Some(2), None == None, None != Some(1)]",
+                "[true, false, true, true]",
+            ),
+            ("enum Other { Empty } Other.Empty == Shape.Empty", "false"),
+            ("match (Rect(1, 2)) { Rect(w) => w, _ => 0 }", "0"),
+            (
+                "Circle(1, 2)",
+                "Circle expects 1 argument(s) but received 2",
+            ),
+            ("Shape.Square", "Shape has no variant 'Square'"),
+            ("Some(1) < Some(2)", "unknown operator: Option < Option"),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(&format!("{} {}", prelude, input)).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+
+        let shape = test_eval(&format!("{} Circle(1)", prelude)).expect("No output");
+        assert_eq!("Shape", shape.obj_type().to_string());
+    }
+
+    #[test]
+    fn test_try_operator() {
+        let prelude = r#"
+        let parse = fn(s) { if (s == "") { Err("empty input") } else { Ok(len(s)) } };
+        let total = fn(a, b) { let x = parse(a)?; let y = parse(b)?; Ok(x + y) };
+        let first = fn(xs) { if (len(xs) == 0) { None } else { Some(xs[0]) } };
+        let first_doubled = fn(xs) { Some(first(xs)? * 2) };
+        "#;
+        let inputs = [
+            (r#"total("ab", "cde")"#, "Ok(5)"),
+            (r#"total("", "cde")"#, "Err(empty input)"),
+            (r#"total("ab", "")"#, "Err(empty input)"),
+            ("first_doubled([4, 5])", "Some(8)"),
+            ("first_doubled([])", "None"),
+            ("let x = Some(3); fn() { Some(x? + 1) }()", "Some(4)"),
+            ("[[1], []].map(first_doubled)", "[Some(2), None]"),
+            (
+                "let f = fn() { let a = [1, Err(\"bad\")?, 3]; a }; f()",
+                "Err(bad)",
+            ),
+            (
+                "let f = fn() { 5? }; f()",
+                "the ? operator expects an Option or Result, got INTEGER",
+            ),
+            (
+                "let f = fn(r) { Ok(r? + 1) }; [f(Ok(1)), f(Err(\"bad\"))]",
+                "[Ok(2), Err(bad)]",
+            ),
+            (
+                "struct B { v } let f = fn(b) { Some(b.v? * 2) }; f(B { v: Some(4) })",
+                "Some(8)",
+            ),
+            (
+                "let ok? = fn(x) { x > 0 }; [[1, -1].map(ok?), ok?(2)]",
+                "[[true, false], true]",
+            ),
+            (
+                "let f = fn(xs) { [...xs?, 3] }; [f(Some([1, 2])), f(None)]",
+                "[[1, 2, 3], None]",
+            ),
+            (
+                "let f = fn(x) { if (x) { return 1; } 2 }; [f(true), f(false)]",
+                "[1, 2]",
+            ),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(&format!("{} {}", prelude, input)).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+
+        // Every REPL line has its own parser, the names bound earlier still count
+        let env = Rc::new(RefCell::new(Environment::new()));
+        let lines = [
+            (
+                "let done? = true; let ok? = fn(x) { x > 0 }; let r = Ok(1); r",
+                "Ok(1)",
+            ),
+            ("[done?, [1, -1].map(ok?)]", "[true, [true, false]]"),
+            ("let f = fn() { Ok(r? + 1) }; f()", "Ok(2)"),
+        ];
+        for (input, expected) in lines {
+            let mut parser = Parser::new(Lexer::new(input.into()));
+            let mut program = parser
+                .parse_program()
+                .expect("Program did not parse properly");
+            assert!(parser.errors().is_empty(), "input: {}", input);
+            let ev = eval_program(&mut program, Rc::clone(&env)).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_modules() {
+        let dir = std::env::temp_dir().join(format!("monkey_modules_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("lib")).unwrap();
+        let files = [
+            (
+                "util.monkey",
+                "export let double = fn(x) { x * 2 }; let hidden = 1; export let valid? = fn(x) { x > 0 }; export let parsed = Ok(3);",
+            ),
+            (
+                "lib/math.monkey",
+                r#"import "../util.monkey" as u; export let [two, three] = [2, 3];
+                export let quad = fn(x) { u.double(u.double(x)) };"#,
+            ),
+            ("a.monkey", r#"import "b.monkey" as b; export let x = 1;"#),
+            ("b.monkey", r#"import "a.monkey" as a; export let y = 2;"#),
+            ("broken.monkey", "export let z = 1 + true;"),
+            ("invalid.monkey", "export let = 1;"),
+        ];
+        for (name, source) in files {
+            std::fs::write(dir.join(name), source).unwrap();
+        }
+        let run = |input: &str| {
+            let mut env = Environment::new();
+            env.set_base_dir(dir.clone());
+            let mut program = Parser::new(Lexer::new(input.into()))
+                .parse_program()
+                .expect("Program did not parse properly");
+            eval_program(&mut program, Rc::new(RefCell::new(env))).expect("No output")
+        };
+        let inputs = [
+            (r#"import "lib/math.monkey" as m; m.quad(m.three)"#, "12"),
+            (r#"import "util.monkey" as u; let f = u.double; f(4)"#, "8"),
+            (
+                r#"import "util.monkey" as u; u.hidden"#,
+                "module UTIL has no export 'hidden'",
+            ),
+            (
+                r#"import "util.monkey" as u; [1, -1].map(u.valid?)"#,
+                "[true, false]",
+            ),
+            (
+                r#"import "util.monkey" as u; fn() { Ok(u.parsed? + 1) }()"#,
+                "Ok(4)",
+            ),
+            (r#"import "lib/math.monkey" as m; len"#, "Builtin Function"),
+        ];
+        let util = dir.join("util.monkey").canonicalize().unwrap();
+        for (input, expected) in inputs {
+            let expected = expected.replace("UTIL", &util.display().to_string());
+            assert_eq!(expected, run(input).inspect(), "input: {}", input);
+        }
+
+        let (a, b) = (
+            dir.join("a.monkey")
+                .canonicalize()
+                .unwrap()
+                .display()
+                .to_string(),
+            dir.join("b.monkey")
+                .canonicalize()
+                .unwrap()
+                .display()
+                .to_string(),
+        );
+        assert_eq!(
+            format!(
+                "in module {}: in module {}: import cycle: {} -> {} -> {}",
+                a, b, a, b, a
+            ),
+            run(r#"import "a.monkey" as a; a.x"#).inspect()
+        );
+        let broken = run(r#"import "broken.monkey" as b; b.z"#).inspect();
+        assert!(
+            broken.ends_with("type mismatch: INTEGER + BOOLEAN"),
+            "{}",
+            broken
+        );
+        let invalid = run(r#"import "invalid.monkey" as i; i"#).inspect();
+        assert!(invalid.starts_with("cannot parse "), "{}", invalid);
+        let missing = run(r#"import "nope.monkey" as n; n"#).inspect();
+        assert!(
+            missing.starts_with("cannot import \"nope.monkey\""),
+            "{}",
+            missing
+        );
+
+        // Every import of a file shares the module it evaluated to
+        let mut env = Environment::new();
+        env.set_base_dir(dir.clone());
+        let env = Rc::new(RefCell::new(env));
+        let input = r#"import "util.monkey" as a; import "lib/../util.monkey" as b;"#;
+        let mut program = Parser::new(Lexer::new(input.into()))
+            .parse_program()
+            .unwrap();
+        eval_program(&mut program, Rc::clone(&env));
+        let (a, b) = (env.borrow().get("a".into()), env.borrow().get("b".into()));
+        assert!(matches!(a, Some(Objects::Module(_))));
+        assert_eq!(a, b);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_standard_library() {
+        let inputs = [
+            ("math.sqrt(16)", "4.0"),
+            ("math.floor(2.7) + math.ceil(2.1)", "5"),
+            ("[math.min(3, 1.5, 2), math.max(3, 1.5, 2), math.abs(-4)]", "[1.5, 3, 4]"),
+            ("math.round(math.pi * 100)", "314"),
+            (r#"import "math" as m; m.pow(2, 10)"#, "1024.0"),
+            (r#"string.join(string.split("a,b,c", ","), "-")"#, "a-b-c"),
+            (r#"string.replace(string.upper("ha ha"), "A", "E")"#, "HE HE"),
+            (r#"string.repeat("ab", 3)"#, "ababab"),
+            ("array.sort([3, 1, 2])", "[1, 2, 3]"),
+            ("array.sort([3.5, 1, 2 ** 70, -(2 ** 70)])", "[-1180591620717411303424, 1, 3.5, 1180591620717411303424]"),
+            ("array.contains([1, 2 ** 70], 2 ** 70)", "true"),
+            (
+                "let xs = map(range(0, 40), fn(i) { if (i % 3 == 0) { 0.0 / 0.0 } else { 40 - i } }); let s = xs.sort().filter(fn(x) { x == x }); [len(s), s[0], s[25]]",
+                "[26, 2, 39]",
+            ),
+            (
+                r#"array.sort(map(range(0, 40), fn(i) { if (i % 2 == 0) { i } else { "s" } }))"#,
+                "cannot compare STRING with INTEGER",
+            ),
+            ("array.sort([fn() { 1 }]).len()", "1"),
+            ("[10].map(time.sleep)", "[null]"),
+            ("struct T { wait } let t = T { wait: time.sleep }; t.wait(10)", "null"),
+            (r#"array.join([1, "a"], "-")"#, "1-a"),
+            ("array.slice(array.concat([1, 2], [3, 4]), 1, 10)", "[2, 3, 4]"),
+            ("let xs = [1, 2]; [array.push(xs, 3), xs]", "[[1, 2, 3], [1, 2]]"),
+            ("[array.first([]), array.rest([1])]", "[null, []]"),
+            (r#"json.stringify({"a": [1, 2.5, true, print()], "b": "q\"uote"})"#, r#"{"a":[1,2.5,true,null],"b":"q\"uote"}"#),
+            (r#"json.parse("{\"xs\": [1, -2.5e1, \"\\u0041\"], \"ok\": false}")"#, "{xs: [1, -25.0, A], ok: false}"),
+            ("time.now() > 0", "true"),
+            ("print(1, \"two\")", "null"),
+            ("let math = 1; math", "1"),
+            // Errors
+            ("math.sqrt(\"x\")", "argument to 'sqrt' not supported, got STRING"),
+            ("math.sqrt(1, 2)", "sqrt expects 1 argument(s) but received 2"),
+            ("array.sort([1, \"a\"])", "cannot compare STRING with INTEGER"),
+            ("math.tau", "module math has no export 'tau'"),
+            ("json.stringify(fn(x) { x })", "cannot convert FUNCTION to JSON"),
+            (r#"json.parse("[1, 2")"#, "invalid JSON at position 5: expected ',' or ']'"),
+            (r#"json.parse("\"\\ud83d\\ude00!\"")"#, "😀!"),
+            (r#"json.parse("\"\\ud83d\"")"#, "invalid JSON at position 7: invalid unicode escape"),
+        ];
+        for (input, expected) in inputs {
+            let ev = test_eval(input).expect("No output");
+            assert_eq!(expected, ev.inspect(), "input: {}", input);
+        }
+    }
 }
@@ -0,0 +1,1549 @@
+use crate::ast::{
+    BlockStatement, CallExpression, Expressions, FieldAccess, FunctionLiteral, ImplStatement,
+    InfixExpression, InterpolationPart, LetStatement, Pattern, Program, Statements, TypeAnnotation,
+};
+use crate::lexer::Position;
+use crate::prelude;
+use crate::stdlib;
+use crate::typecheck::TypeError;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+// Modules are opaque to inference, their members can be used at any type
+const MODULE: &str = "module";
+
+// The builtin takes arguments of different types at once, which no function type
+// can say, so calls to it are inferred on their own
+const PRINT: &str = "print";
+
+// A type of the strict mode, every expression has exactly one
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ty {
+    Var(usize),
+    // `int`, `string`, ... and structs and enums applied to the types of their fields
+    Con(String, Vec<Ty>),
+    Array(Box<Ty>),
+    Hash(Box<Ty>, Box<Ty>),
+    Function(Box<Signature>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    params: Vec<Ty>,
+    // The parameters from this one on have defaults
+    required: usize,
+    // The element type of a rest parameter
+    rest: Option<Ty>,
+    result: Ty,
+}
+
+impl Ty {
+    // Type variables are named 'a, 'b, ... in the order they appear
+    fn show(&self, names: &mut Vec<usize>) -> String {
+        match self {
+            Ty::Var(var) => {
+                let index = match names.iter().position(|name| name == var) {
+                    Some(index) => index,
+                    None => {
+                        names.push(*var);
+                        names.len() - 1
+                    }
+                };
+                let letter = (b'a' + (index % 26) as u8) as char;
+                match index / 26 {
+                    0 => format!("'{}", letter),
+                    n => format!("'{}{}", letter, n),
+                }
+            }
+            Ty::Con(name, args) if args.is_empty() => name.clone(),
+            Ty::Con(name, args) => {
+                let args: Vec<String> = args.iter().map(|arg| arg.show(names)).collect();
+                format!("{}<{}>", name, args.join(", "))
+            }
+            Ty::Array(element) => format!("[{}]", element.show(names)),
+            Ty::Hash(key, value) => format!("{{{}: {}}}", key.show(names), value.show(names)),
+            Ty::Function(signature) => {
+                let Signature {
+                    params,
+                    required,
+                    rest,
+                    result,
+                } = signature.as_ref();
+                let simple = params.len() == 1 && *required == 1 && rest.is_none();
+                let params = match params.first() {
+                    Some(param) if simple && !matches!(param, Ty::Function(_)) => param.show(names),
+                    _ => {
+                        let mut parts = Vec::new();
+                        for (i, param) in params.iter().enumerate() {
+                            let optional = if i < *required { "" } else { "?" };
+                            parts.push(format!("{}{}", param.show(names), optional));
+                        }
+                        if let Some(rest) = rest {
+                            parts.push(format!("...[{}]", rest.show(names)));
+                        }
+                        format!("({})", parts.join(", "))
+                    }
+                };
+                format!("{} -> {}", params, result.show(names))
+            }
+        }
+    }
+}
+
+impl Display for Ty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.show(&mut Vec::new()))
+    }
+}
+
+fn named(name: &str) -> Ty {
+    Ty::Con(name.to_string(), Vec::new())
+}
+
+fn is(ty: &Ty, name: &str) -> bool {
+    matches!(ty, Ty::Con(n, args) if n == name && args.is_empty())
+}
+
+fn array(element: Ty) -> Ty {
+    Ty::Array(Box::new(element))
+}
+
+fn function(params: Vec<Ty>, result: Ty) -> Ty {
+    let required = params.len();
+    Ty::Function(Box::new(Signature {
+        params,
+        required,
+        rest: None,
+        result,
+    }))
+}
+
+fn free_vars(ty: &Ty, vars: &mut Vec<usize>) {
+    match ty {
+        Ty::Var(var) if !vars.contains(var) => vars.push(*var),
+        Ty::Var(_) => {}
+        Ty::Con(_, args) => args.iter().for_each(|arg| free_vars(arg, vars)),
+        Ty::Array(element) => free_vars(element, vars),
+        Ty::Hash(key, value) => {
+            free_vars(key, vars);
+            free_vars(value, vars);
+        }
+        Ty::Function(signature) => {
+            signature
+                .params
+                .iter()
+                .for_each(|param| free_vars(param, vars));
+            if let Some(rest) = &signature.rest {
+                free_vars(rest, vars);
+            }
+            free_vars(&signature.result, vars);
+        }
+    }
+}
+
+// A type that is polymorphic in `vars`
+#[derive(Debug, Clone)]
+struct Scheme {
+    vars: Vec<usize>,
+    ty: Ty,
+}
+
+impl Scheme {
+    fn mono(ty: Ty) -> Self {
+        Self {
+            vars: Vec::new(),
+            ty,
+        }
+    }
+}
+
+enum Failure {
+    Mismatch,
+    Infinite(usize, Ty),
+}
+
+#[derive(Debug, Clone)]
+struct Variant {
+    owner: String,
+    // Where the payload starts among the enum's type arguments
+    offset: usize,
+    fields: usize,
+    constructor: Scheme,
+}
+
+// Checks a whole program in strict mode before it is evaluated
+pub fn infer(program: &Program) -> Result<(), Vec<TypeError>> {
+    Inferer::new().check_program(program)
+}
+
+// Hindley-Milner inference with let-polymorphism for function literals. It
+// keeps what earlier programs declared, so the REPL can infer line by line.
+#[derive(Clone)]
+pub struct Inferer {
+    // What each type variable has been unified with
+    substitution: Vec<Option<Ty>>,
+    scopes: Vec<HashMap<String, Scheme>>,
+    // Struct fields and the number of type arguments of each enum
+    structs: HashMap<String, Vec<String>>,
+    enums: HashMap<String, usize>,
+    variants: HashMap<String, Variant>,
+    methods: HashMap<(String, String), Scheme>,
+    // The result of every function being inferred, innermost last
+    returns: Vec<Ty>,
+    // Errors are reported at the closest node that knows where it is
+    position: Position,
+    errors: Vec<TypeError>,
+}
+
+impl Inferer {
+    pub fn new() -> Self {
+        let mut inferer = Self {
+            substitution: Vec::new(),
+            scopes: vec![HashMap::new()],
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            variants: HashMap::new(),
+            methods: HashMap::new(),
+            returns: Vec::new(),
+            position: Position { line: 1, column: 1 },
+            errors: Vec::new(),
+        };
+        inferer.declare_enum("Option", &[("Some", 1), ("None", 0)]);
+        inferer.declare_enum("Result", &[("Ok", 1), ("Err", 1)]);
+        let a = inferer.fresh();
+        let builtins = [
+            ("len", function(vec![a.clone()], named("int"))),
+            ("int", function(vec![a.clone()], named("int"))),
+            ("float", function(vec![a.clone()], named("float"))),
+            ("ord", function(vec![named("char")], named("int"))),
+            ("chr", function(vec![named("int")], named("char"))),
+            (
+                "chars",
+                function(vec![named("string")], array(named("char"))),
+            ),
+            ("gc", function(Vec::new(), named("int"))),
+            (
+                "gc_stats",
+                function(
+                    Vec::new(),
+                    Ty::Hash(Box::new(named("string")), Box::new(named("int"))),
+                ),
+            ),
+            // Natives are not typed, their results can be anything
+            ("invoke", a),
+        ];
+        for (name, ty) in builtins {
+            let scheme = inferer.generalize(&ty);
+            inferer.bind(name, scheme);
+        }
+        for name in stdlib::MODULES {
+            inferer.bind(name, Scheme::mono(named(MODULE)));
+        }
+        // A replaced prelude that does not infer is still usable, whatever
+        // it binds keeps the type inferred so far
+        for statement in prelude::statements().iter() {
+            inferer.statement(statement);
+        }
+        inferer.errors.clear();
+        inferer
+    }
+
+    // Nothing the program declared is kept when it does not infer
+    pub fn check_program(&mut self, program: &Program) -> Result<(), Vec<TypeError>> {
+        let snapshot = self.clone();
+        for statement in &program.statements {
+            self.statement(statement);
+        }
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        let errors = std::mem::take(&mut self.errors);
+        *self = snapshot;
+        Err(errors)
+    }
+
+    // The type of the program's last statement, for `:type` in the REPL
+    pub fn type_of(&mut self, program: &Program) -> Result<Ty, Vec<TypeError>> {
+        let snapshot = self.clone();
+        let mut ty = named("null");
+        for statement in &program.statements {
+            ty = self.statement(statement);
+        }
+        let ty = self.apply(&ty);
+        let errors = std::mem::take(&mut self.errors);
+        *self = snapshot;
+        match errors.is_empty() {
+            true => Ok(ty),
+            false => Err(errors),
+        }
+    }
+
+    fn error(&mut self, message: String) {
+        self.errors.push(TypeError::new(self.position, message));
+    }
+
+    // Moves the reported position into a node, the old one is returned to restore
+    fn enter(&mut self, position: Position) -> Position {
+        std::mem::replace(&mut self.position, position)
+    }
+
+    fn fresh(&mut self) -> Ty {
+        self.substitution.push(None);
+        Ty::Var(self.substitution.len() - 1)
+    }
+
+    fn fresh_args(&mut self, count: usize) -> Vec<Ty> {
+        (0..count).map(|_| self.fresh()).collect()
+    }
+
+    fn bind(&mut self, name: &str, scheme: Scheme) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), scheme);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<Scheme> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).cloned())
+    }
+
+    // Follows the substitution until the type is not a bound variable
+    fn resolve(&self, ty: &Ty) -> Ty {
+        let mut ty = ty.clone();
+        while let Ty::Var(var) = ty {
+            match &self.substitution[var] {
+                Some(bound) => ty = bound.clone(),
+                None => break,
+            }
+        }
+        ty
+    }
+
+    // Substitutes every bound variable, however deep
+    fn apply(&self, ty: &Ty) -> Ty {
+        match self.resolve(ty) {
+            Ty::Var(var) => Ty::Var(var),
+            Ty::Con(name, args) => Ty::Con(name, args.iter().map(|arg| self.apply(arg)).collect()),
+            Ty::Array(element) => array(self.apply(&element)),
+            Ty::Hash(key, value) => {
+                Ty::Hash(Box::new(self.apply(&key)), Box::new(self.apply(&value)))
+            }
+            Ty::Function(signature) => Ty::Function(Box::new(Signature {
+                params: signature
+                    .params
+                    .iter()
+                    .map(|param| self.apply(param))
+                    .collect(),
+                required: signature.required,
+                rest: signature.rest.as_ref().map(|rest| self.apply(rest)),
+                result: self.apply(&signature.result),
+            })),
+        }
+    }
+
+    fn occurs(&self, var: usize, ty: &Ty) -> bool {
+        let mut vars = Vec::new();
+        free_vars(&self.apply(ty), &mut vars);
+        vars.contains(&var)
+    }
+
+    fn unify(&mut self, a: &Ty, b: &Ty) -> Result<(), Failure> {
+        let (a, b) = (self.resolve(a), self.resolve(b));
+        match (&a, &b) {
+            (Ty::Var(x), Ty::Var(y)) if x == y => Ok(()),
+            (Ty::Var(var), other) | (other, Ty::Var(var)) => {
+                if self.occurs(*var, other) {
+                    return Err(Failure::Infinite(*var, other.clone()));
+                }
+                self.substitution[*var] = Some(other.clone());
+                Ok(())
+            }
+            (Ty::Con(x, xs), Ty::Con(y, ys)) if x == y && xs.len() == ys.len() => {
+                for (x, y) in xs.iter().zip(ys) {
+                    self.unify(x, y)?;
+                }
+                Ok(())
+            }
+            (Ty::Array(x), Ty::Array(y)) => self.unify(x, y),
+            (Ty::Hash(xk, xv), Ty::Hash(yk, yv)) => {
+                self.unify(xk, yk)?;
+                self.unify(xv, yv)
+            }
+            (Ty::Function(x), Ty::Function(y))
+                if x.params.len() == y.params.len()
+                    && x.required == y.required
+                    && x.rest.is_some() == y.rest.is_some() =>
+            {
+                for (x, y) in x.params.iter().zip(&y.params) {
+                    self.unify(x, y)?;
+                }
+                if let (Some(x), Some(y)) = (&x.rest, &y.rest) {
+                    self.unify(x, y)?;
+                }
+                self.unify(&x.result, &y.result)
+            }
+            _ => Err(Failure::Mismatch),
+        }
+    }
+
+    // Unifies and reports a failure as `type mismatch in <context>`
+    fn expect(&mut self, expected: &Ty, found: &Ty, context: &str) {
+        let message = match self.unify(expected, found) {
+            Ok(()) => return,
+            Err(Failure::Mismatch) => {
+                let mut names = Vec::new();
+                format!(
+                    "type mismatch in {}: expected {}, found {}",
+                    context,
+                    self.apply(expected).show(&mut names),
+                    self.apply(found).show(&mut names)
+                )
+            }
+            Err(Failure::Infinite(var, ty)) => {
+                let mut names = Vec::new();
+                format!(
+                    "infinite type in {}: {} occurs in {}",
+                    context,
+                    Ty::Var(var).show(&mut names),
+                    self.apply(&ty).show(&mut names)
+                )
+            }
+        };
+        self.error(message);
+    }
+
+    fn generalize(&self, ty: &Ty) -> Scheme {
+        let ty = self.apply(ty);
+        let mut bound = Vec::new();
+        let schemes = self
+            .scopes
+            .iter()
+            .flat_map(|scope| scope.values())
+            .chain(self.methods.values());
+        for scheme in schemes {
+            let mut vars = Vec::new();
+            free_vars(&self.apply(&scheme.ty), &mut vars);
+            bound.extend(vars.into_iter().filter(|var| !scheme.vars.contains(var)));
+        }
+        for result in &self.returns {
+            free_vars(&self.apply(result), &mut bound);
+        }
+        let mut vars = Vec::new();
+        free_vars(&ty, &mut vars);
+        vars.retain(|var| !bound.contains(var));
+        Scheme { vars, ty }
+    }
+
+    fn instantiate(&mut self, scheme: &Scheme) -> Ty {
+        let fresh: HashMap<usize, Ty> =
+            scheme.vars.iter().map(|var| (*var, self.fresh())).collect();
+        let ty = self.apply(&scheme.ty);
+        substitute(&ty, &fresh)
+    }
+
+    fn declare_enum(&mut self, name: &str, variants: &[(&str, usize)]) {
+        let params: usize = variants.iter().map(|(_, fields)| fields).sum();
+        self.enums.insert(name.to_string(), params);
+        let mut offset = 0;
+        for (variant, fields) in variants {
+            let args = self.fresh_args(params);
+            let value = Ty::Con(name.to_string(), args.clone());
+            let ty = match fields {
+                0 => value,
+                _ => function(args[offset..offset + fields].to_vec(), value),
+            };
+            let constructor = self.generalize(&ty);
+            self.bind(variant, constructor.clone());
+            self.variants.insert(
+                variant.to_string(),
+                Variant {
+                    owner: name.to_string(),
+                    offset,
+                    fields: *fields,
+                    constructor,
+                },
+            );
+            offset += fields;
+        }
+    }
+
+    // A struct or an enum with fresh types for its fields
+    fn instance(&mut self, name: &str) -> Option<Ty> {
+        let params = match self.structs.get(name) {
+            Some(fields) => fields.len(),
+            None => *self.enums.get(name)?,
+        };
+        let args = self.fresh_args(params);
+        Some(Ty::Con(name.to_string(), args))
+    }
+
+    // `Point` or `Shape` in `Point.origin()` or `Shape.Circle(1)`, unless a value shadows it
+    fn type_name(&self, expression: &Expressions) -> Option<String> {
+        match expression {
+            Expressions::Identifier(ident)
+                if self.lookup(ident.value()).is_none()
+                    && (self.structs.contains_key(ident.value())
+                        || self.enums.contains_key(ident.value())) =>
+            {
+                Some(ident.value().to_string())
+            }
+            _ => None,
+        }
+    }
+
+    fn annotation(&mut self, annotation: &TypeAnnotation) -> Ty {
+        match annotation {
+            TypeAnnotation::Named(name) => match name.as_str() {
+                "any" => self.fresh(),
+                "int" | "float" | "bool" | "string" | "char" | "null" => named(name),
+                "array" => array(self.fresh()),
+                "hash" => Ty::Hash(Box::new(self.fresh()), Box::new(self.fresh())),
+                _ => match self.instance(name) {
+                    Some(ty) => ty,
+                    None => {
+                        self.error(format!("unknown type: {}", name));
+                        self.fresh()
+                    }
+                },
+            },
+            TypeAnnotation::Array(element) => array(self.annotation(element)),
+            TypeAnnotation::Function(params, result) => {
+                let params = params.iter().map(|param| self.annotation(param)).collect();
+                function(params, self.annotation(result))
+            }
+        }
+    }
+
+    fn block(&mut self, block: &BlockStatement) -> Ty {
+        self.scopes.push(HashMap::new());
+        let mut result = named("null");
+        for statement in block.statements() {
+            result = self.statement(statement);
+        }
+        self.scopes.pop();
+        result
+    }
+
+    fn statement(&mut self, statement: &Statements) -> Ty {
+        match statement {
+            Statements::LetStatement(stmt) => {
+                let saved = self.enter(stmt.position());
+                self.let_statement(stmt);
+                self.position = saved;
+                named("null")
+            }
+            Statements::ReturnStatement(stmt) => {
+                let saved = self.enter(stmt.position());
+                let value = self.infer(stmt.return_value());
+                if let Some(expected) = self.returns.last().cloned() {
+                    self.expect(&expected, &value, "return value");
+                }
+                self.position = saved;
+                // The statement never completes, so it fits wherever it is
+                self.fresh()
+            }
+            Statements::ExpressionStatement(stmt) => self.infer(stmt.expression()),
+            Statements::BlockStatement(block) => self.block(block),
+            Statements::StructStatement(stmt) => {
+                let fields = stmt
+                    .fields()
+                    .iter()
+                    .map(|f| f.value().to_string())
+                    .collect();
+                self.structs.insert(stmt.name().value().to_string(), fields);
+                named("null")
+            }
+            Statements::EnumStatement(stmt) => {
+                let variants: Vec<(&str, usize)> = stmt
+                    .variants()
+                    .iter()
+                    .map(|(variant, fields)| (variant.value(), fields.len()))
+                    .collect();
+                self.declare_enum(stmt.name().value(), &variants);
+                named("null")
+            }
+            Statements::ImplStatement(stmt) => {
+                self.impl_statement(stmt);
+                named("null")
+            }
+            Statements::ImportStatement(stmt) => {
+                self.bind(stmt.alias().value(), Scheme::mono(named(MODULE)));
+                named("null")
+            }
+            Statements::Empty => named("null"),
+        }
+    }
+
+    fn let_statement(&mut self, stmt: &LetStatement) {
+        let declared = stmt
+            .annotation()
+            .map(|annotation| self.annotation(annotation));
+        let context = format!("let {}", stmt.pattern());
+        match (stmt.name(), stmt.value()) {
+            (Some(name), Expressions::FunctionLiteral(literal)) => {
+                // Recursive calls see the function's own type before it is generalized
+                let own = declared.unwrap_or_else(|| self.fresh());
+                self.bind(name.value(), Scheme::mono(own.clone()));
+                let value = self.function(literal, None);
+                self.expect(&own, &value, &context);
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.remove(name.value());
+                }
+                let scheme = self.generalize(&own);
+                self.bind(name.value(), scheme);
+            }
+            _ => {
+                let value = self.infer(stmt.value());
+                if let Some(declared) = &declared {
+                    self.expect(declared, &value, &context);
+                }
+                self.pattern(stmt.pattern(), &value);
+            }
+        }
+    }
+
+    fn impl_statement(&mut self, stmt: &ImplStatement) {
+        let name = stmt.name().value().to_string();
+        let receiver = match self.instance(&name) {
+            Some(receiver) => receiver,
+            None => {
+                self.error(format!("unknown type: {}", name));
+                return;
+            }
+        };
+        // Methods can call each other, so all of them are bound before any is inferred
+        let own = self.fresh_args(stmt.methods().len());
+        for ((method, _), own) in stmt.methods().iter().zip(&own) {
+            let key = (name.clone(), method.value().to_string());
+            self.methods.insert(key, Scheme::mono(own.clone()));
+        }
+        for ((method, literal), own) in stmt.methods().iter().zip(&own) {
+            let value = self.function(literal, Some(receiver.clone()));
+            self.expect(own, &value, &format!("method {}", method));
+        }
+        for ((method, _), own) in stmt.methods().iter().zip(&own) {
+            let key = (name.clone(), method.value().to_string());
+            self.methods.remove(&key);
+            let scheme = self.generalize(own);
+            self.methods.insert(key, scheme);
+        }
+    }
+
+    // Binds what the pattern names, each at its part of `ty`
+    fn pattern(&mut self, pattern: &Pattern, ty: &Ty) {
+        let context = format!("pattern {}", pattern);
+        match pattern {
+            Pattern::Wildcard => {}
+            Pattern::Literal(literal) => {
+                let literal = self.infer(literal);
+                self.expect(ty, &literal, &context);
+            }
+            // A bare `None` is the variant rather than a new name
+            Pattern::Binding(ident) => match self.variants.get(ident.value()) {
+                Some(variant) if variant.fields == 0 => {
+                    let value = self.instantiate(&variant.constructor.clone());
+                    self.expect(ty, &value, &context);
+                }
+                _ => self.bind(ident.value(), Scheme::mono(ty.clone())),
+            },
+            Pattern::Array { head, rest, tail } => {
+                let element = self.fresh();
+                self.expect(ty, &array(element.clone()), &context);
+                for part in head.iter().chain(tail) {
+                    self.pattern(part, &element);
+                }
+                if let Some(Some(ident)) = rest {
+                    self.bind(ident.value(), Scheme::mono(array(element)));
+                }
+            }
+            Pattern::Hash(pairs) => {
+                let (key, value) = (self.fresh(), self.fresh());
+                let hash = Ty::Hash(Box::new(key.clone()), Box::new(value.clone()));
+                self.expect(ty, &hash, &context);
+                for (expression, part) in pairs {
+                    let found = self.infer(expression);
+                    self.expect(&key, &found, &context);
+                    self.pattern(part, &value);
+                }
+            }
+            Pattern::Variant(name, fields) => {
+                let variant = match self.variants.get(name.value()) {
+                    Some(variant) => variant.clone(),
+                    None => {
+                        self.error(format!("unknown variant: {}", name));
+                        return;
+                    }
+                };
+                if fields.len() != variant.fields {
+                    self.error(format!(
+                        "{} expects {} field(s) but received {}",
+                        name,
+                        variant.fields,
+                        fields.len()
+                    ));
+                    return;
+                }
+                let args = match self.instance(&variant.owner) {
+                    Some(Ty::Con(owner, args)) => {
+                        self.expect(ty, &Ty::Con(owner, args.clone()), &context);
+                        args
+                    }
+                    _ => return,
+                };
+                for (i, field) in fields.iter().enumerate() {
+                    self.pattern(field, &args[variant.offset + i]);
+                }
+            }
+            // Every alternative has to bind the same names at the same types
+            Pattern::Alternative(options) => {
+                let names: Vec<String> = options
+                    .first()
+                    .map(|first| {
+                        first
+                            .bindings()
+                            .iter()
+                            .map(|n| n.value().to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let mut previous: Option<Vec<Option<Ty>>> = None;
+                for option in options {
+                    self.pattern(option, ty);
+                    let current: Vec<Option<Ty>> = names
+                        .iter()
+                        .map(|name| {
+                            let scope = self.scopes.last()?;
+                            Some(scope.get(name)?.ty.clone())
+                        })
+                        .collect();
+                    if let Some(previous) = &previous {
+                        for (previous, current) in previous.iter().zip(&current) {
+                            if let (Some(previous), Some(current)) = (previous, current) {
+                                self.expect(previous, current, &context);
+                            }
+                        }
+                    }
+                    previous = Some(current);
+                }
+            }
+        }
+    }
+
+    fn function(&mut self, literal: &FunctionLiteral, receiver: Option<Ty>) -> Ty {
+        let saved = self.enter(literal.position());
+        self.scopes.push(HashMap::new());
+        let (mut params, mut required, mut rest) = (Vec::new(), 0, None);
+        for param in literal.parameters() {
+            let declared = param
+                .annotation()
+                .map(|annotation| self.annotation(annotation));
+            if param.is_rest() {
+                let element = self.fresh();
+                if let Some(declared) = &declared {
+                    self.expect(
+                        declared,
+                        &array(element.clone()),
+                        &format!("parameter {}", param.pattern()),
+                    );
+                }
+                self.pattern(param.pattern(), &array(element.clone()));
+                rest = Some(element);
+                continue;
+            }
+            let ty = declared.unwrap_or_else(|| self.fresh());
+            // `self` in a method is the type the method belongs to
+            if let (Some(receiver), true) = (&receiver, params.is_empty()) {
+                if param.name().is_some_and(|name| name.value() == "self") {
+                    self.expect(&ty, receiver, "self");
+                }
+            }
+            match param.default() {
+                Some(default) => {
+                    let value = self.infer(default);
+                    self.expect(&ty, &value, &format!("default of {}", param.pattern()));
+                }
+                None if required == params.len() => required += 1,
+                None => {}
+            }
+            self.pattern(param.pattern(), &ty);
+            params.push(ty);
+        }
+        let result = match literal.return_type() {
+            Some(annotation) => self.annotation(annotation),
+            None => self.fresh(),
+        };
+        self.returns.push(result.clone());
+        let body = self.block(literal.body());
+        self.expect(&result, &body, "function result");
+        self.returns.pop();
+        self.scopes.pop();
+        self.position = saved;
+        Ty::Function(Box::new(Signature {
+            params,
+            required,
+            rest,
+            result,
+        }))
+    }
+
+    fn infer(&mut self, expression: &Expressions) -> Ty {
+        match expression {
+            Expressions::Identifier(ident) => match self.lookup(ident.value()) {
+                Some(scheme) => self.instantiate(&scheme),
+                None if ident.value() == PRINT => {
+                    let element = self.fresh();
+                    Ty::Function(Box::new(Signature {
+                        params: Vec::new(),
+                        required: 0,
+                        rest: Some(element),
+                        result: named("null"),
+                    }))
+                }
+                // `x?` is one name, without a binding of that name it applies ? to `x`
+                None => match ident
+                    .value()
+                    .strip_suffix('?')
+                    .and_then(|name| self.lookup(name))
+                {
+                    Some(scheme) => {
+                        let value = self.instantiate(&scheme);
+                        self.try_value(&value)
+                    }
+                    None => {
+                        self.error(format!("identifier not found: {}", ident));
+                        self.fresh()
+                    }
+                },
+            },
+            Expressions::BooleanLiteral(_) => named("bool"),
+            Expressions::IntegerLiteral(_) => named("int"),
+            Expressions::FloatLiteral(_) => named("float"),
+            Expressions::StringLiteral(_) => named("string"),
+            Expressions::CharLiteral(_) => named("char"),
+            Expressions::InterpolatedString(string) => {
+                for part in string.parts() {
+                    if let InterpolationPart::Expression(value) = part {
+                        self.infer(value);
+                    }
+                }
+                named("string")
+            }
+            Expressions::ArrayLiteral(literal) => {
+                let element = self.fresh();
+                for (i, value) in literal.elements().iter().enumerate() {
+                    match value {
+                        Expressions::SpreadExpression(spread) => {
+                            let found = self.infer(spread.value());
+                            self.expect(
+                                &array(element.clone()),
+                                &found,
+                                &format!("spread {}", value),
+                            );
+                        }
+                        _ => {
+                            let found = self.infer(value);
+                            self.expect(&element, &found, &format!("array element {}", i + 1));
+                        }
+                    }
+                }
+                array(element)
+            }
+            Expressions::HashLiteral(hash) => {
+                let (key, value) = (self.fresh(), self.fresh());
+                for (k, v) in hash.pairs() {
+                    let found = self.infer(k);
+                    self.expect(&key, &found, "hash key");
+                    let found = self.infer(v);
+                    self.expect(&value, &found, "hash value");
+                }
+                Ty::Hash(Box::new(key), Box::new(value))
+            }
+            Expressions::PrefixExpression(prefix) => {
+                let saved = self.enter(prefix.position());
+                let right = self.infer(prefix.expression_right());
+                let operand = self.resolve(&right);
+                let result = match prefix.operator() {
+                    "!" => named("bool"),
+                    "-" if is(&operand, "int") || is(&operand, "float") => operand,
+                    // Like the infix operators an unknown operand is taken to be an int
+                    "-" | "~" if matches!(operand, Ty::Var(_)) => {
+                        let context = format!("operand of {}", prefix.operator());
+                        self.expect(&named("int"), &operand, &context);
+                        named("int")
+                    }
+                    "~" if is(&operand, "int") => operand,
+                    operator => {
+                        let right = self.apply(&right);
+                        self.error(format!("unknown operator: {}{}", operator, right));
+                        self.fresh()
+                    }
+                };
+                self.position = saved;
+                result
+            }
+            Expressions::InfixExpression(infix) => self.infix(infix),
+            Expressions::IfExpression(if_expression) => {
+                let condition = self.infer(if_expression.condition());
+                self.expect(&named("bool"), &condition, "condition");
+                let consequence = self.block(if_expression.consequence());
+                match if_expression.alternative() {
+                    Some(alternative) => {
+                        let alternative = self.block(alternative);
+                        self.expect(&consequence, &alternative, "else branch");
+                        consequence
+                    }
+                    // Without an else the result is null whenever the condition is false
+                    None => named("null"),
+                }
+            }
+            Expressions::MatchExpression(match_expression) => {
+                let subject = self.infer(match_expression.subject());
+                let result = self.fresh();
+                for (i, arm) in match_expression.arms().iter().enumerate() {
+                    self.scopes.push(HashMap::new());
+                    self.pattern(arm.pattern(), &subject);
+                    if let Some(guard) = arm.guard() {
+                        let guard = self.infer(guard);
+                        self.expect(&named("bool"), &guard, "match guard");
+                    }
+                    let body = self.block(arm.body());
+                    self.expect(&result, &body, &format!("match arm {}", i + 1));
+                    self.scopes.pop();
+                }
+                result
+            }
+            Expressions::CallExpression(call) => {
+                let saved = self.enter(call.position());
+                let result = self.call(call);
+                self.position = saved;
+                result
+            }
+            Expressions::IndexExpression(index) => {
+                let left = self.infer(index.left());
+                let found = self.infer(index.index());
+                match self.resolve(&left) {
+                    Ty::Con(name, _) if name == "string" => {
+                        self.expect(&named("int"), &found, "index");
+                        named("char")
+                    }
+                    Ty::Hash(key, value) => {
+                        self.expect(&key, &found, "hash key");
+                        *value
+                    }
+                    Ty::Array(element) => {
+                        self.expect(&named("int"), &found, "index");
+                        *element
+                    }
+                    Ty::Var(_) => {
+                        let element = self.fresh();
+                        self.expect(&array(element.clone()), &left, "indexed value");
+                        self.expect(&named("int"), &found, "index");
+                        element
+                    }
+                    other => {
+                        let mut names = Vec::new();
+                        self.error(format!(
+                            "index operator not supported: {}[{}]",
+                            self.apply(&other).show(&mut names),
+                            self.apply(&found).show(&mut names)
+                        ));
+                        self.fresh()
+                    }
+                }
+            }
+            Expressions::SpreadExpression(_) => {
+                self.error(format!("cannot infer {} outside of an array", expression));
+                self.fresh()
+            }
+            Expressions::FieldAccess(access) => self.field_access(access),
+            Expressions::TryExpression(try_expression) => {
+                let value = self.infer(try_expression.value());
+                self.try_value(&value)
+            }
+            Expressions::FunctionLiteral(literal) => self.function(literal, None),
+            Expressions::StructLiteral(literal) => {
+                let name = literal.name().value();
+                let (fields, ty) = match (self.structs.get(name).cloned(), self.instance(name)) {
+                    (Some(fields), Some(ty)) => (fields, ty),
+                    _ => {
+                        self.error(format!("unknown struct: {}", name));
+                        return self.fresh();
+                    }
+                };
+                let args = match &ty {
+                    Ty::Con(_, args) => args.clone(),
+                    _ => Vec::new(),
+                };
+                for (field, value) in literal.fields() {
+                    let found = self.infer(value);
+                    match fields.iter().position(|f| f == field.value()) {
+                        Some(i) => self.expect(&args[i], &found, &format!("field {}", field)),
+                        None => self.error(format!("{} has no field '{}'", name, field)),
+                    }
+                }
+                if let Some(base) = literal.base() {
+                    let found = self.infer(base);
+                    self.expect(&ty, &found, &format!("update of {}", name));
+                }
+                ty
+            }
+            Expressions::Empty => self.fresh(),
+        }
+    }
+
+    fn infix(&mut self, infix: &InfixExpression) -> Ty {
+        let saved = self.enter(infix.position());
+        let left = self.infer(infix.expression_left());
+        let right = self.infer(infix.expression_right());
+        let operator = infix.operator();
+        // An int meeting a float is promoted like the evaluator does, as long as
+        // both types are already known
+        let (l, r) = (self.resolve(&left), self.resolve(&right));
+        let promoted = (is(&l, "int") && is(&r, "float")) || (is(&l, "float") && is(&r, "int"));
+        let unified = match promoted {
+            true => Ok(()),
+            false => self.unify(&left, &right),
+        };
+        let result = match unified {
+            Ok(()) => {
+                let operand = match promoted {
+                    true => named("float"),
+                    false => self.resolve(&left),
+                };
+                // A variable could stand for any type, only int is sure to support
+                // the operator
+                let operand = match operand {
+                    Ty::Var(_) if !matches!(operator, "==" | "!=") => {
+                        let context = format!("operand of {}", operator);
+                        self.expect(&named("int"), &operand, &context);
+                        named("int")
+                    }
+                    operand => operand,
+                };
+                let numeric = is(&operand, "int") || is(&operand, "float");
+                let text = is(&operand, "string") || is(&operand, "char");
+                match operator {
+                    "==" | "!=" => Some(named("bool")),
+                    "<" | ">" | "<=" | ">=" if numeric || text => Some(named("bool")),
+                    "+" if is(&operand, "char") => Some(named("string")),
+                    "+" if numeric || text => Some(operand),
+                    "-" | "*" | "/" | "%" | "**" if numeric => Some(operand),
+                    "&" | "|" | "^" | "<<" | ">>" if is(&operand, "int") => Some(operand),
+                    _ => None,
+                }
+            }
+            Err(_) => {
+                let mut names = Vec::new();
+                let (l, r) = (
+                    self.apply(&left).show(&mut names),
+                    self.apply(&right).show(&mut names),
+                );
+                self.error(format!("type mismatch: {} {} {}", l, operator, r));
+                Some(self.fresh())
+            }
+        };
+        let result = result.unwrap_or_else(|| {
+            let (l, r) = (self.apply(&left), self.apply(&right));
+            self.error(format!("unknown operator: {} {} {}", l, operator, r));
+            self.fresh()
+        });
+        self.position = saved;
+        result
+    }
+
+    fn field_access(&mut self, access: &FieldAccess) -> Ty {
+        let field = access.field().value().to_string();
+        if let Some(name) = self.type_name(access.object()) {
+            return self.static_member(&name, &field);
+        }
+        let object = self.infer(access.object());
+        match self.resolve(&object) {
+            Ty::Con(name, _) if name == MODULE => self.fresh(),
+            Ty::Con(name, args) if self.structs.contains_key(&name) => {
+                match self.field_index(&name, &field) {
+                    Some((i, false)) => args[i].clone(),
+                    Some((i, true)) => self.try_value(&args[i]),
+                    None => {
+                        self.error(format!("{} has no field '{}'", name, field));
+                        self.fresh()
+                    }
+                }
+            }
+            // A value of unknown type can only be the one struct with such a field
+            Ty::Var(_) => {
+                let owners: Vec<String> = self
+                    .structs
+                    .keys()
+                    .filter(|name| self.field_index(name, &field).is_some())
+                    .cloned()
+                    .collect();
+                match owners.as_slice() {
+                    [owner] => {
+                        let owner = owner.clone();
+                        let instance = self.instance(&owner).unwrap_or_else(|| self.fresh());
+                        self.expect(&instance, &object, &format!("access of {}", access));
+                        self.field_access(access)
+                    }
+                    _ => {
+                        self.error(format!(
+                            "cannot infer which struct has the field '{}'",
+                            field
+                        ));
+                        self.fresh()
+                    }
+                }
+            }
+            other => {
+                let other = self.apply(&other);
+                self.error(format!("{} has no field '{}'", other, field));
+                self.fresh()
+            }
+        }
+    }
+
+    // The position of a field and whether it was named as `v?`, which applies ?
+    // to the field `v` when the struct has no `v?`
+    fn field_index(&self, owner: &str, field: &str) -> Option<(usize, bool)> {
+        let fields = self.structs.get(owner)?;
+        match fields.iter().position(|f| f == field) {
+            Some(i) => Some((i, false)),
+            None => {
+                let stem = field.strip_suffix('?')?;
+                Some((fields.iter().position(|f| f == stem)?, true))
+            }
+        }
+    }
+
+    fn try_value(&mut self, value: &Ty) -> Ty {
+        match self.resolve(value) {
+            Ty::Con(name, args) if name == "Option" || name == "Result" => {
+                // The enclosing function returns the None or the Err as it is
+                let mut early = self.fresh_args(args.len());
+                early[1..].clone_from_slice(&args[1..]);
+                if let Some(expected) = self.returns.last().cloned() {
+                    self.expect(&expected, &Ty::Con(name, early), "return value of ?");
+                }
+                args[0].clone()
+            }
+            Ty::Var(_) => {
+                self.error(String::from(
+                    "cannot infer whether ? applies to an Option or a Result",
+                ));
+                self.fresh()
+            }
+            other => {
+                let other = self.apply(&other);
+                self.error(format!("? expects an Option or a Result, found {}", other));
+                self.fresh()
+            }
+        }
+    }
+
+    // `Shape.Circle` or `Point.origin`
+    fn static_member(&mut self, name: &str, member: &str) -> Ty {
+        let variant = self
+            .variants
+            .get(member)
+            .filter(|variant| variant.owner == name)
+            .map(|variant| variant.constructor.clone());
+        let method = self
+            .methods
+            .get(&(name.to_string(), member.to_string()))
+            .cloned();
+        match variant.or(method) {
+            Some(scheme) => self.instantiate(&scheme),
+            None => {
+                self.error(format!("{} has no member '{}'", name, member));
+                self.fresh()
+            }
+        }
+    }
+
+    fn arguments(&mut self, call: &CallExpression) -> Vec<Ty> {
+        if let Some((name, _)) = call.named().first() {
+            self.error(format!("cannot infer the named argument {}", name));
+        }
+        let mut arguments = Vec::new();
+        for argument in call.arguments() {
+            if let Expressions::SpreadExpression(_) = argument {
+                self.error(format!("cannot infer the spread argument {}", argument));
+            }
+            arguments.push(self.infer(argument));
+        }
+        arguments
+    }
+
+    fn call(&mut self, call: &CallExpression) -> Ty {
+        if let Expressions::FieldAccess(access) = call.function() {
+            return self.method_call(access, call);
+        }
+        if let Expressions::Identifier(ident) = call.function() {
+            if ident.value() == PRINT && self.lookup(PRINT).is_none() {
+                self.arguments(call);
+                return named("null");
+            }
+        }
+        let callee = self.infer(call.function());
+        let arguments = self.arguments(call);
+        self.apply_call(&callee, arguments, &call.function().to_string())
+    }
+
+    fn method_call(&mut self, access: &FieldAccess, call: &CallExpression) -> Ty {
+        let method = access.field().value().to_string();
+        if self.type_name(access.object()).is_some() {
+            let callee = self.field_access(access);
+            let arguments = self.arguments(call);
+            return self.apply_call(&callee, arguments, &method);
+        }
+        let receiver = self.infer(access.object());
+        let arguments = self.arguments(call);
+        let with_receiver =
+            |arguments: &[Ty]| [vec![receiver.clone()], arguments.to_vec()].concat();
+        match self.resolve(&receiver) {
+            Ty::Con(name, _) if name == MODULE => return self.fresh(),
+            Ty::Con(name, args) => {
+                if let Some(scheme) = self.methods.get(&(name.clone(), method.clone())).cloned() {
+                    let callee = self.instantiate(&scheme);
+                    return self.apply_call(&callee, with_receiver(&arguments), &method);
+                }
+                // A field holding a function is called without the receiver
+                let field = self
+                    .structs
+                    .get(&name)
+                    .and_then(|fields| fields.iter().position(|f| *f == method));
+                if let Some(i) = field {
+                    return self.apply_call(&args[i].clone(), arguments, &method);
+                }
+            }
+            // A value of unknown type can only be the one type with such a method
+            Ty::Var(_) => {
+                let owners: Vec<String> = self
+                    .methods
+                    .keys()
+                    .filter(|(_, name)| *name == method)
+                    .map(|(owner, _)| owner.clone())
+                    .collect();
+                if let [owner] = owners.as_slice() {
+                    let owner = owner.clone();
+                    let instance = self.instance(&owner).unwrap_or_else(|| self.fresh());
+                    self.expect(&instance, &receiver, &format!("receiver of {}", method));
+                    let scheme = self.methods[&(owner, method.clone())].clone();
+                    let callee = self.instantiate(&scheme);
+                    return self.apply_call(&callee, with_receiver(&arguments), &method);
+                }
+            }
+            _ => {}
+        }
+        if let Some(callee) = self.builtin_method(&receiver, &method) {
+            return self.apply_call(&callee, arguments, &method);
+        }
+        // Any function in scope can be called as a method of its first argument
+        if let Some(scheme) = self.lookup(&method) {
+            let callee = self.instantiate(&scheme);
+            return self.apply_call(&callee, with_receiver(&arguments), &method);
+        }
+        let receiver = self.apply(&receiver);
+        self.error(format!("undefined method '{}' for {}", method, receiver));
+        self.fresh()
+    }
+
+    // The methods the evaluator implements for arrays, strings and hashes. The
+    // other functions of the string and array modules work as methods too but
+    // are as untyped as module members.
+    fn builtin_method(&mut self, receiver: &Ty, method: &str) -> Option<Ty> {
+        let in_module =
+            |module: &str| stdlib::module(module).is_some_and(|m| m.get(method).is_some());
+        let kind = match self.resolve(receiver) {
+            Ty::Array(_) => "array",
+            Ty::Hash(..) => "hash",
+            Ty::Con(name, _) if name == "string" => "string",
+            Ty::Var(_) => match method {
+                "first" | "last" | "push" | "map" | "filter" | "reduce" | "join" => "array",
+                "upper" | "lower" | "trim" | "split" | "starts_with" | "ends_with" => "string",
+                "keys" | "values" => "hash",
+                _ if in_module("array") && !in_module("string") => "array",
+                _ if in_module("string") && !in_module("array") => "string",
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let ty = match kind {
+            "array" => {
+                let (element, other) = (self.fresh(), self.fresh());
+                self.unify(receiver, &array(element.clone())).ok()?;
+                match method {
+                    "first" | "last" => function(Vec::new(), element),
+                    "push" => function(vec![element.clone()], array(element)),
+                    "contains" => function(vec![element], named("bool")),
+                    "join" => function(vec![other], named("string")),
+                    "map" => function(vec![function(vec![element], other.clone())], array(other)),
+                    "filter" => function(
+                        vec![function(vec![element.clone()], named("bool"))],
+                        array(element),
+                    ),
+                    "reduce" => {
+                        let step = function(vec![other.clone(), element], other.clone());
+                        function(vec![other.clone(), step], other)
+                    }
+                    "sort" | "reverse" | "rest" => function(Vec::new(), array(element)),
+                    _ if in_module("array") => self.fresh(),
+                    _ => return None,
+                }
+            }
+            "string" => {
+                self.unify(receiver, &named("string")).ok()?;
+                match method {
+                    "upper" | "lower" | "trim" => function(Vec::new(), named("string")),
+                    "split" => function(vec![named("string")], array(named("string"))),
+                    "contains" | "starts_with" | "ends_with" => {
+                        function(vec![named("string")], named("bool"))
+                    }
+                    _ if in_module("string") => self.fresh(),
+                    _ => return None,
+                }
+            }
+            _ => {
+                let (key, value) = (self.fresh(), self.fresh());
+                let hash = Ty::Hash(Box::new(key.clone()), Box::new(value.clone()));
+                self.unify(receiver, &hash).ok()?;
+                match method {
+                    "keys" => function(Vec::new(), array(key)),
+                    "values" => function(Vec::new(), array(value)),
+                    "contains" => function(vec![key], named("bool")),
+                    _ => return None,
+                }
+            }
+        };
+        Some(ty)
+    }
+
+    fn apply_call(&mut self, callee: &Ty, arguments: Vec<Ty>, name: &str) -> Ty {
+        match self.resolve(callee) {
+            Ty::Function(signature) => {
+                let Signature {
+                    params,
+                    required,
+                    rest,
+                    result,
+                } = *signature;
+                let count = arguments.len();
+                if count < required || (count > params.len() && rest.is_none()) {
+                    let expected = match (&rest, required == params.len()) {
+                        (Some(_), _) => format!("at least {}", required),
+                        (None, true) => required.to_string(),
+                        (None, false) => format!("{} to {}", required, params.len()),
+                    };
+                    self.error(format!(
+                        "wrong number of arguments: expected {} but received {}",
+                        expected, count
+                    ));
+                    return result;
+                }
+                for (i, argument) in arguments.iter().enumerate() {
+                    if let Some(expected) = params.get(i).or(rest.as_ref()) {
+                        self.expect(
+                            expected,
+                            argument,
+                            &format!("argument {} of {}", i + 1, name),
+                        );
+                    }
+                }
+                result
+            }
+            Ty::Var(_) => {
+                let result = self.fresh();
+                let signature = function(arguments, result.clone());
+                self.expect(callee, &signature, &format!("call of {}", name));
+                result
+            }
+            other => {
+                let other = self.apply(&other);
+                self.error(format!("not a function: {}", other));
+                self.fresh()
+            }
+        }
+    }
+}
+
+fn substitute(ty: &Ty, fresh: &HashMap<usize, Ty>) -> Ty {
+    match ty {
+        Ty::Var(var) => fresh.get(var).cloned().unwrap_or(Ty::Var(*var)),
+        Ty::Con(name, args) => Ty::Con(
+            name.clone(),
+            args.iter().map(|arg| substitute(arg, fresh)).collect(),
+        ),
+        Ty::Array(element) => array(substitute(element, fresh)),
+        Ty::Hash(key, value) => Ty::Hash(
+            Box::new(substitute(key, fresh)),
+            Box::new(substitute(value, fresh)),
+        ),
+        Ty::Function(signature) => Ty::Function(Box::new(Signature {
+            params: signature
+                .params
+                .iter()
+                .map(|param| substitute(param, fresh))
+                .collect(),
+            required: signature.required,
+            rest: signature.rest.as_ref().map(|rest| substitute(rest, fresh)),
+            result: substitute(&signature.result, fresh),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lexer::Lexer, parser::Parser};
+
+    fn parse(input: &str) -> Program {
+        Parser::new(Lexer::new(input.into()))
+            .parse_program()
+            .expect("Program did not parse properly")
+    }
+
+    fn type_of(input: &str) -> Result<String, Vec<String>> {
+        Inferer::new()
+            .type_of(&parse(input))
+            .map(|ty| ty.to_string())
+            .map_err(|errors| errors.iter().map(|e| e.to_string()).collect())
+    }
+
+    #[test]
+    fn test_inferred_types() {
+        let inputs = [
+            ("fn(x) { x + 1 }", "int -> int"),
+            ("fn(x) { x }", "'a -> 'a"),
+            ("fn(x, y) { x + y }", "(int, int) -> int"),
+            ("fn(x, y) { x < y }", "(int, int) -> bool"),
+            ("fn(x, y) { x == y }", "('a, 'a) -> bool"),
+            ("fn(x) { -x }", "int -> int"),
+            ("fn(s) { s + \"!\" }", "string -> string"),
+            (
+                "fn(f, g) { fn(x) { f(g(x)) } }",
+                "('a -> 'b, 'c -> 'a) -> 'c -> 'b",
+            ),
+            ("let id = fn(x) { x }; let n = id(1); id(\"s\")", "string"),
+            (
+                "let fact = fn(n) { if (n < 2) { 1 } else { n * fact(n - 1) } }; fact",
+                "int -> int",
+            ),
+            ("fn(a: string, b = 1) { a }", "(string, int?) -> string"),
+            ("fn(...xs) { xs }", "(...['a]) -> ['a]"),
+            ("range", "(int, int) -> [int]"),
+            ("map", "(['a], 'a -> 'b) -> ['b]"),
+            ("{\"a\": 1.5}", "{string: float}"),
+            ("Some(1)", "Option<int>"),
+            ("struct B { v } let b = B { v: Some(4) }; b.v? * 2", "int"),
+            (
+                "fn(o) { match (o) { Some(x) => x, None => 0 } }",
+                "Option<int> -> int",
+            ),
+            (
+                "fn(r: Result) { let v = r?; Ok(v + 1) }",
+                "Result<int, 'a> -> Result<int, 'a>",
+            ),
+            (
+                "struct Point { x, y } fn(p) { p.x + 1 }",
+                "Point<int, 'a> -> int",
+            ),
+            (
+                "struct P { x } impl P { fn get(self) { self.x } } P { x: \"s\" }.get()",
+                "string",
+            ),
+            ("fn(s) { s.upper() }", "string -> string"),
+            ("fn(xs) { xs.sort() }", "['a] -> ['a]"),
+            ("fn(x) { print(x, 1) }", "'a -> null"),
+            ("1 + 1.5", "float"),
+            ("2 ** 0.5 < 1", "bool"),
+            ("fn(x: int) { x * 0.5 }", "int -> float"),
+        ];
+        for (input, expected) in inputs {
+            assert_eq!(Ok(expected.to_string()), type_of(input), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_inference_errors() {
+        let inputs = [
+            (
+                "let f = fn(x) { x + 1 };\nf(\"s\")",
+                "2:2: type mismatch in argument 1 of f: expected int, found string",
+            ),
+            ("1 + \"a\"", "1:3: type mismatch: int + string"),
+            ("1 & 1.5", "1:3: unknown operator: int & float"),
+            ("true - false", "1:6: unknown operator: bool - bool"),
+            (
+                "fn(x) { x(x) }",
+                "1:10: infinite type in call of x: 'a occurs in 'a -> 'b",
+            ),
+            (
+                "let f = fn(x) { x }; f(1, 2)",
+                "1:23: wrong number of arguments: expected 1 but received 2",
+            ),
+            (
+                "let x: int = \"s\";",
+                "1:1: type mismatch in let x: expected int, found string",
+            ),
+            (
+                "if (1) { 2 }",
+                "1:1: type mismatch in condition: expected bool, found int",
+            ),
+            (
+                "if (true) { 1 } else { \"a\" }",
+                "1:1: type mismatch in else branch: expected int, found string",
+            ),
+            ("y", "1:1: identifier not found: y"),
+            (
+                "let add = fn(x, y) { x + y }; add(true, 1)",
+                "1:34: type mismatch in argument 1 of add: expected int, found bool",
+            ),
+            // Parameters are not generalized, only let-bound functions are
+            (
+                "fn(f) { [f(1), f(\"a\")] }",
+                "1:17: type mismatch in argument 1 of f: expected int, found string",
+            ),
+            (
+                "fn(r) { r? }",
+                "1:1: cannot infer whether ? applies to an Option or a Result",
+            ),
+            (
+                "fn() -> string { return 1; }",
+                "1:18: type mismatch in return value: expected string, found int",
+            ),
+        ];
+        for (input, expected) in inputs {
+            assert_eq!(
+                Err(vec![expected.to_string()]),
+                type_of(input),
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_inferer_keeps_declarations() {
+        let mut inferer = Inferer::new();
+        assert!(inferer
+            .check_program(&parse("let twice = fn(f, x) { f(f(x)) };"))
+            .is_ok());
+        let ty = inferer.type_of(&parse("twice")).unwrap();
+        assert_eq!("('a -> 'a, 'a) -> 'a", ty.to_string());
+        // A program that fails leaves no bindings behind
+        assert!(inferer
+            .check_program(&parse("let n = 1; n + \"s\""))
+            .is_err());
+        assert!(inferer.type_of(&parse("n")).is_err());
+        // `x?` on a later line still finds the names bound before
+        assert!(inferer
+            .check_program(&parse("let ok? = fn(x) { x > 0 }; let r = Some(1);"))
+            .is_ok());
+        let ty = inferer.type_of(&parse("[1].map(ok?)")).unwrap();
+        assert_eq!("[bool]", ty.to_string());
+        let ty = inferer.type_of(&parse("fn() { Some(r? + 1) }")).unwrap();
+        assert_eq!("() -> Option<int>", ty.to_string());
+        assert!(infer(&parse(crate::prelude::PRELUDE)).is_ok());
+    }
+}
@@ -1,10 +1,35 @@
 use std::fmt::Display;
 
+// Pieces of an interpolated string, the code of `${...}` is kept as source
+// text and parsed into an expression by the parser
+#[derive(Debug, PartialEq, Clone, PartialOrd)]
+pub enum StringPart {
+    Text(String),
+    Code(String),
+}
+
+// Where a token starts in the source, both counted from 1. Nodes built
+// outside the parser are at 0:0.
+#[derive(Debug, PartialEq, Clone, Copy, Default, PartialOrd)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Default, PartialOrd)]
 pub enum Token {
     Ident(String),
     Integer(String),
+    Float(String),
     String(String),
+    InterpolatedString(String, Vec<StringPart>),
+    Char(String),
     Function,
     Let,
     Lparen,
@@ -15,11 +40,20 @@ pub enum Token {
     Rsquare,
     Comma,
     Semicolon,
+    Colon,
+    Dot,
+    Question,
+    DotDot,
+    Ellipsis,
+    FatArrow,
+    Arrow,
     Assign,
     Plus,
     Minus,
     Lt,
     Gt,
+    LtEq,
+    GtEq,
     Equal,
     And,
     Or,
@@ -27,12 +61,24 @@ pub enum Token {
     If,
     Else,
     Return,
+    Match,
+    Struct,
+    Impl,
+    Enum,
+    Import,
+    Export,
+    As,
     True,
     False,
     Divide,
     Multiply,
+    Modulo,
+    Power,
+    Caret,
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
     Bang,
-    SingleQuote,
     Eof,
     #[default]
     Illegal,
@@ -43,7 +89,10 @@ impl Token {
         match self {
             Token::Ident(v) => v,
             Token::Integer(v) => v,
+            Token::Float(v) => v,
             Token::String(v) => v,
+            Token::InterpolatedString(v, _) => v,
+            Token::Char(v) => v,
             Token::Function => "fn",
             Token::Let => "let",
             Token::Lparen => "(",
@@ -54,11 +103,20 @@ impl Token {
             Token::Rsquare => "]",
             Token::Comma => ",",
             Token::Semicolon => ";",
+            Token::Colon => ":",
+            Token::Dot => ".",
+            Token::Question => "?",
+            Token::DotDot => "..",
+            Token::Ellipsis => "...",
+            Token::FatArrow => "=>",
+            Token::Arrow => "->",
             Token::Assign => "=",
             Token::Plus => "+",
             Token::Minus => "-",
             Token::Lt => "<",
             Token::Gt => ">",
+            Token::LtEq => "<=",
+            Token::GtEq => ">=",
             Token::Equal => "==",
             Token::And => "&",
             Token::Or => "|",
@@ -66,12 +124,24 @@ impl Token {
             Token::If => "if",
             Token::Else => "else",
             Token::Return => "return",
+            Token::Match => "match",
+            Token::Struct => "struct",
+            Token::Impl => "impl",
+            Token::Enum => "enum",
+            Token::Import => "import",
+            Token::Export => "export",
+            Token::As => "as",
             Token::True => "true",
             Token::False => "false",
             Token::Divide => "/",
             Token::Multiply => "*",
+            Token::Modulo => "%",
+            Token::Power => "**",
+            Token::Caret => "^",
+            Token::Tilde => "~",
+            Token::ShiftLeft => "<<",
+            Token::ShiftRight => ">>",
             Token::Bang => "!",
-            Token::SingleQuote => "'",
             Token::Eof => "EOF",
             Token::Illegal => "Illegal",
         }
@@ -81,7 +151,10 @@ impl Token {
         match self {
             Token::Ident(_) => String::from("Ident"),
             Token::Integer(_) => String::from("Integer"),
+            Token::Float(_) => String::from("Float"),
             Token::String(_) => String::from("String"),
+            Token::InterpolatedString(_, _) => String::from("InterpolatedString"),
+            Token::Char(_) => String::from("Char"),
             Token::Function => String::from("Function"),
             Token::Let => String::from("Let"),
             Token::Lparen => String::from("Lparen"),
@@ -92,11 +165,20 @@ impl Token {
             Token::Rsquare => String::from("Rsquare"),
             Token::Comma => String::from("Comma"),
             Token::Semicolon => String::from("Semicolon"),
+            Token::Colon => String::from("Colon"),
+            Token::Dot => String::from("Dot"),
+            Token::Question => String::from("Question"),
+            Token::DotDot => String::from("DotDot"),
+            Token::Ellipsis => String::from("Ellipsis"),
+            Token::FatArrow => String::from("FatArrow"),
+            Token::Arrow => String::from("Arrow"),
             Token::Assign => String::from("Assign"),
             Token::Plus => String::from("Plus"),
             Token::Minus => String::from("Minus"),
             Token::Lt => String::from("Lt"),
             Token::Gt => String::from("Gt"),
+            Token::LtEq => String::from("LtEq"),
+            Token::GtEq => String::from("GtEq"),
             Token::Equal => String::from("Equal"),
             Token::And => String::from("And"),
             Token::Or => String::from("Or"),
@@ -104,12 +186,24 @@ impl Token {
             Token::If => String::from("If"),
             Token::Else => String::from("Else"),
             Token::Return => String::from("Return"),
+            Token::Match => String::from("Match"),
+            Token::Struct => String::from("Struct"),
+            Token::Impl => String::from("Impl"),
+            Token::Enum => String::from("Enum"),
+            Token::Import => String::from("Import"),
+            Token::Export => String::from("Export"),
+            Token::As => String::from("As"),
             Token::True => String::from("True"),
             Token::False => String::from("False"),
             Token::Divide => String::from("Divide"),
             Token::Multiply => String::from("Multiply"),
+            Token::Modulo => String::from("Modulo"),
+            Token::Power => String::from("Power"),
+            Token::Caret => String::from("Caret"),
+            Token::Tilde => String::from("Tilde"),
+            Token::ShiftLeft => String::from("ShiftLeft"),
+            Token::ShiftRight => String::from("ShiftRight"),
             Token::Bang => String::from("Bang"),
-            Token::SingleQuote => String::from("SingleQuote"),
             Token::Eof => String::from("Eof"),
             Token::Illegal => String::from("Illegal"),
         }
@@ -129,6 +223,12 @@ pub struct Lexer {
     position: usize,
     input: Vec<u8>,
     ch: u8,
+    errors: Vec<String>,
+    doc_comments: Vec<String>,
+    line: usize,
+    // Offset of the first byte of the current line
+    line_start: usize,
+    token_position: Position,
 }
 
 impl Lexer {
@@ -138,15 +238,25 @@ impl Lexer {
             read_position: 0,
             input: input.into_bytes(),
             ch: 0,
+            errors: Vec::new(),
+            doc_comments: Vec::new(),
+            line: 1,
+            line_start: 0,
+            token_position: Position::default(),
         };
         l.read_char();
         l
     }
 
     pub fn next_token(&mut self) -> Token {
-        self.skip_whitespace();
+        self.skip_trivia();
+        self.token_position = self.position();
         let tok = match self.ch {
-            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+            b'r' if matches!(self.peek(), b'"' | b'#') => {
+                self.read_char();
+                return self.read_raw_str();
+            }
+            _ if is_ident_start(self.current_char().0) => {
                 let ident = self.read_ident();
                 let tok = match ident {
                     "fn" => Token::Function,
@@ -154,6 +264,13 @@ impl Lexer {
                     "if" => Token::If,
                     "else" => Token::Else,
                     "return" => Token::Return,
+                    "match" => Token::Match,
+                    "struct" => Token::Struct,
+                    "impl" => Token::Impl,
+                    "enum" => Token::Enum,
+                    "import" => Token::Import,
+                    "export" => Token::Export,
+                    "as" => Token::As,
                     "true" => Token::True,
                     "false" => Token::False,
                     _ => Token::Ident(ident.into()),
@@ -161,9 +278,10 @@ impl Lexer {
                 return tok;
             }
             b'0'..=b'9' => {
-                let ident = self.read_int();
-                let tok = Token::Integer(ident.into());
-                return tok;
+                return match self.read_number() {
+                    Ok(tok) => tok,
+                    Err(msg) => self.error(msg),
+                };
             }
             b'(' => Token::Lparen,
             b')' => Token::Rparen,
@@ -172,20 +290,51 @@ impl Lexer {
             b'[' => Token::Lsquare,
             b']' => Token::Rsquare,
             b';' => Token::Semicolon,
+            b':' => Token::Colon,
+            b'.' if *self.peek() == b'.' => {
+                self.read_char();
+                if *self.peek() == b'.' {
+                    self.read_char();
+                    Token::Ellipsis
+                } else {
+                    Token::DotDot
+                }
+            }
+            b'.' => Token::Dot,
             b',' => Token::Comma,
             b'"' => {
                 self.read_char();
-                return Token::String(self.read_str().into());
+                return match self.read_str() {
+                    Ok(tok) => tok,
+                    Err(msg) => self.error(msg),
+                };
+            }
+            b'\'' => {
+                self.read_char();
+                return match self.read_char_literal() {
+                    Ok(value) => Token::Char(value.to_string()),
+                    Err(msg) => self.error(msg),
+                };
             }
             b'=' => match self.peek() {
                 b'=' => {
                     self.read_char();
                     Token::Equal
                 }
+                b'>' => {
+                    self.read_char();
+                    Token::FatArrow
+                }
                 _ => Token::Assign,
             },
             b'+' => Token::Plus,
-            b'-' => Token::Minus,
+            b'-' => match self.peek() {
+                b'>' => {
+                    self.read_char();
+                    Token::Arrow
+                }
+                _ => Token::Minus,
+            },
             b'!' => match self.peek() {
                 b'=' => {
                     self.read_char();
@@ -193,21 +342,60 @@ impl Lexer {
                 }
                 _ => Token::Bang,
             },
-            b'*' => Token::Multiply,
+            b'*' => match self.peek() {
+                b'*' => {
+                    self.read_char();
+                    Token::Power
+                }
+                _ => Token::Multiply,
+            },
+            b'%' => Token::Modulo,
+            b'^' => Token::Caret,
+            b'~' => Token::Tilde,
             b'/' => Token::Divide,
-            b'\'' => Token::SingleQuote,
-            b'<' => Token::Lt,
-            b'>' => Token::Gt,
+            b'<' => match self.peek() {
+                b'=' => {
+                    self.read_char();
+                    Token::LtEq
+                }
+                b'<' => {
+                    self.read_char();
+                    Token::ShiftLeft
+                }
+                _ => Token::Lt,
+            },
+            b'>' => match self.peek() {
+                b'=' => {
+                    self.read_char();
+                    Token::GtEq
+                }
+                b'>' => {
+                    self.read_char();
+                    Token::ShiftRight
+                }
+                _ => Token::Gt,
+            },
             b'&' => Token::And,
             b'|' => Token::Or,
-            0 => Token::Eof,
-            _ => Token::Illegal,
+            b'?' => Token::Question,
+            0 if self.at_end() => Token::Eof,
+            _ => {
+                let (ch, width) = self.current_char();
+                for _ in 1..width {
+                    self.read_char();
+                }
+                self.error(format!("unexpected character {:?}", ch))
+            }
         };
         self.read_char();
         tok
     }
 
     fn read_char(&mut self) {
+        if self.ch == b'\n' {
+            self.line += 1;
+            self.line_start = self.read_position;
+        }
         if self.read_position >= self.input.len() {
             self.ch = 0;
         } else {
@@ -223,9 +411,81 @@ impl Lexer {
         }
     }
 
+    // Skips whitespace and comments. `///` doc comments are kept until the
+    // next call to take_doc_comments so the parser can attach them.
+    fn skip_trivia(&mut self) {
+        loop {
+            self.skip_whitespace();
+            match (self.ch, *self.peek()) {
+                (b'/', b'/') => self.read_line_comment(),
+                (b'/', b'*') => self.skip_block_comment(),
+                _ => return,
+            }
+        }
+    }
+
+    fn read_line_comment(&mut self) {
+        let pos = self.position;
+        while self.ch != b'\n' && !self.at_end() {
+            self.read_char();
+        }
+        let comment = String::from_utf8_lossy(&self.input[pos..self.position]);
+        // Four or more slashes make an ordinary comment again
+        if let Some(doc) = comment.strip_prefix("///") {
+            if !doc.starts_with('/') {
+                let doc = doc.strip_prefix(' ').unwrap_or(doc);
+                self.doc_comments.push(doc.trim_end().to_string());
+            }
+        }
+    }
+
+    // Block comments nest, so code containing comments can be commented out
+    fn skip_block_comment(&mut self) {
+        let start = self.position();
+        let mut depth = 0;
+        loop {
+            if self.at_end() {
+                self.errors
+                    .push(format!("unterminated block comment starting at {}", start));
+                return;
+            }
+            match (self.ch, *self.peek()) {
+                (b'/', b'*') => {
+                    depth += 1;
+                    self.read_char();
+                }
+                (b'*', b'/') => {
+                    depth -= 1;
+                    self.read_char();
+                    if depth == 0 {
+                        self.read_char();
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.read_char();
+        }
+    }
+
+    pub fn take_doc_comments(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.doc_comments)
+    }
+
+    // Identifiers may end in `?` or `!` by convention for predicates and
+    // mutating functions, as long as it does not start a `!=`
     fn read_ident(&mut self) -> &str {
         let pos = self.position;
-        while self.ch.is_ascii_alphabetic() || self.ch == b'_' && self.ch != 0 {
+        loop {
+            let (ch, width) = self.current_char();
+            if !is_ident_continue(ch) {
+                break;
+            }
+            for _ in 0..width {
+                self.read_char();
+            }
+        }
+        if matches!(self.ch, b'?' | b'!') && *self.peek() != b'=' {
             self.read_char();
         }
         match std::str::from_utf8(&self.input[pos..self.position]) {
@@ -234,37 +494,374 @@ impl Lexer {
         }
     }
 
-    fn read_int(&mut self) -> &str {
+    fn read_number(&mut self) -> Result<Token, String> {
         let pos = self.position;
-        while self.ch.is_ascii_digit() && self.ch != 0 {
+        if self.ch == b'0' && matches!(self.peek(), b'x' | b'o' | b'b') {
+            return self.read_prefixed_int();
+        }
+        self.read_digits();
+        if self.ch == b'.' && self.peek().is_ascii_digit() {
             self.read_char();
+            self.read_digits();
         }
-        match std::str::from_utf8(&self.input[pos..self.position]) {
-            Ok(valid) => valid,
-            _ => panic!("Non UTF-8 Character encounterd"),
+        if self.ch == b'e' || self.ch == b'E' {
+            let exponent_start = match self.peek() {
+                b'+' | b'-' => self.input.get(self.read_position + 1).copied().unwrap_or(0),
+                ch => *ch,
+            };
+            if exponent_start.is_ascii_digit() {
+                self.read_char();
+                if self.ch == b'+' || self.ch == b'-' {
+                    self.read_char();
+                }
+                self.read_digits();
+            }
+        }
+        let literal = String::from_utf8_lossy(&self.input[pos..self.position]).into_owned();
+        check_separators(&literal, 10, &literal)?;
+        if !literal.contains(['.', 'e', 'E']) {
+            return Ok(Token::Integer(literal));
+        }
+        match literal.replace('_', "").parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(Token::Float(literal)),
+            _ => Err(format!("float literal out of range: {}", literal)),
+        }
+    }
+
+    fn read_digits(&mut self) {
+        while self.ch.is_ascii_digit() || self.ch == b'_' {
+            self.read_char();
+        }
+    }
+
+    // Reads 0x, 0o and 0b literals. Every alphanumeric character following
+    // the prefix belongs to the literal so `0b102` is reported as malformed.
+    fn read_prefixed_int(&mut self) -> Result<Token, String> {
+        let pos = self.position;
+        self.read_char();
+        let (radix, name) = match self.ch {
+            b'x' => (16, "hexadecimal"),
+            b'o' => (8, "octal"),
+            _ => (2, "binary"),
+        };
+        self.read_char();
+        let digits_start = self.position;
+        while self.ch.is_ascii_alphanumeric() || self.ch == b'_' {
+            self.read_char();
         }
+        let literal = String::from_utf8_lossy(&self.input[pos..self.position]).into_owned();
+        let digits = &literal[digits_start - pos..];
+        if let Some(bad) = digits.chars().find(|ch| *ch != '_' && !ch.is_digit(radix)) {
+            return Err(format!(
+                "invalid digit {:?} in {} literal {}",
+                bad, name, literal
+            ));
+        }
+        if digits.chars().all(|ch| ch == '_') {
+            return Err(format!("missing digits in {} literal {}", name, literal));
+        }
+        check_separators(digits, radix, &literal)?;
+        Ok(Token::Integer(literal))
     }
 
-    fn peek(&mut self) -> &u8 {
+    fn peek(&self) -> &u8 {
         if self.read_position < self.input.len() {
             return &self.input[self.read_position];
         }
         &0
     }
 
-    fn read_str(&mut self) -> &str {
+    // Position of the token last returned by next_token
+    pub fn token_position(&self) -> Position {
+        self.token_position
+    }
+
+    // Position of the current character
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.position - self.line_start + 1,
+        }
+    }
+
+    // Errors are collected so the parser can report them alongside its own
+    pub fn take_errors(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.errors)
+    }
+
+    fn error(&mut self, msg: String) -> Token {
+        self.errors.push(msg);
+        Token::Illegal
+    }
+
+    fn at_end(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    // Strings may span several lines, a backslash at the end of a line
+    // skips the newline and the indentation of the next one. After a bad
+    // escape the rest of the string is still consumed so lexing can resume.
+    fn read_str(&mut self) -> Result<Token, String> {
+        let start = self.position;
+        let mut bytes = Vec::new();
+        let mut parts = Vec::new();
+        let mut error = None;
+        loop {
+            if self.at_end() {
+                return Err(format!(
+                    "unterminated string starting at {}",
+                    self.token_position
+                ));
+            }
+            match self.ch {
+                b'"' => break,
+                b'\\' => {
+                    self.read_char();
+                    if self.ch == b'\n' {
+                        self.skip_whitespace();
+                        continue;
+                    }
+                    if self.at_end() {
+                        continue;
+                    }
+                    match self.read_escape() {
+                        Ok(ch) => bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+                        Err(msg) => {
+                            error.get_or_insert(msg);
+                            continue;
+                        }
+                    }
+                }
+                b'$' if *self.peek() == b'{' => {
+                    let code = self.read_interpolation()?;
+                    parts.push(StringPart::Text(utf8(std::mem::take(&mut bytes))?));
+                    parts.push(StringPart::Code(code));
+                }
+                ch => bytes.push(ch),
+            }
+            self.read_char();
+        }
+        let end = self.position;
+        self.read_char();
+        if let Some(msg) = error {
+            return Err(msg);
+        }
+        if parts.is_empty() {
+            return Ok(Token::String(utf8(bytes)?));
+        }
+        parts.push(StringPart::Text(utf8(bytes)?));
+        parts.retain(|part| part != &StringPart::Text(String::new()));
+        let source = String::from_utf8_lossy(&self.input[start..end]).into_owned();
+        Ok(Token::InterpolatedString(source, parts))
+    }
+
+    // Reads the code of a `${...}` up to its matching brace, which is left as
+    // the current character
+    fn read_interpolation(&mut self) -> Result<String, String> {
+        let start = self.position();
+        self.read_char();
+        self.read_char();
         let pos = self.position;
-        while &self.ch != &b'"' && self.ch != 0 {
+        let mut depth = 0;
+        loop {
+            if self.at_end() {
+                return Err(format!("unterminated interpolation starting at {}", start));
+            }
+            match self.ch {
+                b'{' => depth += 1,
+                b'}' if depth == 0 => break,
+                b'}' => depth -= 1,
+                b'"' | b'\'' => self.skip_quoted(),
+                _ => {}
+            }
             self.read_char();
         }
+        Ok(String::from_utf8_lossy(&self.input[pos..self.position]).into_owned())
+    }
+
+    // Skips a string or char literal nested in an interpolation, leaves the
+    // closing quote as the current character
+    fn skip_quoted(&mut self) {
+        let quote = self.ch;
         self.read_char();
-        match std::str::from_utf8(&self.input[pos..self.position - 1]) {
-            Ok(valid) => valid,
-            _ => panic!("Non UTF-8 Character encounterd"),
+        while self.ch != quote && !self.at_end() {
+            if self.ch == b'\\' {
+                self.read_char();
+            }
+            self.read_char();
+        }
+    }
+
+    // Reads the escape sequence following a backslash, leaves its last
+    // character as the current one
+    fn read_escape(&mut self) -> Result<char, String> {
+        match self.ch {
+            b'n' => Ok('\n'),
+            b't' => Ok('\t'),
+            b'r' => Ok('\r'),
+            b'0' => Ok('\0'),
+            b'"' => Ok('"'),
+            b'\'' => Ok('\''),
+            b'\\' => Ok('\\'),
+            b'$' => Ok('$'),
+            b'u' => self.read_unicode_escape(),
+            other => Err(format!("unknown escape sequence \\{}", other as char)),
+        }
+    }
+
+    fn read_char_literal(&mut self) -> Result<char, String> {
+        let start = self.token_position;
+        let value = match self.ch {
+            _ if self.at_end() => {
+                return Err(format!(
+                    "unterminated character literal starting at {}",
+                    start
+                ))
+            }
+            b'\'' => {
+                self.read_char();
+                return Err(String::from("empty character literal"));
+            }
+            b'\\' => {
+                self.read_char();
+                let value = self.read_escape();
+                self.read_char();
+                value
+            }
+            _ => Ok(self.read_utf8_char()),
+        };
+        if self.ch != b'\'' {
+            // Skip the rest of the literal so lexing can resume after it
+            while self.ch != b'\'' && self.ch != b'\n' && !self.at_end() {
+                self.read_char();
+            }
+            if self.ch != b'\'' {
+                return Err(format!(
+                    "unterminated character literal starting at {}",
+                    start
+                ));
+            }
+            self.read_char();
+            return Err(String::from(
+                "character literal may only contain one character",
+            ));
+        }
+        self.read_char();
+        value
+    }
+
+    // Decodes the character starting at the current byte and returns it with
+    // its length in bytes
+    fn current_char(&self) -> (char, usize) {
+        let width = match self.ch {
+            0xF0.. => 4,
+            0xE0.. => 3,
+            0xC0.. => 2,
+            _ => 1,
+        };
+        let end = (self.position + width).min(self.input.len());
+        let value = std::str::from_utf8(&self.input[self.position.min(end)..end])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        (value, width)
+    }
+
+    fn read_utf8_char(&mut self) -> char {
+        let (value, width) = self.current_char();
+        for _ in 0..width {
+            self.read_char();
+        }
+        value
+    }
+
+    // Reads the `{XXXX}` part of a `\u{XXXX}` escape, leaves the closing brace as
+    // the current character
+    fn read_unicode_escape(&mut self) -> Result<char, String> {
+        self.read_char();
+        if self.ch != b'{' {
+            return Err(String::from("expected '{' after \\u"));
+        }
+        self.read_char();
+        let pos = self.position;
+        while self.ch.is_ascii_hexdigit() {
+            self.read_char();
+        }
+        let digits = String::from_utf8_lossy(&self.input[pos..self.position]).into_owned();
+        if self.ch != b'}' || digits.is_empty() || digits.len() > 6 {
+            return Err(String::from("malformed unicode escape, expected \\u{XXXX}"));
+        }
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| format!("invalid unicode escape \\u{{{}}}", digits))
+    }
+
+    // Raw strings are written r"..." or r#"..."# and keep backslashes as they
+    // are, any number of hashes lets the string contain quotes
+    fn read_raw_str(&mut self) -> Token {
+        let mut hashes = 0;
+        while self.ch == b'#' {
+            hashes += 1;
+            self.read_char();
+        }
+        if self.ch != b'"' {
+            return self.error(String::from("expected '\"' to start raw string"));
+        }
+        self.read_char();
+        let pos = self.position;
+        loop {
+            if self.at_end() {
+                return self.error(format!(
+                    "unterminated raw string starting at {}",
+                    self.token_position
+                ));
+            }
+            if self.ch == b'"' {
+                let end = self.position;
+                let closing = (1..=hashes).all(|i| self.input.get(end + i) == Some(&b'#'));
+                if closing {
+                    for _ in 0..=hashes {
+                        self.read_char();
+                    }
+                    let value = String::from_utf8_lossy(&self.input[pos..end]).into_owned();
+                    return Token::String(value);
+                }
+            }
+            self.read_char();
         }
     }
 }
 
+// Unicode letters are accepted as an approximation of XID_Start/XID_Continue
+fn is_ident_start(ch: char) -> bool {
+    ch == '_' || ch.is_alphabetic()
+}
+
+fn is_ident_continue(ch: char) -> bool {
+    ch == '_' || ch.is_alphanumeric()
+}
+
+// A `_` separator has to be followed by a digit, so `1_`, `1_.5` and `1_e3`
+// are rejected
+fn check_separators(digits: &str, radix: u32, literal: &str) -> Result<(), String> {
+    let bytes = digits.as_bytes();
+    for (i, byte) in bytes.iter().enumerate() {
+        if *byte == b'_'
+            && !bytes
+                .get(i + 1)
+                .is_some_and(|next| (*next as char).is_digit(radix))
+        {
+            return Err(format!("misplaced digit separator in {}", literal));
+        }
+    }
+    Ok(())
+}
+
+fn utf8(bytes: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(bytes).map_err(|_| String::from("string is not valid UTF-8"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,9 +936,7 @@ mod tests {
             Token::Let,
             Token::Ident("ch".into()),
             Token::Assign,
-            Token::SingleQuote,
-            Token::Ident("a".into()),
-            Token::SingleQuote,
+            Token::Char("a".into()),
             Token::Semicolon,
             Token::Let,
             Token::Ident("result".into()),
@@ -364,7 +959,7 @@ mod tests {
     #[test]
     fn logical_operators() {
         let input = r#"
-        !-/*5;
+        !-/ *5;
         5 < 10 > 5;
         if (5 < 10) {
             return true;
@@ -456,4 +1051,381 @@ mod tests {
             assert_eq!(token, l.next_token());
         }
     }
+
+    #[test]
+    fn float_literals() {
+        let input = "3.14 1e-3 2.5E+10 10 7.x 1.";
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Float("3.14".into()),
+            Token::Float("1e-3".into()),
+            Token::Float("2.5E+10".into()),
+            Token::Integer("10".into()),
+            Token::Integer("7".into()),
+            Token::Dot,
+            Token::Ident("x".into()),
+            Token::Integer("1".into()),
+            Token::Dot,
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+    }
+
+    #[test]
+    fn comparison_operators() {
+        let input = "a <= b >= c < d > e";
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Ident("a".into()),
+            Token::LtEq,
+            Token::Ident("b".into()),
+            Token::GtEq,
+            Token::Ident("c".into()),
+            Token::Lt,
+            Token::Ident("d".into()),
+            Token::Gt,
+            Token::Ident("e".into()),
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+    }
+
+    #[test]
+    fn arithmetic_and_bitwise_operators() {
+        let input = "a % b ** c & d | e ^ ~f << g >> h * i";
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Ident("a".into()),
+            Token::Modulo,
+            Token::Ident("b".into()),
+            Token::Power,
+            Token::Ident("c".into()),
+            Token::And,
+            Token::Ident("d".into()),
+            Token::Or,
+            Token::Ident("e".into()),
+            Token::Caret,
+            Token::Tilde,
+            Token::Ident("f".into()),
+            Token::ShiftLeft,
+            Token::Ident("g".into()),
+            Token::ShiftRight,
+            Token::Ident("h".into()),
+            Token::Multiply,
+            Token::Ident("i".into()),
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+    }
+
+    #[test]
+    fn string_escapes() {
+        let input = r##""a\nb\t\"c\"\\" "\u{1F600}\u{e9}" "line one
+line two" "joined \
+           here" r"C:\path\n" r#"say "hi""# "##;
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::String("a\nb\t\"c\"\\".into()),
+            Token::String("😀é".into()),
+            Token::String("line one\nline two".into()),
+            Token::String("joined here".into()),
+            Token::String("C:\\path\\n".into()),
+            Token::String("say \"hi\"".into()),
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+        assert!(l.take_errors().is_empty());
+    }
+
+    #[test]
+    fn string_errors() {
+        let inputs = [
+            (r#""abc"#, "unterminated string starting at 1:1"),
+            ("x = 1;\n  \"abc", "unterminated string starting at 2:3"),
+            (r#"x = "\q""#, "unknown escape sequence \\q"),
+            (r#""\u{110000}""#, "invalid unicode escape \\u{110000}"),
+            (r#""\u1F600""#, "expected '{' after \\u"),
+            (r##"r#"abc"##, "unterminated raw string starting at 1:1"),
+        ];
+
+        for (input, expected) in inputs {
+            let mut l = Lexer::new(input.into());
+            while l.next_token() != Token::Eof {}
+            assert_eq!(vec![String::from(expected)], l.take_errors());
+        }
+    }
+
+    #[test]
+    fn char_literals() {
+        let input = r"'a' 'é' '😀' '\n' '\'' '\u{41}' 'ab' '' 'x";
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Char("a".into()),
+            Token::Char("é".into()),
+            Token::Char("😀".into()),
+            Token::Char("\n".into()),
+            Token::Char("'".into()),
+            Token::Char("A".into()),
+            Token::Illegal,
+            Token::Illegal,
+            Token::Illegal,
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+        assert_eq!(
+            vec![
+                String::from("character literal may only contain one character"),
+                String::from("empty character literal"),
+                String::from("unterminated character literal starting at 1:44"),
+            ],
+            l.take_errors()
+        );
+    }
+
+    #[test]
+    fn interpolated_strings() {
+        let input = r#""hello ${name}!" "${a + b}" "${f("}")}x" "cost: \${x}" "${"#;
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::InterpolatedString(
+                "hello ${name}!".into(),
+                vec![
+                    StringPart::Text("hello ".into()),
+                    StringPart::Code("name".into()),
+                    StringPart::Text("!".into()),
+                ],
+            ),
+            Token::InterpolatedString("${a + b}".into(), vec![StringPart::Code("a + b".into())]),
+            Token::InterpolatedString(
+                r#"${f("}")}x"#.into(),
+                vec![
+                    StringPart::Code(r#"f("}")"#.into()),
+                    StringPart::Text("x".into()),
+                ],
+            ),
+            Token::String("cost: ${x}".into()),
+            Token::Illegal,
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+        assert_eq!(
+            vec![String::from("unterminated interpolation starting at 1:57")],
+            l.take_errors()
+        );
+    }
+
+    #[test]
+    fn comments() {
+        let input = r#"
+        // a line comment
+        let a = 10 / 2; // trailing
+        /* block /* nested */ still comment */ a
+        /// Doubles a number.
+        ///
+        ///   Keeps indentation.
+        //// not a doc comment
+        let double = "// not a comment";
+        /* unterminated
+        "#;
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Let,
+            Token::Ident("a".into()),
+            Token::Assign,
+            Token::Integer("10".into()),
+            Token::Divide,
+            Token::Integer("2".into()),
+            Token::Semicolon,
+            Token::Ident("a".into()),
+            Token::Let,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+        assert_eq!(
+            vec![
+                String::from("Doubles a number."),
+                String::new(),
+                String::from("  Keeps indentation."),
+            ],
+            l.take_doc_comments()
+        );
+
+        let tokens = vec![
+            Token::Ident("double".into()),
+            Token::Assign,
+            Token::String("// not a comment".into()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+        assert!(l.take_doc_comments().is_empty());
+        assert_eq!(
+            vec![String::from("unterminated block comment starting at 10:9")],
+            l.take_errors()
+        );
+    }
+
+    #[test]
+    fn identifiers() {
+        let input = "var1 x_2y _tmp9 empty? push! a!=b c != d ünïcödé 变量 π2 x?y 1abc €";
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Ident("var1".into()),
+            Token::Ident("x_2y".into()),
+            Token::Ident("_tmp9".into()),
+            Token::Ident("empty?".into()),
+            Token::Ident("push!".into()),
+            Token::Ident("a".into()),
+            Token::NotEqual,
+            Token::Ident("b".into()),
+            Token::Ident("c".into()),
+            Token::NotEqual,
+            Token::Ident("d".into()),
+            Token::Ident("ünïcödé".into()),
+            Token::Ident("变量".into()),
+            Token::Ident("π2".into()),
+            Token::Ident("x?".into()),
+            Token::Ident("y".into()),
+            Token::Integer("1".into()),
+            Token::Ident("abc".into()),
+            Token::Illegal,
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+        assert_eq!(
+            vec![String::from("unexpected character '€'")],
+            l.take_errors()
+        );
+    }
+
+    #[test]
+    fn integer_literal_forms() {
+        let input = "0xFF 0o17 0b1010 1_000_000 0xdead_beef 1_0.2_5e1_0 0";
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Integer("0xFF".into()),
+            Token::Integer("0o17".into()),
+            Token::Integer("0b1010".into()),
+            Token::Integer("1_000_000".into()),
+            Token::Integer("0xdead_beef".into()),
+            Token::Float("1_0.2_5e1_0".into()),
+            Token::Integer("0".into()),
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+        assert!(l.take_errors().is_empty());
+    }
+
+    #[test]
+    fn malformed_number_literals() {
+        let inputs = [
+            ("0xFG", "invalid digit 'G' in hexadecimal literal 0xFG"),
+            ("0o18", "invalid digit '8' in octal literal 0o18"),
+            ("0b102", "invalid digit '2' in binary literal 0b102"),
+            ("0x", "missing digits in hexadecimal literal 0x"),
+            ("0b__", "missing digits in binary literal 0b__"),
+            ("1_", "misplaced digit separator in 1_"),
+            ("1__0", "misplaced digit separator in 1__0"),
+            ("1_e5", "misplaced digit separator in 1_e5"),
+            ("0x1_", "misplaced digit separator in 0x1_"),
+            ("1e999", "float literal out of range: 1e999"),
+        ];
+
+        for (input, expected) in inputs {
+            let mut l = Lexer::new(format!("{} + 1", input));
+            assert_eq!(Token::Illegal, l.next_token());
+            assert_eq!(Token::Plus, l.next_token());
+            assert_eq!(vec![String::from(expected)], l.take_errors());
+        }
+    }
+
+    #[test]
+    fn match_tokens() {
+        let input = r#"match (x) { [a, ..rest] => a, {"k": v} => v, x >= 1 => x }"#;
+
+        let mut l = Lexer::new(input.into());
+
+        let tokens = vec![
+            Token::Match,
+            Token::Lparen,
+            Token::Ident("x".into()),
+            Token::Rparen,
+            Token::Lbrace,
+            Token::Lsquare,
+            Token::Ident("a".into()),
+            Token::Comma,
+            Token::DotDot,
+            Token::Ident("rest".into()),
+            Token::Rsquare,
+            Token::FatArrow,
+            Token::Ident("a".into()),
+            Token::Comma,
+            Token::Lbrace,
+            Token::String("k".into()),
+            Token::Colon,
+            Token::Ident("v".into()),
+            Token::Rbrace,
+            Token::FatArrow,
+            Token::Ident("v".into()),
+            Token::Comma,
+            Token::Ident("x".into()),
+            Token::GtEq,
+            Token::Integer("1".into()),
+            Token::FatArrow,
+            Token::Ident("x".into()),
+            Token::Rbrace,
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            assert_eq!(token, l.next_token());
+        }
+    }
 }
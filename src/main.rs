@@ -1,11 +1,60 @@
 mod ast;
+mod bigint;
 mod builtins;
 mod evaluator;
+mod infer;
 mod lexer;
+mod module;
 mod object;
 mod parser;
+mod prelude;
 mod repl;
+mod sandbox;
+mod stdlib;
+mod typecheck;
+
+// Large enough for the evaluator to reach sandbox::DEFAULT_MAX_CALL_DEPTH
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+// repl_rs [--strict] [--prelude FILE | --no-prelude] [SCRIPT]
+fn run(args: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let mut script = None;
+    let mut strict = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--prelude" => {
+                let path = args.next().ok_or("--prelude expects a file")?;
+                let source = std::fs::read_to_string(&path)
+                    .map_err(|err| format!("cannot read {}: {}", path, err))?;
+                prelude::set_source(Some(&source))?;
+            }
+            "--no-prelude" => prelude::set_source(None)?,
+            "--strict" => strict = true,
+            _ if script.is_none() => script = Some(arg),
+            _ => return Err(format!("unexpected argument: {}", arg).into()),
+        }
+    }
+    // The evaluator runs on the large stack spawned below
+    let limits = sandbox::Limits {
+        max_stack: Some(STACK_SIZE / 2),
+        ..sandbox::Limits::default()
+    };
+    match script {
+        Some(path) => repl::run_file(&path, strict, limits),
+        None => repl::start(strict, limits),
+    }
+}
 
 fn main() {
-    repl::start();
+    let repl = std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(|| {
+            if let Err(err) = run(std::env::args().skip(1).collect()) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        })
+        .expect("could not spawn the repl thread");
+    repl.join().expect("repl thread panicked");
 }
@@ -0,0 +1,44 @@
+use crate::object::Objects;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+// Modules evaluated so far, shared by every environment of one interpreter so a
+// file imported from several places is only evaluated once
+#[derive(Debug, Default)]
+pub struct Modules {
+    cache: RefCell<HashMap<PathBuf, Objects>>,
+    // Files whose evaluation has started but not finished, in import order
+    loading: RefCell<Vec<PathBuf>>,
+}
+
+impl Modules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, path: &Path) -> Option<Objects> {
+        self.cache.borrow().get(path).cloned()
+    }
+
+    pub fn insert(&self, path: PathBuf, module: Objects) {
+        self.cache.borrow_mut().insert(path, module);
+    }
+
+    // Marks the file as being evaluated. When it already is, returns the chain
+    // of imports from that file back to itself.
+    pub fn begin(&self, path: &Path) -> Result<(), Vec<PathBuf>> {
+        let mut loading = self.loading.borrow_mut();
+        if let Some(start) = loading.iter().position(|p| p == path) {
+            let mut cycle = loading[start..].to_vec();
+            cycle.push(path.to_path_buf());
+            return Err(cycle);
+        }
+        loading.push(path.to_path_buf());
+        Ok(())
+    }
+
+    pub fn finish(&self, path: &Path) {
+        self.loading.borrow_mut().retain(|p| p != path);
+    }
+}
@@ -0,0 +1,35 @@
+use std::fmt::Display;
+
+use super::{Object, ObjectTypes, Objects};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct Array {
+    elements: Vec<Objects>,
+}
+
+impl Array {
+    pub fn new(elements: Vec<Objects>) -> Self {
+        Self { elements }
+    }
+
+    pub fn elements(&self) -> &[Objects] {
+        &self.elements
+    }
+}
+
+impl Object for Array {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Array
+    }
+
+    fn inspect(&self) -> String {
+        let elements: Vec<String> = self.elements.iter().map(|e| e.inspect()).collect();
+        format!("[{}]", elements.join(", "))
+    }
+}
+
+impl Display for Array {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use super::{Integer, Object, ObjectTypes, Objects};
+use crate::bigint::BigInt;
+
+// Integer that no longer fits an isize, scripts only ever see an INTEGER
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct BigInteger {
+    value: BigInt,
+}
+
+impl BigInteger {
+    pub fn new(value: BigInt) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    // Demotes back to a plain Integer whenever the value fits
+    pub fn normalize(value: BigInt) -> Objects {
+        match value.to_isize() {
+            Some(small) => Objects::Integer(Integer::new(small)),
+            None => Objects::BigInteger(BigInteger::new(value)),
+        }
+    }
+}
+
+impl Object for BigInteger {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Integer
+    }
+
+    fn inspect(&self) -> String {
+        format!("{}", self.value)
+    }
+}
+
+impl Display for BigInteger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
@@ -1,10 +1,15 @@
 use super::{Object, ObjectTypes, Objects};
+use crate::sandbox::Sandbox;
 
 // type BuiltinFunction = fn(args: &[Objects]) -> Objects;
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum BuiltinFunction {
     Default(fn(args: &[Objects]) -> Objects),
+    // Runs long enough that it has to honour the timeout and cancellation
+    Sandboxed(fn(args: &[Objects], sandbox: &Sandbox) -> Objects),
     Drop,
+    Gc,
+    GcStats,
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
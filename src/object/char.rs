@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use super::{Object, ObjectTypes};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct Char {
+    value: char,
+}
+
+impl Char {
+    pub fn new(value: char) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> char {
+        self.value
+    }
+}
+
+impl Object for Char {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Char
+    }
+
+    fn inspect(&self) -> String {
+        self.value.to_string()
+    }
+}
+
+impl Display for Char {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
@@ -0,0 +1,102 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use super::{EnumValue, Object, ObjectTypes, Objects, VariantConstructor};
+
+// The value an `enum` declaration binds its name to
+#[derive(Debug, Clone)]
+pub struct EnumType {
+    name: Rc<str>,
+    // Variant names with the names of their payload fields
+    variants: Rc<[(String, Vec<String>)]>,
+    methods: Rc<RefCell<HashMap<String, Objects>>>,
+}
+
+impl EnumType {
+    pub fn new(name: &str, variants: Vec<(String, Vec<String>)>) -> Self {
+        Self {
+            name: Rc::from(name),
+            variants: Rc::from(variants),
+            methods: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn variants(&self) -> &[(String, Vec<String>)] {
+        &self.variants
+    }
+
+    // A constructor for variants with a payload, the value itself otherwise
+    pub fn variant(&self, name: &str) -> Option<Objects> {
+        let index = self.variants.iter().position(|(n, _)| n == name)?;
+        if self.variants[index].1.is_empty() {
+            Some(Objects::Enum(EnumValue::new(
+                self.clone(),
+                index,
+                Vec::new(),
+            )))
+        } else {
+            Some(Objects::Constructor(VariantConstructor::new(
+                self.clone(),
+                index,
+            )))
+        }
+    }
+
+    pub fn method(&self, name: &str) -> Option<Objects> {
+        self.methods.borrow().get(name).cloned()
+    }
+
+    pub fn add_method(&self, name: String, method: Objects) {
+        self.methods.borrow_mut().insert(name, method);
+    }
+
+    pub fn methods(&self) -> Vec<Objects> {
+        self.methods.borrow().values().cloned().collect()
+    }
+
+    pub fn same_type(&self, other: &EnumType) -> bool {
+        Rc::ptr_eq(&self.variants, &other.variants)
+    }
+}
+
+impl PartialEq for EnumType {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_type(other)
+    }
+}
+
+impl PartialOrd for EnumType {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        None
+    }
+}
+
+impl Object for EnumType {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::EnumType
+    }
+
+    fn inspect(&self) -> String {
+        let variants: Vec<String> = self
+            .variants
+            .iter()
+            .map(|(name, fields)| match fields.is_empty() {
+                true => name.clone(),
+                false => format!("{}({})", name, fields.join(", ")),
+            })
+            .collect();
+        format!("enum {} {{ {} }}", self.name, variants.join(", "))
+    }
+}
+
+impl Display for EnumType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+use super::{EnumType, Object, ObjectTypes, Objects};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct EnumValue {
+    definition: EnumType,
+    variant: usize,
+    values: Vec<Objects>,
+}
+
+impl EnumValue {
+    pub fn new(definition: EnumType, variant: usize, values: Vec<Objects>) -> Self {
+        Self {
+            definition,
+            variant,
+            values,
+        }
+    }
+
+    pub fn definition(&self) -> &EnumType {
+        &self.definition
+    }
+
+    pub fn variant(&self) -> &str {
+        &self.definition.variants()[self.variant].0
+    }
+
+    pub fn values(&self) -> &[Objects] {
+        &self.values
+    }
+}
+
+impl Object for EnumValue {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Enum(self.definition.name().into())
+    }
+
+    fn inspect(&self) -> String {
+        if self.values.is_empty() {
+            return self.variant().to_string();
+        }
+        let values: Vec<String> = self.values.iter().map(|v| v.inspect()).collect();
+        format!("{}({})", self.variant(), values.join(", "))
+    }
+}
+
+impl Display for EnumValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
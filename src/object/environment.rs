@@ -1,74 +1,262 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use super::{builtin_function::BuiltinFunction, BuiltinWrapper, Objects};
+use super::{builtin_function::BuiltinFunction, BuiltinWrapper, EnumType, Heap, Objects};
 use crate::builtins::BuiltinFunctions;
+use crate::module::Modules;
+use crate::prelude;
+use crate::sandbox::{Limits, Sandbox};
+use crate::stdlib;
 
 #[derive(Debug, Clone)]
 pub struct Environment {
     store: HashMap<String, Objects>,
     outer: Option<Rc<RefCell<Environment>>>,
+    sandbox: Rc<Sandbox>,
+    heap: Rc<Heap>,
+    modules: Rc<Modules>,
+    // Directory that relative imports are resolved against
+    base_dir: Rc<Path>,
+    exports: Vec<String>,
 }
 
 impl Environment {
+    #[cfg(test)]
     pub fn new() -> Self {
-        Self {
+        Environment::with_limits(Limits::default())
+    }
+
+    pub fn with_limits(limits: Limits) -> Self {
+        let mut env = Self {
             store: Environment::create_map_with_builtins(),
             outer: None,
-        }
+            sandbox: Rc::new(Sandbox::new(limits)),
+            heap: Rc::new(Heap::new()),
+            modules: Rc::new(Modules::new()),
+            base_dir: Rc::from(Path::new(".")),
+            exports: Vec::new(),
+        };
+        prelude::load(&mut env);
+        env
     }
 
     pub fn default() -> Self {
         Self {
             store: HashMap::new(),
             outer: None,
+            sandbox: Rc::new(Sandbox::default()),
+            heap: Rc::new(Heap::new()),
+            modules: Rc::new(Modules::new()),
+            base_dir: Rc::from(Path::new(".")),
+            exports: Vec::new(),
         }
     }
 
+    pub fn sandbox(&self) -> &Rc<Sandbox> {
+        &self.sandbox
+    }
+
+    pub fn heap(&self) -> &Rc<Heap> {
+        &self.heap
+    }
+
+    pub fn modules(&self) -> &Rc<Modules> {
+        &self.modules
+    }
+
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
+    pub fn set_base_dir(&mut self, dir: PathBuf) {
+        self.base_dir = Rc::from(dir);
+    }
+
+    // For hosts that stop a running program from another thread
+    #[allow(dead_code)]
+    pub fn cancel_handle(&self) -> crate::sandbox::CancelHandle {
+        self.sandbox.cancel_handle()
+    }
+
     fn create_map_with_builtins() -> HashMap<String, Objects> {
         let mut map: HashMap<String, Objects> = HashMap::new();
-        let map = HashMap::from_iter([(
-            String::from("len"),
-            Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
-                BuiltinFunctions::len,
-            )))),
-        )]);
+        let mut map = HashMap::from_iter([
+            (
+                String::from("len"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    BuiltinFunctions::len,
+                )))),
+            ),
+            (
+                String::from("int"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    BuiltinFunctions::int,
+                )))),
+            ),
+            (
+                String::from("float"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    BuiltinFunctions::float,
+                )))),
+            ),
+            (
+                String::from("ord"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    BuiltinFunctions::ord,
+                )))),
+            ),
+            (
+                String::from("chr"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    BuiltinFunctions::chr,
+                )))),
+            ),
+            (
+                String::from("chars"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    BuiltinFunctions::chars,
+                )))),
+            ),
+            (
+                String::from("print"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    stdlib::print,
+                )))),
+            ),
+            (
+                String::from("gc"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Gc))),
+            ),
+            (
+                String::from("gc_stats"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::GcStats))),
+            ),
+            (
+                String::from("invoke"),
+                Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(
+                    BuiltinFunctions::invoke,
+                )))),
+            ),
+        ]);
+        // Option and Result are ordinary enums that are always in scope
+        let option = EnumType::new(
+            "Option",
+            vec![
+                (String::from("Some"), vec![String::from("value")]),
+                (String::from("None"), Vec::new()),
+            ],
+        );
+        let result = EnumType::new(
+            "Result",
+            vec![
+                (String::from("Ok"), vec![String::from("value")]),
+                (String::from("Err"), vec![String::from("error")]),
+            ],
+        );
+        bind_enum(&mut map, option);
+        bind_enum(&mut map, result);
+        // The rest of the standard library is reached through its modules, `math.sqrt(2)`
+        for name in stdlib::MODULES {
+            if let Some(module) = stdlib::module(name) {
+                map.insert(name.to_string(), Objects::Module(module));
+            }
+        }
         map
     }
 
+    // Binds the enum's name and each of its variants
+    pub fn declare_enum(&mut self, definition: EnumType) {
+        bind_enum(&mut self.store, definition);
+    }
+
     pub fn get(&self, key: String) -> Option<Objects> {
-        let mut result = self.store.get(&key).cloned();
-        if result.is_none() && self.outer.is_some() {
-            result = self
-                .outer
-                .as_ref()
-                .unwrap()
-                .borrow()
-                .store
-                .get(&key)
-                .cloned();
+        match self.store.get(&key) {
+            Some(value) => Some(value.clone()),
+            None => self.outer.as_ref()?.borrow().get(key),
         }
-        result
+    }
+
+    pub fn bindings(&self) -> impl Iterator<Item = (&String, &Objects)> {
+        self.store.iter()
     }
 
     pub fn set(&mut self, key: String, value: Objects) {
         self.store.insert(key, value);
     }
 
+    // Makes the binding visible to modules that import this environment
+    pub fn export(&mut self, key: String) {
+        if !self.exports.contains(&key) {
+            self.exports.push(key);
+        }
+    }
+
+    pub fn exported(&self) -> HashMap<String, Objects> {
+        self.exports
+            .iter()
+            .filter_map(|key| Some((key.clone(), self.store.get(key)?.clone())))
+            .collect()
+    }
+
     pub fn delete(&mut self, key: &str) {
         self.store.remove(key);
     }
 
+    // Visits every environment this one keeps alive
+    pub fn trace(&self, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>, bool)) {
+        if let Some(outer) = &self.outer {
+            visit(outer, false);
+        }
+        for value in self.store.values() {
+            value.trace(visit);
+        }
+    }
+
+    pub fn clear(&mut self) -> HashMap<String, Objects> {
+        self.outer = None;
+        std::mem::take(&mut self.store)
+    }
+
     pub fn set_outer_env(&mut self, outer: Rc<RefCell<Environment>>) {
         self.outer = Some(outer);
     }
 
     pub fn new_enclosed_environment(outer: Rc<RefCell<Environment>>) -> Environment {
         let mut new_env = Environment::default();
+        new_env.sandbox = Rc::clone(outer.borrow().sandbox());
+        new_env.heap = Rc::clone(outer.borrow().heap());
+        new_env.modules = Rc::clone(outer.borrow().modules());
+        new_env.base_dir = Rc::clone(&outer.borrow().base_dir);
         new_env.set_outer_env(outer);
         new_env
     }
+
+    // A fresh root environment for a module, it sees the builtins and the prelude but nothing
+    // of the importer apart from the limits and the module cache
+    pub fn new_module_environment(importer: &Environment, dir: PathBuf) -> Environment {
+        let mut env = Self {
+            store: Environment::create_map_with_builtins(),
+            outer: None,
+            sandbox: Rc::clone(&importer.sandbox),
+            heap: Rc::clone(&importer.heap),
+            modules: Rc::clone(&importer.modules),
+            base_dir: Rc::from(dir),
+            exports: Vec::new(),
+        };
+        prelude::load(&mut env);
+        env
+    }
+}
+
+fn bind_enum(store: &mut HashMap<String, Objects>, definition: EnumType) {
+    for (variant, _) in definition.variants() {
+        if let Some(value) = definition.variant(variant) {
+            store.insert(variant.clone(), value);
+        }
+    }
+    store.insert(definition.name().into(), Objects::EnumType(definition));
 }
 
 impl PartialEq for Environment {
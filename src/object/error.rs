@@ -2,19 +2,51 @@ use std::fmt::Display;
 
 use super::{Object, ObjectTypes};
 
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum Limit {
+    Steps,
+    CallDepth,
+    Stack,
+    Timeout,
+    Allocations,
+    Cancelled,
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum ErrorKind {
+    Runtime,
+    // Raised by the sandbox, the evaluation was aborted and not the script at fault
+    LimitExceeded(Limit),
+}
+
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub struct Error {
     message: String,
+    kind: ErrorKind,
 }
 
 impl Error {
     pub fn new(message: String) -> Self {
-        Self { message }
+        Self {
+            message,
+            kind: ErrorKind::Runtime,
+        }
+    }
+
+    pub fn limit_exceeded(limit: Limit, message: String) -> Self {
+        Self {
+            message,
+            kind: ErrorKind::LimitExceeded(limit),
+        }
     }
 
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
 }
 
 impl Object for Error {
@@ -26,7 +58,7 @@ impl Object for Error {
         format!("{}", self.message)
     }
 }
-impl Display for Error{
+impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.obj_type())
     }
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use super::{Object, ObjectTypes};
+
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct Float {
+    value: f64,
+}
+
+impl Float {
+    pub fn new(value: f64) -> Self {
+        Self { value }
+    }
+
+    pub fn value(&self) -> &f64 {
+        &self.value
+    }
+}
+
+impl Object for Float {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Float
+    }
+
+    fn inspect(&self) -> String {
+        format!("{:?}", self.value)
+    }
+}
+
+impl Display for Float {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
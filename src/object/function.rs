@@ -1,4 +1,4 @@
-use crate::ast::{BlockStatement, Identifier};
+use crate::ast::{BlockStatement, Parameter};
 use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
@@ -7,14 +7,14 @@ use super::{Environment, Object, ObjectTypes};
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub struct Function {
-    parameters: Vec<Identifier>,
+    parameters: Vec<Parameter>,
     body: BlockStatement,
     env: Rc<RefCell<Environment>>,
 }
 
 impl Function {
     pub fn new(
-        parameters: Vec<Identifier>,
+        parameters: Vec<Parameter>,
         body: BlockStatement,
         env: Rc<RefCell<Environment>>,
     ) -> Self {
@@ -25,10 +25,14 @@ impl Function {
         }
     }
 
-    pub fn parameters(&self) -> &Vec<Identifier> {
+    pub fn parameters(&self) -> &Vec<Parameter> {
         &self.parameters
     }
 
+    pub fn parameters_mut(&mut self) -> &mut Vec<Parameter> {
+        &mut self.parameters
+    }
+
     pub fn body(&self) -> &BlockStatement {
         &self.body
     }
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::bigint::BigInt;
+
+use super::{Object, ObjectTypes, Objects};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+    Integer(isize),
+    BigInteger(BigInt),
+    Boolean(bool),
+    String(String),
+    Char(char),
+}
+
+impl HashKey {
+    pub fn from_object(obj: &Objects) -> Option<HashKey> {
+        match obj {
+            Objects::Integer(x) => Some(HashKey::Integer(*x.value())),
+            Objects::BigInteger(x) => Some(HashKey::BigInteger(x.value().clone())),
+            Objects::Boolean(x) => Some(HashKey::Boolean(*x.value())),
+            Objects::String(x) => Some(HashKey::String(x.value().to_string())),
+            Objects::Char(x) => Some(HashKey::Char(x.value())),
+            _ => None,
+        }
+    }
+}
+
+// Pairs are kept in insertion order so printing a hash is deterministic
+#[derive(Debug, Clone, Default)]
+pub struct Hash {
+    pairs: Vec<(Objects, Objects)>,
+    index: HashMap<HashKey, usize>,
+}
+
+impl Hash {
+    pub fn new() -> Self {
+        Hash::default()
+    }
+
+    // Returns the key back when it cannot be hashed
+    pub fn insert(&mut self, key: Objects, value: Objects) -> Result<(), Objects> {
+        let hash_key = match HashKey::from_object(&key) {
+            Some(hash_key) => hash_key,
+            None => return Err(key),
+        };
+        match self.index.get(&hash_key) {
+            Some(&i) => self.pairs[i].1 = value,
+            None => {
+                self.index.insert(hash_key, self.pairs.len());
+                self.pairs.push((key, value));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &Objects) -> Option<&Objects> {
+        let i = self.index.get(&HashKey::from_object(key)?)?;
+        Some(&self.pairs[*i].1)
+    }
+
+    pub fn pairs(&self) -> &[(Objects, Objects)] {
+        &self.pairs
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+}
+
+impl PartialEq for Hash {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .pairs
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl PartialOrd for Hash {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        None
+    }
+}
+
+impl Object for Hash {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Hash
+    }
+
+    fn inspect(&self) -> String {
+        let pairs: Vec<String> = self
+            .pairs
+            .iter()
+            .map(|(key, value)| format!("{}: {}", key.inspect(), value.inspect()))
+            .collect();
+        format!("{{{}}}", pairs.join(", "))
+    }
+}
+
+impl Display for Hash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
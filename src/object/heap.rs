@@ -0,0 +1,119 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use super::{Environment, Objects};
+
+// Number of environments tracked between two automatic collections
+pub const COLLECTION_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GcStats {
+    pub collections: usize,
+    pub tracked: usize,
+    pub freed: usize,
+}
+
+// Cycle collector for environments. A closure stored in the environment it
+// captured keeps that environment alive forever through its Rc, so the heap
+// keeps weak handles to every call environment and periodically looks for
+// groups of them that are only referenced by each other.
+#[derive(Debug, Default)]
+pub struct Heap {
+    environments: RefCell<Vec<Weak<RefCell<Environment>>>>,
+    since_collection: Cell<usize>,
+    stats: Cell<GcStats>,
+}
+
+impl Heap {
+    pub fn new() -> Self {
+        Heap::default()
+    }
+
+    pub fn track(&self, env: &Rc<RefCell<Environment>>) {
+        self.environments.borrow_mut().push(Rc::downgrade(env));
+        self.since_collection.set(self.since_collection.get() + 1);
+    }
+
+    pub fn should_collect(&self) -> bool {
+        self.since_collection.get() >= COLLECTION_THRESHOLD
+    }
+
+    pub fn stats(&self) -> GcStats {
+        self.stats.get()
+    }
+
+    // Returns the number of environments that were freed
+    pub fn collect(&self) -> usize {
+        let live: Vec<Rc<RefCell<Environment>>> = {
+            let mut environments = self.environments.borrow_mut();
+            environments.retain(|env| env.strong_count() > 0);
+            environments
+                .iter()
+                .filter_map(|env| env.upgrade())
+                .collect()
+        };
+        let index: HashMap<*const RefCell<Environment>, usize> = live
+            .iter()
+            .enumerate()
+            .map(|(i, env)| (Rc::as_ptr(env), i))
+            .collect();
+
+        // References held from outside the tracked environments make a root.
+        // One strong count belongs to `live` itself.
+        let mut external: Vec<isize> = live
+            .iter()
+            .map(|env| Rc::strong_count(env) as isize - 1)
+            .collect();
+        let mut edges: Vec<Vec<usize>> = vec![Vec::new(); live.len()];
+        for (i, env) in live.iter().enumerate() {
+            match env.try_borrow() {
+                Ok(env) => env.trace(&mut |target, shared| {
+                    if let Some(&j) = index.get(&Rc::as_ptr(target)) {
+                        // A shared container holds one reference however many
+                        // values point at it, so it is left to count as a root
+                        if !shared {
+                            external[j] -= 1;
+                        }
+                        edges[i].push(j);
+                    }
+                }),
+                // Currently being mutated, so it is in use
+                Err(_) => external[i] += 1,
+            }
+        }
+
+        let mut reachable = vec![false; live.len()];
+        let mut stack: Vec<usize> = (0..live.len()).filter(|&i| external[i] > 0).collect();
+        while let Some(i) = stack.pop() {
+            if reachable[i] {
+                continue;
+            }
+            reachable[i] = true;
+            stack.extend(edges[i].iter().copied().filter(|&j| !reachable[j]));
+        }
+
+        // Emptying the unreachable environments breaks their cycles, the
+        // values are dropped once no borrow is held anymore
+        let mut garbage: Vec<HashMap<String, Objects>> = Vec::new();
+        for (i, env) in live.iter().enumerate() {
+            if !reachable[i] {
+                garbage.push(env.borrow_mut().clear());
+            }
+        }
+        let freed = garbage.len();
+        drop(garbage);
+
+        let mut stats = self.stats.get();
+        stats.collections += 1;
+        stats.tracked = live.len() - freed;
+        stats.freed += freed;
+        self.stats.set(stats);
+        self.since_collection.set(0);
+        drop(live);
+        self.environments
+            .borrow_mut()
+            .retain(|env| env.strong_count() > 0);
+        freed
+    }
+}
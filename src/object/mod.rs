@@ -1,64 +1,131 @@
 use std::{
+    cell::RefCell,
     error::Error,
     fmt::{Debug, Display},
     rc::Rc,
 };
 
+mod array;
+mod big_integer;
 mod boolean;
 mod builtin_function;
+mod char;
+mod enum_type;
+mod enum_value;
 mod environment;
 mod error;
+mod float;
 mod function;
+mod hash;
+mod heap;
 mod integer;
+mod module;
+mod native;
 mod null;
 mod return_object;
 mod string_object;
+mod struct_object;
+mod struct_type;
+mod variant_constructor;
+pub use array::Array;
+pub use big_integer::BigInteger;
 pub use boolean::Boolean;
-pub use builtin_function::BuiltinWrapper;
 pub use builtin_function::BuiltinFunction;
+pub use builtin_function::BuiltinWrapper;
+pub use char::Char;
+pub use enum_type::EnumType;
+pub use enum_value::EnumValue;
 pub use environment::Environment;
 pub use error::Error as ErrorObject;
+pub use error::ErrorKind;
+pub use error::Limit;
+pub use float::Float;
 pub use function::Function;
+pub use hash::Hash;
+pub use hash::HashKey;
+pub use heap::GcStats;
+pub use heap::Heap;
 pub use integer::Integer;
+pub use module::Module;
+pub use native::Native;
 pub use null::Null;
 pub use return_object::Return;
 pub use string_object::StringObject;
+pub use struct_object::Struct;
+pub use struct_type::StructType;
+pub use variant_constructor::VariantConstructor;
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub enum Objects {
     Integer(Integer),
+    BigInteger(BigInteger),
+    Float(Float),
     Boolean(Boolean),
     Null(Null),
     Return(Return),
     Error(ErrorObject),
     Function(Function),
     String(StringObject),
+    Char(Char),
+    Array(Array),
+    Hash(Hash),
+    Struct(Struct),
+    StructType(StructType),
+    Enum(EnumValue),
+    EnumType(EnumType),
+    Constructor(VariantConstructor),
+    Module(Module),
     Builtin(Rc<BuiltinWrapper>),
+    // Only a host embedding the interpreter creates natives
+    #[allow(dead_code)]
+    Native(Native),
 }
 
 #[derive(Debug, PartialEq, PartialOrd)]
 pub enum ObjectTypes {
     Integer,
+    Float,
     Boolean,
     Null,
     Return,
     Error,
     Function,
     String,
+    Char,
+    Array,
+    Hash,
+    Struct(String),
+    StructType,
+    Enum(String),
+    EnumType,
+    Constructor,
+    Module,
     Builtin,
+    Native(String),
 }
 
 impl Display for ObjectTypes {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ObjectTypes::Integer => write!(f, "INTEGER"),
+            ObjectTypes::Float => write!(f, "FLOAT"),
             ObjectTypes::Boolean => write!(f, "BOOLEAN"),
             ObjectTypes::Null => write!(f, "NULL"),
             ObjectTypes::Return => write!(f, "RETURN"),
             ObjectTypes::Error => write!(f, "ERROR"),
             ObjectTypes::Function => write!(f, "FUNCTION"),
             ObjectTypes::String => write!(f, "STRING"),
+            ObjectTypes::Char => write!(f, "CHAR"),
+            ObjectTypes::Array => write!(f, "ARRAY"),
+            ObjectTypes::Hash => write!(f, "HASH"),
+            ObjectTypes::Struct(name) => write!(f, "{}", name),
+            ObjectTypes::StructType => write!(f, "STRUCT"),
+            ObjectTypes::Enum(name) => write!(f, "{}", name),
+            ObjectTypes::EnumType => write!(f, "ENUM"),
+            ObjectTypes::Constructor => write!(f, "CONSTRUCTOR"),
+            ObjectTypes::Module => write!(f, "MODULE"),
             ObjectTypes::Builtin => write!(f, "BUILTIN"),
+            ObjectTypes::Native(name) => write!(f, "{}", name),
         }
     }
 }
@@ -67,13 +134,25 @@ impl Display for Objects {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Objects::Integer(x) => write!(f, "{}", x.obj_type()),
+            Objects::BigInteger(x) => write!(f, "{}", x.obj_type()),
+            Objects::Float(x) => write!(f, "{}", x.obj_type()),
             Objects::Boolean(x) => write!(f, "{}", x.obj_type()),
             Objects::Null(x) => write!(f, "{}", x.obj_type()),
             Objects::Return(x) => write!(f, "{}", x.obj_type()),
             Objects::Error(x) => write!(f, "{}", x.obj_type()),
             Objects::Function(x) => write!(f, "{}", x.obj_type()),
             Objects::String(x) => write!(f, "{}", x.obj_type()),
+            Objects::Char(x) => write!(f, "{}", x.obj_type()),
+            Objects::Array(x) => write!(f, "{}", x.obj_type()),
+            Objects::Hash(x) => write!(f, "{}", x.obj_type()),
+            Objects::Struct(x) => write!(f, "{}", x.obj_type()),
+            Objects::StructType(x) => write!(f, "{}", x.obj_type()),
+            Objects::Enum(x) => write!(f, "{}", x.obj_type()),
+            Objects::EnumType(x) => write!(f, "{}", x.obj_type()),
+            Objects::Constructor(x) => write!(f, "{}", x.obj_type()),
+            Objects::Module(x) => write!(f, "{}", x.obj_type()),
             Objects::Builtin(x) => write!(f, "{}", x.obj_type()),
+            Objects::Native(x) => write!(f, "{}", x.obj_type()),
         }
     }
 }
@@ -85,6 +164,12 @@ impl Objects {
             _ => None,
         }
     }
+    pub fn as_float(self) -> Option<Float> {
+        match self {
+            Objects::Float(x) => Some(x),
+            _ => None,
+        }
+    }
     pub fn as_boolean(self) -> Option<Boolean> {
         match self {
             Objects::Boolean(x) => Some(x),
@@ -121,12 +206,100 @@ impl Objects {
             _ => None,
         }
     }
+    pub fn as_char(self) -> Option<Char> {
+        match self {
+            Objects::Char(x) => Some(x),
+            _ => None,
+        }
+    }
+    pub fn as_array(self) -> Option<Array> {
+        match self {
+            Objects::Array(x) => Some(x),
+            _ => None,
+        }
+    }
+    pub fn as_hash(self) -> Option<Hash> {
+        match self {
+            Objects::Hash(x) => Some(x),
+            _ => None,
+        }
+    }
+    pub fn as_struct(self) -> Option<Struct> {
+        match self {
+            Objects::Struct(x) => Some(x),
+            _ => None,
+        }
+    }
+    pub fn as_enum(self) -> Option<EnumValue> {
+        match self {
+            Objects::Enum(x) => Some(x),
+            _ => None,
+        }
+    }
+    pub fn as_module(self) -> Option<Module> {
+        match self {
+            Objects::Module(x) => Some(x),
+            _ => None,
+        }
+    }
     pub fn as_builtin(self) -> Option<Rc<BuiltinWrapper>> {
         match self {
             Objects::Builtin(x) => Some(x),
             _ => None,
         }
     }
+    pub fn as_native(self) -> Option<Native> {
+        match self {
+            Objects::Native(x) => Some(x),
+            _ => None,
+        }
+    }
+    // Visits every environment the value keeps alive. The flag is set for those
+    // reached through a method table or module exports: such containers are
+    // shared by every value that points at them, so one reference cannot be
+    // told apart from the others.
+    pub fn trace(&self, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>, bool)) {
+        self.trace_from(false, visit)
+    }
+
+    fn trace_from(&self, shared: bool, visit: &mut dyn FnMut(&Rc<RefCell<Environment>>, bool)) {
+        match self {
+            Objects::Function(x) => visit(x.environment(), shared),
+            Objects::Return(x) => x.value().trace_from(shared, visit),
+            Objects::Array(x) => x
+                .elements()
+                .iter()
+                .for_each(|e| e.trace_from(shared, visit)),
+            Objects::Hash(x) => x
+                .pairs()
+                .iter()
+                .for_each(|(_, value)| value.trace_from(shared, visit)),
+            Objects::Struct(x) => {
+                x.values().iter().for_each(|v| v.trace_from(shared, visit));
+                x.definition()
+                    .methods()
+                    .iter()
+                    .for_each(|m| m.trace_from(true, visit));
+            }
+            Objects::StructType(x) => x.methods().iter().for_each(|m| m.trace_from(true, visit)),
+            Objects::Enum(x) => {
+                x.values().iter().for_each(|v| v.trace_from(shared, visit));
+                x.definition()
+                    .methods()
+                    .iter()
+                    .for_each(|m| m.trace_from(true, visit));
+            }
+            Objects::EnumType(x) => x.methods().iter().for_each(|m| m.trace_from(true, visit)),
+            Objects::Constructor(x) => x
+                .definition()
+                .methods()
+                .iter()
+                .for_each(|m| m.trace_from(true, visit)),
+            Objects::Module(x) => x.exports().values().for_each(|v| v.trace_from(true, visit)),
+            _ => {}
+        }
+    }
+
     pub fn is_return(&self) -> bool {
         match self {
             Objects::Return(_) => true,
@@ -134,6 +307,11 @@ impl Objects {
         }
     }
 
+    // An early return unwinds through expressions the same way an error does
+    pub fn is_unwinding(&self) -> bool {
+        self.is_err() || self.is_return()
+    }
+
     pub fn is_null(&self) -> bool {
         match self {
             Objects::Null(_) => true,
@@ -146,26 +324,50 @@ impl Object for Objects {
     fn obj_type(&self) -> ObjectTypes {
         match self {
             Objects::Integer(x) => x.obj_type(),
+            Objects::BigInteger(x) => x.obj_type(),
+            Objects::Float(x) => x.obj_type(),
             Objects::Boolean(x) => x.obj_type(),
             Objects::Null(x) => x.obj_type(),
             Objects::Return(x) => x.obj_type(),
             Objects::Error(x) => x.obj_type(),
             Objects::Function(x) => x.obj_type(),
             Objects::String(x) => x.obj_type(),
+            Objects::Char(x) => x.obj_type(),
+            Objects::Array(x) => x.obj_type(),
+            Objects::Hash(x) => x.obj_type(),
+            Objects::Struct(x) => x.obj_type(),
+            Objects::StructType(x) => x.obj_type(),
+            Objects::Enum(x) => x.obj_type(),
+            Objects::EnumType(x) => x.obj_type(),
+            Objects::Constructor(x) => x.obj_type(),
+            Objects::Module(x) => x.obj_type(),
             Objects::Builtin(x) => x.obj_type(),
+            Objects::Native(x) => x.obj_type(),
         }
     }
 
     fn inspect(&self) -> String {
         match self {
             Objects::Integer(x) => x.inspect(),
+            Objects::BigInteger(x) => x.inspect(),
+            Objects::Float(x) => x.inspect(),
             Objects::Boolean(x) => x.inspect(),
             Objects::Null(x) => x.inspect(),
             Objects::Return(x) => x.inspect(),
             Objects::Error(x) => x.inspect(),
             Objects::Function(x) => x.inspect(),
             Objects::String(x) => x.inspect(),
+            Objects::Char(x) => x.inspect(),
+            Objects::Array(x) => x.inspect(),
+            Objects::Hash(x) => x.inspect(),
+            Objects::Struct(x) => x.inspect(),
+            Objects::StructType(x) => x.inspect(),
+            Objects::Enum(x) => x.inspect(),
+            Objects::EnumType(x) => x.inspect(),
+            Objects::Constructor(x) => x.inspect(),
+            Objects::Module(x) => x.inspect(),
             Objects::Builtin(x) => x.inspect(),
+            Objects::Native(x) => x.inspect(),
         }
     }
 
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use super::{Object, ObjectTypes, Objects};
+
+// The value an `import` binds its alias to. Every import of the same file
+// shares one evaluated module, so the exports are never copied.
+#[derive(Debug, Clone)]
+pub struct Module {
+    path: Rc<str>,
+    exports: Rc<HashMap<String, Objects>>,
+}
+
+impl Module {
+    pub fn new(path: &str, exports: HashMap<String, Objects>) -> Self {
+        Self {
+            path: Rc::from(path),
+            exports: Rc::new(exports),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn get(&self, name: &str) -> Option<Objects> {
+        self.exports.get(name).cloned()
+    }
+
+    pub fn exports(&self) -> &HashMap<String, Objects> {
+        &self.exports
+    }
+}
+
+impl PartialEq for Module {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.exports, &other.exports)
+    }
+}
+
+impl PartialOrd for Module {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        None
+    }
+}
+
+impl Object for Module {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Module
+    }
+
+    fn inspect(&self) -> String {
+        let mut names: Vec<&str> = self.exports.keys().map(|k| k.as_str()).collect();
+        names.sort();
+        format!("module {:?} {{ {} }}", self.path, names.join(", "))
+    }
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
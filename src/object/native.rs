@@ -0,0 +1,103 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::rc::Rc;
+
+use super::{Object, ObjectTypes, Objects};
+
+pub type NativeMethod = fn(this: &dyn Any, args: &[Objects]) -> Objects;
+pub type NativeInspect = fn(this: &dyn Any) -> String;
+
+// Host owned value handed to scripts. Scripts can only pass it around and call
+// the methods registered by the host, the value itself is never exposed.
+#[derive(Clone)]
+pub struct Native {
+    type_name: Rc<str>,
+    value: Rc<dyn Any>,
+    methods: Rc<HashMap<String, NativeMethod>>,
+    inspect: Option<NativeInspect>,
+}
+
+// Host API, the binary itself never builds a native so these stay unused outside the tests
+impl Native {
+    #[allow(dead_code)]
+    pub fn new<T: Any>(type_name: &str, value: T) -> Self {
+        Self {
+            type_name: Rc::from(type_name),
+            value: Rc::new(value),
+            methods: Rc::new(HashMap::new()),
+            inspect: None,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_method(mut self, name: &str, method: NativeMethod) -> Self {
+        Rc::make_mut(&mut self.methods).insert(name.into(), method);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_inspect(mut self, inspect: NativeInspect) -> Self {
+        self.inspect = Some(inspect);
+        self
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    #[allow(dead_code)]
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref::<T>()
+    }
+
+    pub fn method(&self, name: &str) -> Option<NativeMethod> {
+        self.methods.get(name).copied()
+    }
+
+    pub fn call_method(&self, name: &str, args: &[Objects]) -> Option<Objects> {
+        let method = self.method(name)?;
+        Some(method(self.value.as_ref(), args))
+    }
+}
+
+impl Object for Native {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Native(self.type_name.to_string())
+    }
+
+    fn inspect(&self) -> String {
+        match self.inspect {
+            Some(inspect) => inspect(self.value.as_ref()),
+            None => format!("<{}>", self.type_name),
+        }
+    }
+}
+
+impl Debug for Native {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Native({})", self.type_name)
+    }
+}
+
+impl Display for Native {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
+
+// Two natives are only equal when they share the same host value
+impl PartialEq for Native {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+impl PartialOrd for Native {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        if self == other {
+            return Some(std::cmp::Ordering::Equal);
+        }
+        None
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::Display;
+
+use super::{Object, ObjectTypes, Objects, StructType};
+
+// Fields are kept in declaration order
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct Struct {
+    definition: StructType,
+    values: Vec<Objects>,
+}
+
+impl Struct {
+    pub fn new(definition: StructType, values: Vec<Objects>) -> Self {
+        Self { definition, values }
+    }
+
+    pub fn definition(&self) -> &StructType {
+        &self.definition
+    }
+
+    pub fn name(&self) -> &str {
+        self.definition.name()
+    }
+
+    pub fn get(&self, field: &str) -> Option<&Objects> {
+        let index = self.definition.fields().iter().position(|f| f == field)?;
+        self.values.get(index)
+    }
+
+    pub fn values(&self) -> &[Objects] {
+        &self.values
+    }
+}
+
+impl Object for Struct {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Struct(self.name().into())
+    }
+
+    fn inspect(&self) -> String {
+        let fields: Vec<String> = self
+            .definition
+            .fields()
+            .iter()
+            .zip(&self.values)
+            .map(|(field, value)| format!("{}: {}", field, value.inspect()))
+            .collect();
+        format!("{} {{ {} }}", self.name(), fields.join(", "))
+    }
+}
+
+impl Display for Struct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
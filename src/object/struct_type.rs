@@ -0,0 +1,73 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use super::{Object, ObjectTypes, Objects};
+
+// The value a `struct` declaration binds its name to. Methods added by `impl`
+// blocks are shared with every instance, including ones built earlier.
+#[derive(Debug, Clone)]
+pub struct StructType {
+    name: Rc<str>,
+    fields: Rc<[String]>,
+    methods: Rc<RefCell<HashMap<String, Objects>>>,
+}
+
+impl StructType {
+    pub fn new(name: &str, fields: Vec<String>) -> Self {
+        Self {
+            name: Rc::from(name),
+            fields: Rc::from(fields),
+            methods: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    pub fn method(&self, name: &str) -> Option<Objects> {
+        self.methods.borrow().get(name).cloned()
+    }
+
+    pub fn add_method(&self, name: String, method: Objects) {
+        self.methods.borrow_mut().insert(name, method);
+    }
+
+    pub fn methods(&self) -> Vec<Objects> {
+        self.methods.borrow().values().cloned().collect()
+    }
+}
+
+impl PartialEq for StructType {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.methods, &other.methods)
+    }
+}
+
+impl PartialOrd for StructType {
+    fn partial_cmp(&self, _other: &Self) -> Option<std::cmp::Ordering> {
+        None
+    }
+}
+
+impl Object for StructType {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::StructType
+    }
+
+    fn inspect(&self) -> String {
+        format!("struct {} {{ {} }}", self.name, self.fields.join(", "))
+    }
+}
+
+impl Display for StructType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use super::{EnumType, EnumValue, ErrorObject, Object, ObjectTypes, Objects};
+
+// Calling `Circle(1)` builds the variant from its payload
+#[derive(Debug, PartialEq, PartialOrd, Clone)]
+pub struct VariantConstructor {
+    definition: EnumType,
+    variant: usize,
+}
+
+impl VariantConstructor {
+    pub fn new(definition: EnumType, variant: usize) -> Self {
+        Self {
+            definition,
+            variant,
+        }
+    }
+
+    pub fn definition(&self) -> &EnumType {
+        &self.definition
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition.variants()[self.variant].0
+    }
+
+    pub fn construct(&self, args: &[Objects]) -> Objects {
+        let arity = self.definition.variants()[self.variant].1.len();
+        if args.len() != arity {
+            return Objects::Error(ErrorObject::new(format!(
+                "{} expects {} argument(s) but received {}",
+                self.name(),
+                arity,
+                args.len()
+            )));
+        }
+        Objects::Enum(EnumValue::new(
+            self.definition.clone(),
+            self.variant,
+            args.to_vec(),
+        ))
+    }
+}
+
+impl Object for VariantConstructor {
+    fn obj_type(&self) -> ObjectTypes {
+        ObjectTypes::Constructor
+    }
+
+    fn inspect(&self) -> String {
+        format!("{}::{}", self.definition.name(), self.name())
+    }
+}
+
+impl Display for VariantConstructor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.obj_type())
+    }
+}
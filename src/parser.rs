@@ -1,43 +1,82 @@
+use crate::ast::ArrayLiteral;
 use crate::ast::BlockStatement;
 use crate::ast::BooleanLiteral;
 use crate::ast::CallExpression;
+use crate::ast::CharLiteral;
+use crate::ast::EnumStatement;
 use crate::ast::ExpressionStatement;
+use crate::ast::FieldAccess;
+use crate::ast::FloatLiteral;
 use crate::ast::FunctionLiteral;
+use crate::ast::HashLiteral;
 use crate::ast::IfExpression;
+use crate::ast::ImplStatement;
+use crate::ast::ImportStatement;
+use crate::ast::IndexExpression;
 use crate::ast::InfixExpression;
 use crate::ast::IntegerLiteral;
+use crate::ast::InterpolatedString;
+use crate::ast::InterpolationPart;
 use crate::ast::LetStatement;
+use crate::ast::MatchArm;
+use crate::ast::MatchExpression;
 use crate::ast::OptionalBlockStatement;
+use crate::ast::Parameter;
+use crate::ast::Pattern;
 use crate::ast::PrefixExpression;
 use crate::ast::ReturnStatement;
+use crate::ast::SpreadExpression;
 use crate::ast::StringLiteral;
+use crate::ast::StructLiteral;
+use crate::ast::StructStatement;
+use crate::ast::TryExpression;
+use crate::ast::TypeAnnotation;
 use crate::{
     ast::{Expressions, Identifier, Program, Statements},
-    lexer::{Lexer, Token},
+    lexer::{Lexer, Position, StringPart, Token},
 };
 use std::collections::HashMap;
 use std::error::Error;
 use std::mem::discriminant;
 
+// The checkers and the evaluator walk the tree recursively, deeper expressions
+// would overflow their stack
+const MAX_NESTING: usize = 256;
+
 #[derive(Debug, PartialOrd, PartialEq, Copy, Clone)]
 enum Priority {
     Lowest,
     Equals,
     LessGreater,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
     Sum,
     Product,
+    Exponent,
     Prefix,
     Call,
+    Index,
 }
 
 type PrefixParseFn = fn(p: &mut Parser) -> Option<Expressions>;
 type InfixParseFn = fn(p: &mut Parser, expresion: Expressions) -> Option<Expressions>;
+// Positional arguments followed by the `name: value` ones
+type CallArguments = (Vec<Expressions>, Vec<(Identifier, Expressions)>);
 
 pub struct Parser {
     lexer: Lexer,
     curr_token: Option<Token>,
     peek_token: Option<Token>,
+    // Doc comments that preceded the current and the peek token
+    curr_docs: Vec<String>,
+    peek_docs: Vec<String>,
+    curr_position: Position,
+    peek_position: Position,
     errors: Vec<String>,
+    // How deep the expression being parsed is nested so far
+    nesting: usize,
     prefix_parse_fns: HashMap<String, PrefixParseFn>,
     infix_parse_fns: HashMap<String, InfixParseFn>,
     precedences: HashMap<String, Priority>,
@@ -50,17 +89,34 @@ impl Parser {
             (Token::NotEqual.token_type(), Priority::Equals),
             (Token::Lt.token_type(), Priority::LessGreater),
             (Token::Gt.token_type(), Priority::LessGreater),
+            (Token::LtEq.token_type(), Priority::LessGreater),
+            (Token::GtEq.token_type(), Priority::LessGreater),
             (Token::Plus.token_type(), Priority::Sum),
             (Token::Minus.token_type(), Priority::Sum),
             (Token::Divide.token_type(), Priority::Product),
             (Token::Multiply.token_type(), Priority::Product),
+            (Token::Modulo.token_type(), Priority::Product),
+            (Token::Power.token_type(), Priority::Exponent),
+            (Token::Or.token_type(), Priority::BitOr),
+            (Token::Caret.token_type(), Priority::BitXor),
+            (Token::And.token_type(), Priority::BitAnd),
+            (Token::ShiftLeft.token_type(), Priority::Shift),
+            (Token::ShiftRight.token_type(), Priority::Shift),
             (Token::Lparen.token_type(), Priority::Call),
+            (Token::Lsquare.token_type(), Priority::Index),
+            (Token::Dot.token_type(), Priority::Call),
+            (Token::Question.token_type(), Priority::Call),
         ]);
         let mut p = Parser {
             lexer,
             curr_token: None,
             peek_token: None,
+            curr_docs: Vec::new(),
+            peek_docs: Vec::new(),
+            curr_position: Position::default(),
+            peek_position: Position::default(),
             errors: Vec::new(),
+            nesting: 0,
             prefix_parse_fns: HashMap::new(),
             infix_parse_fns: HashMap::new(),
             precedences,
@@ -74,7 +130,12 @@ impl Parser {
 
     pub fn next_token(&mut self) {
         self.curr_token = self.peek_token.take();
+        self.curr_docs = std::mem::take(&mut self.peek_docs);
+        self.curr_position = self.peek_position;
         let tok = self.lexer.next_token();
+        self.peek_position = self.lexer.token_position();
+        self.peek_docs = self.lexer.take_doc_comments();
+        self.errors.append(&mut self.lexer.take_errors());
         self.peek_token = Some(tok);
     }
 
@@ -137,11 +198,32 @@ impl Parser {
         self.register_infix(Token::Minus.token_type(), Parser::parse_infix_expression);
         self.register_infix(Token::Divide.token_type(), Parser::parse_infix_expression);
         self.register_infix(Token::Multiply.token_type(), Parser::parse_infix_expression);
+        self.register_infix(Token::Modulo.token_type(), Parser::parse_infix_expression);
+        self.register_infix(
+            Token::Power.token_type(),
+            Parser::parse_right_associative_infix_expression,
+        );
+        self.register_infix(Token::Or.token_type(), Parser::parse_infix_expression);
+        self.register_infix(Token::Caret.token_type(), Parser::parse_infix_expression);
+        self.register_infix(Token::And.token_type(), Parser::parse_infix_expression);
+        self.register_infix(
+            Token::ShiftLeft.token_type(),
+            Parser::parse_infix_expression,
+        );
+        self.register_infix(
+            Token::ShiftRight.token_type(),
+            Parser::parse_infix_expression,
+        );
         self.register_infix(Token::Equal.token_type(), Parser::parse_infix_expression);
         self.register_infix(Token::NotEqual.token_type(), Parser::parse_infix_expression);
         self.register_infix(Token::Lt.token_type(), Parser::parse_infix_expression);
         self.register_infix(Token::Gt.token_type(), Parser::parse_infix_expression);
+        self.register_infix(Token::LtEq.token_type(), Parser::parse_infix_expression);
+        self.register_infix(Token::GtEq.token_type(), Parser::parse_infix_expression);
         self.register_infix(Token::Lparen.token_type(), Parser::parse_call_expression);
+        self.register_infix(Token::Lsquare.token_type(), Parser::parse_index_expression);
+        self.register_infix(Token::Dot.token_type(), Parser::parse_field_access);
+        self.register_infix(Token::Question.token_type(), Parser::parse_try_expression);
         self.register_infix(
             Token::String(String::new()).token_type(),
             Parser::parse_infix_expression,
@@ -157,17 +239,37 @@ impl Parser {
             Token::Integer("1".into()).token_type(),
             Parser::parse_integer_literal,
         );
+        self.register_prefix(
+            Token::Float("1.0".into()).token_type(),
+            Parser::parse_float_literal,
+        );
         self.register_prefix(Token::Bang.token_type(), Parser::parse_prefix_expression);
         self.register_prefix(Token::Minus.token_type(), Parser::parse_prefix_expression);
+        self.register_prefix(Token::Tilde.token_type(), Parser::parse_prefix_expression);
         self.register_prefix(Token::True.token_type(), Parser::parse_boolean);
         self.register_prefix(Token::False.token_type(), Parser::parse_boolean);
         self.register_prefix(Token::Lparen.token_type(), Parser::parse_grouped_expression);
         self.register_prefix(Token::If.token_type(), Parser::parse_if_expression);
+        self.register_prefix(Token::Match.token_type(), Parser::parse_match_expression);
         self.register_prefix(Token::Function.token_type(), Parser::parse_function_literal);
         self.register_prefix(
             Token::String(String::new()).token_type(),
             Parser::parse_string_literal,
         );
+        self.register_prefix(
+            Token::InterpolatedString(String::new(), Vec::new()).token_type(),
+            Parser::parse_interpolated_string,
+        );
+        self.register_prefix(
+            Token::Char(String::new()).token_type(),
+            Parser::parse_char_literal,
+        );
+        self.register_prefix(Token::Lsquare.token_type(), Parser::parse_array_literal);
+        self.register_prefix(Token::Lbrace.token_type(), Parser::parse_hash_literal);
+        self.register_prefix(
+            Token::Ellipsis.token_type(),
+            Parser::parse_spread_expression,
+        );
     }
 
     fn parse_statement(&mut self) -> Option<Statements> {
@@ -175,6 +277,11 @@ impl Parser {
         match curr_token {
             Some(Token::Let) => self.parse_let_statement(),
             Some(Token::Return) => self.parse_return_statement(),
+            Some(Token::Struct) => self.parse_struct_statement(),
+            Some(Token::Impl) => self.parse_impl_statement(),
+            Some(Token::Enum) => self.parse_enum_statement(),
+            Some(Token::Import) => self.parse_import_statement(),
+            Some(Token::Export) => self.parse_export_statement(),
             Some(_) => self.parse_expression_statement(),
             _ => None,
         }
@@ -182,24 +289,188 @@ impl Parser {
 
     fn parse_return_statement(&mut self) -> Option<Statements> {
         let return_token = self.curr_token.take().unwrap();
+        let position = self.curr_position;
         self.next_token();
         let return_value = self.parse_expression(Priority::Lowest)?;
         if self.peek_token == Some(Token::Semicolon) {
             self.next_token();
         }
-        Some(Statements::ReturnStatement(ReturnStatement::new(
-            return_token,
-            return_value,
+        Some(Statements::ReturnStatement(
+            ReturnStatement::new(return_token, return_value).with_position(position),
+        ))
+    }
+
+    fn parse_struct_statement(&mut self) -> Option<Statements> {
+        let struct_token = self.curr_token.take()?;
+        if !self.expect_peek(Token::Ident("".into())) {
+            return None;
+        }
+        let name = Identifier::new(self.curr_token.take()?);
+        if !self.expect_peek(Token::Lbrace) {
+            return None;
+        }
+        let mut fields: Vec<Identifier> = Vec::new();
+        while self.peek_token != Some(Token::Rbrace) {
+            if !self.expect_peek(Token::Ident("".into())) {
+                return None;
+            }
+            let field = Identifier::new(self.curr_token.take()?);
+            if fields.iter().any(|f| f.value() == field.value()) {
+                self.errors.push(format!(
+                    "field '{}' is declared more than once in struct {}",
+                    field, name
+                ));
+                return None;
+            }
+            fields.push(field);
+            if self.peek_token != Some(Token::Rbrace) && !self.expect_peek(Token::Comma) {
+                return None;
+            }
+        }
+        self.next_token();
+        if self.peek_token == Some(Token::Semicolon) {
+            self.next_token();
+        }
+        Some(Statements::StructStatement(StructStatement::new(
+            struct_token,
+            name,
+            fields,
         )))
     }
 
-    fn parse_let_statement(&mut self) -> Option<Statements> {
-        let let_token = self.curr_token.take().unwrap();
+    fn parse_enum_statement(&mut self) -> Option<Statements> {
+        let enum_token = self.curr_token.take()?;
+        if !self.expect_peek(Token::Ident("".into())) {
+            return None;
+        }
+        let name = Identifier::new(self.curr_token.take()?);
+        if !self.expect_peek(Token::Lbrace) {
+            return None;
+        }
+        let mut variants: Vec<(Identifier, Vec<Identifier>)> = Vec::new();
+        while self.peek_token != Some(Token::Rbrace) {
+            if !self.expect_peek(Token::Ident("".into())) {
+                return None;
+            }
+            let variant = Identifier::new(self.curr_token.take()?);
+            if variants.iter().any(|(v, _)| v.value() == variant.value()) {
+                self.errors.push(format!(
+                    "variant '{}' is declared more than once in enum {}",
+                    variant, name
+                ));
+                return None;
+            }
+            let mut fields = Vec::new();
+            if self.peek_token == Some(Token::Lparen) {
+                self.next_token();
+                while self.peek_token != Some(Token::Rparen) {
+                    if !self.expect_peek(Token::Ident("".into())) {
+                        return None;
+                    }
+                    fields.push(Identifier::new(self.curr_token.take()?));
+                    if self.peek_token != Some(Token::Rparen) && !self.expect_peek(Token::Comma) {
+                        return None;
+                    }
+                }
+                self.next_token();
+            }
+            variants.push((variant, fields));
+            if self.peek_token != Some(Token::Rbrace) && !self.expect_peek(Token::Comma) {
+                return None;
+            }
+        }
+        self.next_token();
+        if self.peek_token == Some(Token::Semicolon) {
+            self.next_token();
+        }
+        Some(Statements::EnumStatement(EnumStatement::new(
+            enum_token, name, variants,
+        )))
+    }
+
+    fn parse_impl_statement(&mut self) -> Option<Statements> {
+        let impl_token = self.curr_token.take()?;
+        if !self.expect_peek(Token::Ident("".into())) {
+            return None;
+        }
+        let name = Identifier::new(self.curr_token.take()?);
+        if !self.expect_peek(Token::Lbrace) {
+            return None;
+        }
+        let mut methods = Vec::new();
+        while self.peek_token != Some(Token::Rbrace) {
+            if !self.expect_peek(Token::Function) {
+                return None;
+            }
+            let fn_token = self.curr_token.take()?;
+            let position = self.curr_position;
+            if !self.expect_peek(Token::Ident("".into())) {
+                return None;
+            }
+            let method = Identifier::new(self.curr_token.take()?);
+            if !self.expect_peek(Token::Lparen) {
+                return None;
+            }
+            let parameters = self.parse_function_parameters()?;
+            let return_type = self.parse_return_type()?;
+            if !self.expect_peek(Token::Lbrace) {
+                return None;
+            }
+            let body = self.parse_block_statement()?;
+            let method_literal = FunctionLiteral::new(fn_token, parameters, body)
+                .with_return_type(return_type)
+                .with_position(position);
+            methods.push((method, method_literal));
+            if self.peek_token == Some(Token::Semicolon) {
+                self.next_token();
+            }
+        }
+        self.next_token();
+        Some(Statements::ImplStatement(ImplStatement::new(
+            impl_token, name, methods,
+        )))
+    }
+
+    fn parse_import_statement(&mut self) -> Option<Statements> {
+        let import_token = self.curr_token.take()?;
+        if !self.expect_peek(Token::String("".into())) {
+            return None;
+        }
+        let path = self.curr_token.take()?.literal().to_string();
+        if !self.expect_peek(Token::As) {
+            return None;
+        }
         if !self.expect_peek(Token::Ident("".into())) {
             return None;
         }
-        let ident_token = self.curr_token.take().unwrap();
-        let ident = Identifier::new(ident_token);
+        let alias = Identifier::new(self.curr_token.take()?);
+        if self.peek_token == Some(Token::Semicolon) {
+            self.next_token();
+        }
+        Some(Statements::ImportStatement(ImportStatement::new(
+            import_token,
+            path,
+            alias,
+        )))
+    }
+
+    fn parse_export_statement(&mut self) -> Option<Statements> {
+        if !self.expect_peek(Token::Let) {
+            return None;
+        }
+        match self.parse_let_statement()? {
+            Statements::LetStatement(stmt) => Some(Statements::LetStatement(stmt.with_export())),
+            _ => None,
+        }
+    }
+
+    fn parse_let_statement(&mut self) -> Option<Statements> {
+        let let_token = self.curr_token.take().unwrap();
+        let position = self.curr_position;
+        let docs = std::mem::take(&mut self.curr_docs);
+        self.next_token();
+        let pattern = self.parse_pattern()?;
+        let annotation = self.parse_annotation()?;
 
         if !self.expect_peek(Token::Assign) {
             return None;
@@ -209,9 +480,73 @@ impl Parser {
         if self.peek_token == Some(Token::Semicolon) {
             self.next_token();
         }
-        Some(Statements::LetStatement(LetStatement::new(
-            let_token, ident, value,
-        )))
+        Some(Statements::LetStatement(
+            LetStatement::new(let_token, pattern, value)
+                .with_docs(docs)
+                .with_annotation(annotation)
+                .with_position(position),
+        ))
+    }
+
+    // An optional `: type` after a binding
+    fn parse_annotation(&mut self) -> Option<Option<TypeAnnotation>> {
+        if self.peek_token != Some(Token::Colon) {
+            return Some(None);
+        }
+        self.next_token();
+        self.next_token();
+        Some(Some(self.parse_type()?))
+    }
+
+    // An optional `-> type` after a parameter list
+    fn parse_return_type(&mut self) -> Option<Option<TypeAnnotation>> {
+        if self.peek_token != Some(Token::Arrow) {
+            return Some(None);
+        }
+        self.next_token();
+        self.next_token();
+        Some(Some(self.parse_type()?))
+    }
+
+    // `int`, `Point`, `[string]` or `fn(int, int) -> bool`
+    fn parse_type(&mut self) -> Option<TypeAnnotation> {
+        match self.curr_token.take() {
+            Some(Token::Ident(name)) => Some(TypeAnnotation::Named(name)),
+            Some(Token::Lsquare) => {
+                self.next_token();
+                let element = self.parse_type()?;
+                if !self.expect_peek(Token::Rsquare) {
+                    return None;
+                }
+                Some(TypeAnnotation::Array(Box::new(element)))
+            }
+            Some(Token::Function) => {
+                if !self.expect_peek(Token::Lparen) {
+                    return None;
+                }
+                let mut parameters = Vec::new();
+                while self.peek_token != Some(Token::Rparen) {
+                    self.next_token();
+                    parameters.push(self.parse_type()?);
+                    if self.peek_token != Some(Token::Rparen) && !self.expect_peek(Token::Comma) {
+                        return None;
+                    }
+                }
+                self.next_token();
+                if !self.expect_peek(Token::Arrow) {
+                    return None;
+                }
+                self.next_token();
+                let result = self.parse_type()?;
+                Some(TypeAnnotation::Function(parameters, Box::new(result)))
+            }
+            tok => {
+                let tok = tok.unwrap_or(Token::Illegal);
+                self.errors
+                    .push(format!("expected a type, but received {}", tok.literal()));
+                None
+            }
+        }
     }
 
     fn expect_peek(&mut self, token_type: Token) -> bool {
@@ -257,6 +592,19 @@ impl Parser {
     }
 
     fn parse_expression(&mut self, precedence: Priority) -> Option<Expressions> {
+        let outer = self.nesting;
+        let expression = self.parse_nested_expression(precedence);
+        self.nesting = outer;
+        expression
+    }
+
+    // Every operator folded into the left operand nests it one level deeper
+    fn parse_nested_expression(&mut self, precedence: Priority) -> Option<Expressions> {
+        self.nest()?;
+        // The lexer already reported why the token is illegal
+        if self.curr_token == Some(Token::Illegal) {
+            return None;
+        }
         let token_type = self.curr_token.as_mut()?.token_type();
         let prefix_func = self.prefix_parse_fns.get(&token_type);
         if prefix_func.is_none() {
@@ -271,6 +619,7 @@ impl Parser {
             if self.peek_token.is_none() {
                 return left_exp;
             }
+            self.nest()?;
             let peek_token_type = self.peek_token.as_ref().unwrap().token_type();
             let infix_func = self
                 .infix_parse_fns
@@ -281,6 +630,24 @@ impl Parser {
         left_exp
     }
 
+    fn nest(&mut self) -> Option<()> {
+        self.nesting += 1;
+        if self.nesting > MAX_NESTING {
+            self.errors.push(format!(
+                "expression nested more than {} levels deep",
+                MAX_NESTING
+            ));
+            // Nothing sensible can be parsed up to the end of the statement
+            while self.curr_token != Some(Token::Semicolon)
+                && !matches!(self.peek_token, Some(Token::Eof) | None)
+            {
+                self.next_token();
+            }
+            return None;
+        }
+        Some(())
+    }
+
     fn parse_if_expression(&mut self) -> Option<Expressions> {
         match self.expect_peek(Token::Lparen) {
             true => {
@@ -313,6 +680,187 @@ impl Parser {
         }
     }
 
+    fn parse_match_expression(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        if !self.expect_peek(Token::Lparen) {
+            return None;
+        }
+        self.next_token();
+        let subject = self.parse_expression(Priority::Lowest)?;
+        if !self.expect_peek(Token::Rparen) || !self.expect_peek(Token::Lbrace) {
+            return None;
+        }
+        let mut arms = Vec::new();
+        while self.peek_token != Some(Token::Rbrace) {
+            self.next_token();
+            arms.push(self.parse_match_arm()?);
+            // The comma is optional after an arm ending in a brace
+            if self.peek_token == Some(Token::Comma) {
+                self.next_token();
+            } else if self.peek_token != Some(Token::Rbrace)
+                && self.curr_token != Some(Token::Rbrace)
+            {
+                self.peek_error(Token::Rbrace);
+                return None;
+            }
+        }
+        self.next_token();
+        Some(Expressions::MatchExpression(MatchExpression::new(
+            tok, subject, arms,
+        )))
+    }
+
+    // An arm body is either a block or a single expression
+    fn parse_match_arm(&mut self) -> Option<MatchArm> {
+        let pattern = self.parse_pattern()?;
+        let mut guard = None;
+        if self.peek_token == Some(Token::If) {
+            self.next_token();
+            self.next_token();
+            guard = Some(self.parse_expression(Priority::Lowest)?);
+        }
+        if !self.expect_peek(Token::FatArrow) {
+            return None;
+        }
+        self.next_token();
+        let body = if self.curr_token == Some(Token::Lbrace) {
+            self.parse_block_statement()?
+        } else {
+            let tok = self.curr_token.clone()?;
+            let expression = self.parse_expression(Priority::Lowest)?;
+            BlockStatement::new(
+                tok,
+                vec![Statements::ExpressionStatement(ExpressionStatement::new(
+                    expression,
+                ))],
+            )
+        };
+        Some(MatchArm::new(pattern, guard, body))
+    }
+
+    fn parse_pattern(&mut self) -> Option<Pattern> {
+        let mut patterns = vec![self.parse_single_pattern()?];
+        while self.peek_token == Some(Token::Or) {
+            self.next_token();
+            self.next_token();
+            patterns.push(self.parse_single_pattern()?);
+        }
+        match patterns.len() {
+            1 => patterns.pop(),
+            _ => Some(Pattern::Alternative(patterns)),
+        }
+    }
+
+    fn parse_single_pattern(&mut self) -> Option<Pattern> {
+        match self.curr_token.as_ref()? {
+            Token::Ident(name) if name == "_" => Some(Pattern::Wildcard),
+            Token::Ident(_) if self.peek_token == Some(Token::Lparen) => {
+                let name = Identifier::new(self.curr_token.take()?);
+                self.next_token();
+                let mut fields = Vec::new();
+                while self.peek_token != Some(Token::Rparen) {
+                    self.next_token();
+                    fields.push(self.parse_pattern()?);
+                    if self.peek_token != Some(Token::Rparen) && !self.expect_peek(Token::Comma) {
+                        return None;
+                    }
+                }
+                self.next_token();
+                Some(Pattern::Variant(name, fields))
+            }
+            Token::Ident(_) => {
+                let tok = self.curr_token.take()?;
+                Some(Pattern::Binding(Identifier::new(tok)))
+            }
+            Token::Lsquare => self.parse_array_pattern(),
+            Token::Lbrace => self.parse_hash_pattern(),
+            Token::Integer(_)
+            | Token::Float(_)
+            | Token::String(_)
+            | Token::Char(_)
+            | Token::True
+            | Token::False
+            | Token::Minus => Some(Pattern::Literal(Box::new(
+                self.parse_expression(Priority::Prefix)?,
+            ))),
+            tok => {
+                self.errors
+                    .push(format!("unexpected {} in pattern", tok.literal()));
+                None
+            }
+        }
+    }
+
+    fn parse_array_pattern(&mut self) -> Option<Pattern> {
+        let mut head = Vec::new();
+        let mut rest = None;
+        let mut tail = Vec::new();
+        while self.peek_token != Some(Token::Rsquare) {
+            self.next_token();
+            if self.curr_token == Some(Token::DotDot) {
+                if rest.is_some() {
+                    self.errors.push(String::from(
+                        "only one rest pattern is allowed in an array pattern",
+                    ));
+                    return None;
+                }
+                let mut name = None;
+                if let Some(Token::Ident(_)) = self.peek_token {
+                    self.next_token();
+                    name = Some(Identifier::new(self.curr_token.take()?));
+                }
+                rest = Some(name);
+            } else if rest.is_some() {
+                tail.push(self.parse_pattern()?);
+            } else {
+                head.push(self.parse_pattern()?);
+            }
+            if self.peek_token == Some(Token::Comma) {
+                self.next_token();
+            } else if self.peek_token != Some(Token::Rsquare) {
+                self.peek_error(Token::Rsquare);
+                return None;
+            }
+        }
+        self.next_token();
+        Some(Pattern::Array { head, rest, tail })
+    }
+
+    // `{"key": pattern}` matches a key, `{name}` is short for `{"name": name}`
+    fn parse_hash_pattern(&mut self) -> Option<Pattern> {
+        let mut pairs = Vec::new();
+        while self.peek_token != Some(Token::Rbrace) {
+            self.next_token();
+            if let Some(Token::Ident(name)) = &self.curr_token {
+                let key =
+                    Expressions::StringLiteral(StringLiteral::new(Token::String(name.clone())));
+                if self.peek_token == Some(Token::Colon) {
+                    self.next_token();
+                    self.next_token();
+                    pairs.push((key, self.parse_pattern()?));
+                } else {
+                    let ident = Identifier::new(self.curr_token.take()?);
+                    pairs.push((key, Pattern::Binding(ident)));
+                }
+            } else {
+                let key = self.parse_expression(Priority::Prefix)?;
+                if !self.expect_peek(Token::Colon) {
+                    return None;
+                }
+                self.next_token();
+                pairs.push((key, self.parse_pattern()?));
+            }
+            if self.peek_token == Some(Token::Comma) {
+                self.next_token();
+            } else if self.peek_token != Some(Token::Rbrace) {
+                self.peek_error(Token::Rbrace);
+                return None;
+            }
+        }
+        self.next_token();
+        Some(Pattern::Hash(pairs))
+    }
+
     fn parse_block_statement(&mut self) -> Option<BlockStatement> {
         let mut v = Vec::new();
         let curr_token = self.curr_token.take()?;
@@ -338,27 +886,107 @@ impl Parser {
     }
 
     fn parse_identifier(&mut self) -> Option<Expressions> {
+        if self.peek_token == Some(Token::Lbrace) {
+            return self.parse_struct_literal();
+        }
         let tok = self.curr_token.take().unwrap();
         Some(Expressions::Identifier(Identifier::new(tok)))
     }
 
+    // `Name { field: value, shorthand, ..base }`
+    fn parse_struct_literal(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        self.next_token();
+        let mut fields = Vec::new();
+        let mut base = None;
+        while self.peek_token != Some(Token::Rbrace) {
+            self.next_token();
+            if self.curr_token == Some(Token::DotDot) {
+                self.next_token();
+                base = Some(self.parse_expression(Priority::Lowest)?);
+                if self.peek_token != Some(Token::Rbrace) {
+                    self.errors.push(String::from(
+                        "the ..base of a struct literal must come last",
+                    ));
+                    return None;
+                }
+                break;
+            }
+            let field = match self.curr_token.take()? {
+                tok @ Token::Ident(_) => Identifier::new(tok),
+                tok => {
+                    self.errors.push(format!(
+                        "expected a field name, but received {}",
+                        tok.literal()
+                    ));
+                    return None;
+                }
+            };
+            let value = if self.peek_token == Some(Token::Colon) {
+                self.next_token();
+                self.next_token();
+                self.parse_expression(Priority::Lowest)?
+            } else {
+                Expressions::Identifier(field.clone())
+            };
+            fields.push((field, value));
+            if self.peek_token != Some(Token::Rbrace) && !self.expect_peek(Token::Comma) {
+                return None;
+            }
+        }
+        self.next_token();
+        Some(Expressions::StructLiteral(Box::new(StructLiteral::new(
+            tok, fields, base,
+        ))))
+    }
+
+    fn parse_try_expression(&mut self, value: Expressions) -> Option<Expressions> {
+        self.next_token();
+        let tok = self.curr_token.take()?;
+        Some(Expressions::TryExpression(Box::new(TryExpression::new(
+            tok, value,
+        ))))
+    }
+
+    fn parse_field_access(&mut self, object: Expressions) -> Option<Expressions> {
+        self.next_token();
+        let tok = self.curr_token.take()?;
+        if !self.expect_peek(Token::Ident("".into())) {
+            return None;
+        }
+        let field = Identifier::new(self.curr_token.take()?);
+        Some(Expressions::FieldAccess(Box::new(FieldAccess::new(
+            tok, object, field,
+        ))))
+    }
+
     fn parse_integer_literal(&mut self) -> Option<Expressions> {
         let tok = self.curr_token.take()?;
-        Some(Expressions::IntegerLiteral(IntegerLiteral::new(tok)))
+        let literal = IntegerLiteral::new(tok);
+        if !literal.is_valid() {
+            self.errors
+                .push(format!("could not parse {} as integer", literal));
+            return None;
+        }
+        Some(Expressions::IntegerLiteral(literal))
+    }
+
+    fn parse_float_literal(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        Some(Expressions::FloatLiteral(FloatLiteral::new(tok)))
     }
 
     fn parse_boolean(&mut self) -> Option<Expressions> {
         let tok = self.curr_token.take()?;
-        Some(Expressions::BooleanLiteral(BooleanLiteral::new(
-            tok,
-        )))
+        Some(Expressions::BooleanLiteral(BooleanLiteral::new(tok)))
     }
     fn parse_prefix_expression(&mut self) -> Option<Expressions> {
         let tok = self.curr_token.take()?;
+        let position = self.curr_position;
         self.next_token();
         let expression_right = self.parse_expression(Priority::Prefix)?;
         Some(Expressions::PrefixExpression(Box::new(
-            PrefixExpression::new(tok, expression_right),
+            PrefixExpression::new(tok, expression_right).with_position(position),
         )))
     }
 
@@ -366,46 +994,78 @@ impl Parser {
         self.next_token();
         let tok = self.curr_token.as_ref()?;
         let operator = String::from(tok.literal());
+        let position = self.curr_position;
         let precedence = self.curr_precedence();
         self.next_token();
         let expression_right = self.parse_expression(precedence)?;
         Some(Expressions::InfixExpression(Box::new(
-            InfixExpression::new(operator, expression_left, expression_right),
+            InfixExpression::new(operator, expression_left, expression_right)
+                .with_position(position),
+        )))
+    }
+
+    // The right operand binds one level looser so that `a ** b ** c` is `a ** (b ** c)`
+    fn parse_right_associative_infix_expression(
+        &mut self,
+        expression_left: Expressions,
+    ) -> Option<Expressions> {
+        self.next_token();
+        let tok = self.curr_token.as_ref()?;
+        let operator = String::from(tok.literal());
+        let position = self.curr_position;
+        self.next_token();
+        let expression_right = self.parse_expression(Priority::Product)?;
+        Some(Expressions::InfixExpression(Box::new(
+            InfixExpression::new(operator, expression_left, expression_right)
+                .with_position(position),
         )))
     }
 
     fn parse_function_literal(&mut self) -> Option<Expressions> {
         let tok = self.curr_token.take()?;
+        let position = self.curr_position;
         if !self.expect_peek(Token::Lparen) {
             return None;
         }
         let parameters = self.parse_function_parameters()?;
+        let return_type = self.parse_return_type()?;
         if !self.expect_peek(Token::Lbrace) {
             return None;
         }
         let body = self.parse_block_statement()?;
-        Some(Expressions::FunctionLiteral(FunctionLiteral::new(
-            tok, parameters, body,
-        )))
+        Some(Expressions::FunctionLiteral(
+            FunctionLiteral::new(tok, parameters, body)
+                .with_return_type(return_type)
+                .with_position(position),
+        ))
     }
 
-    fn parse_function_parameters(&mut self) -> Option<Vec<Identifier>> {
+    fn parse_function_parameters(&mut self) -> Option<Vec<Parameter>> {
         let mut v = Vec::new();
         if self.peek_token == Some(Token::Rparen) {
             self.next_token();
             return Some(v);
         }
         self.next_token();
-        let tok = self.curr_token.take().unwrap();
-        let ident = Identifier::new(tok);
-        v.push(ident);
+        v.push(self.parse_parameter()?);
 
         while self.peek_token == Some(Token::Comma) {
+            if v.last().is_some_and(|p| p.is_rest()) {
+                self.errors
+                    .push(String::from("rest parameter must be the last parameter"));
+                return None;
+            }
             self.next_token();
             self.next_token();
-            let tok = self.curr_token.take().unwrap();
-            let ident = Identifier::new(tok);
-            v.push(ident);
+            let parameter = self.parse_parameter()?;
+            let after_default = v.last().is_some_and(|p| p.default().is_some());
+            if after_default && parameter.default().is_none() && !parameter.is_rest() {
+                self.errors.push(String::from(
+                    "required parameter follows a parameter with a default",
+                ));
+                return None;
+            }
+            v.push(parameter);
         }
 
         if !self.expect_peek(Token::Rparen) {
@@ -414,18 +1074,98 @@ impl Parser {
         Some(v)
     }
 
+    // `...rest`, `pattern` or `pattern = default`
+    fn parse_parameter(&mut self) -> Option<Parameter> {
+        if self.curr_token == Some(Token::Ellipsis) {
+            if !self.expect_peek(Token::Ident("".into())) {
+                return None;
+            }
+            let name = Identifier::new(self.curr_token.take()?);
+            let annotation = self.parse_annotation()?;
+            return Some(Parameter::rest(name).with_annotation(annotation));
+        }
+        let pattern = self.parse_pattern()?;
+        let annotation = self.parse_annotation()?;
+        let mut default = None;
+        if self.peek_token == Some(Token::Assign) {
+            self.next_token();
+            self.next_token();
+            default = Some(self.parse_expression(Priority::Lowest)?);
+        }
+        Some(Parameter::new(pattern, default).with_annotation(annotation))
+    }
+
     fn parse_call_expression(&mut self, function: Expressions) -> Option<Expressions> {
         self.next_token();
         let tok = self.curr_token.take()?;
-        let args = self.parse_call_arguments()?;
-        Some(Expressions::CallExpression(Box::new(CallExpression::new(
-            tok, function, args,
-        ))))
+        let position = self.curr_position;
+        let (args, named) = self.parse_call_arguments()?;
+        Some(Expressions::CallExpression(Box::new(
+            CallExpression::new(tok, function, args)
+                .with_named(named)
+                .with_position(position),
+        )))
     }
 
-    fn parse_call_arguments(&mut self) -> Option<Vec<Expressions>> {
-        let mut v = Vec::new();
+    fn parse_spread_expression(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        self.next_token();
+        let value = self.parse_expression(Priority::Prefix)?;
+        Some(Expressions::SpreadExpression(Box::new(
+            SpreadExpression::new(tok, value),
+        )))
+    }
+
+    fn parse_index_expression(&mut self, left: Expressions) -> Option<Expressions> {
+        self.next_token();
+        let tok = self.curr_token.take()?;
+        self.next_token();
+        let index = self.parse_expression(Priority::Lowest)?;
+        if !self.expect_peek(Token::Rsquare) {
+            return None;
+        }
+        Some(Expressions::IndexExpression(Box::new(
+            IndexExpression::new(tok, left, index),
+        )))
+    }
+
+    fn parse_call_arguments(&mut self) -> Option<CallArguments> {
+        let mut args = Vec::new();
+        let mut named = Vec::new();
         if self.peek_token == Some(Token::Rparen) {
+            self.next_token();
+            return Some((args, named));
+        }
+        loop {
+            self.next_token();
+            let is_named = matches!(self.curr_token, Some(Token::Ident(_)))
+                && self.peek_token == Some(Token::Colon);
+            if is_named {
+                let name = Identifier::new(self.curr_token.take()?);
+                self.next_token();
+                self.next_token();
+                named.push((name, self.parse_expression(Priority::Lowest)?));
+            } else if !named.is_empty() {
+                self.errors
+                    .push(String::from("positional argument follows named argument"));
+                return None;
+            } else {
+                args.push(self.parse_expression(Priority::Lowest)?);
+            }
+            if self.peek_token != Some(Token::Comma) {
+                break;
+            }
+            self.next_token();
+        }
+        if !self.expect_peek(Token::Rparen) {
+            return None;
+        }
+        Some((args, named))
+    }
+
+    fn parse_expression_list(&mut self, end: Token) -> Option<Vec<Expressions>> {
+        let mut v = Vec::new();
+        if self.peek_token.as_ref() == Some(&end) {
             self.next_token();
             return Some(v);
         }
@@ -441,12 +1181,82 @@ impl Parser {
             v.push(exp);
         }
 
-        if !self.expect_peek(Token::Rparen) {
+        if !self.expect_peek(end) {
             return None;
         }
         Some(v)
     }
 
+    // Every `${...}` is parsed on its own and has to hold exactly one expression
+    fn parse_interpolated_string(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        let segments = match &tok {
+            Token::InterpolatedString(_, parts) => parts.clone(),
+            _ => return None,
+        };
+        let mut parts = Vec::new();
+        for segment in segments {
+            match segment {
+                StringPart::Text(text) => parts.push(InterpolationPart::Text(text)),
+                StringPart::Code(code) => {
+                    let mut parser = Parser::new(Lexer::new(code.clone()));
+                    let expression = match parser.curr_token {
+                        Some(Token::Eof) => None,
+                        _ => parser.parse_expression(Priority::Lowest),
+                    };
+                    let complete = parser.peek_token == Some(Token::Eof);
+                    self.errors.append(&mut parser.errors);
+                    match expression {
+                        Some(expression) if complete => {
+                            parts.push(InterpolationPart::Expression(expression))
+                        }
+                        _ => {
+                            self.errors.push(format!(
+                                "invalid expression in interpolation: ${{{}}}",
+                                code
+                            ));
+                            return None;
+                        }
+                    }
+                }
+            }
+        }
+        Some(Expressions::InterpolatedString(InterpolatedString::new(
+            tok, parts,
+        )))
+    }
+
+    fn parse_char_literal(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        Some(Expressions::CharLiteral(CharLiteral::new(tok)))
+    }
+
+    fn parse_array_literal(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        let elements = self.parse_expression_list(Token::Rsquare)?;
+        Some(Expressions::ArrayLiteral(ArrayLiteral::new(tok, elements)))
+    }
+
+    fn parse_hash_literal(&mut self) -> Option<Expressions> {
+        let tok = self.curr_token.take()?;
+        let mut pairs = Vec::new();
+        while self.peek_token != Some(Token::Rbrace) {
+            self.next_token();
+            let key = self.parse_expression(Priority::Lowest)?;
+            if !self.expect_peek(Token::Colon) {
+                return None;
+            }
+            self.next_token();
+            let value = self.parse_expression(Priority::Lowest)?;
+            pairs.push((key, value));
+            if self.peek_token != Some(Token::Rbrace) && !self.expect_peek(Token::Comma) {
+                return None;
+            }
+        }
+        self.next_token();
+        Some(Expressions::HashLiteral(HashLiteral::new(tok, pairs)))
+    }
+
     fn parse_string_literal(&mut self) -> Option<Expressions> {
         let tok = self
             .curr_token
@@ -493,7 +1303,7 @@ mod tests {
     }
 
     fn test_integer(expression: &IntegerLiteral, integer: isize) {
-        assert_eq!(integer, expression.value());
+        assert_eq!(Some(integer), expression.value());
         assert_eq!(format!("{}", integer), expression.token_literal());
     }
 
@@ -570,7 +1380,7 @@ mod tests {
     }
     fn test_let_statement(let_statement: &LetStatement, identifier: Types, value: Types) {
         match identifier {
-            Types::String(v) => test_ident(let_statement.name(), v),
+            Types::String(v) => test_ident(let_statement.name().unwrap(), v),
             _ => {
                 unreachable!()
             }
@@ -590,7 +1400,7 @@ mod tests {
         let let_statement = statement
             .as_let_statement()
             .expect("statement was not LetStatement");
-        assert_eq!("x", let_statement.name().token_literal());
+        assert_eq!("x", let_statement.name().unwrap().token_literal());
         assert_eq!("fn(x, y) { (x + y) }", format!("{}", let_statement.value()));
     }
 
@@ -667,6 +1477,74 @@ mod tests {
         let _ = test_helper(input);
     }
 
+    #[test]
+    fn test_lexer_errors_are_reported() {
+        let l = Lexer::new(r#"let x = "abc;"#.into());
+        let mut parser = Parser::new(l);
+        let _ = parser.parse_program();
+        assert_eq!(
+            &vec![String::from("unterminated string starting at 1:9")],
+            parser.errors()
+        );
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let sum = |terms: usize| format!("1{}", " + 1".repeat(terms - 1));
+        let negation = |depth: usize| format!("{}1{}", "-(".repeat(depth), ")".repeat(depth));
+        let error = format!("expression nested more than {} levels deep", MAX_NESTING);
+        let inputs = [
+            (sum(MAX_NESTING / 2), None),
+            (negation(MAX_NESTING / 4), None),
+            (sum(MAX_NESTING + 1), Some(&error)),
+            (negation(MAX_NESTING), Some(&error)),
+        ];
+        for (input, expected) in inputs {
+            let mut parser = Parser::new(Lexer::new(input));
+            let _ = parser.parse_program();
+            assert_eq!(expected, parser.errors().first());
+        }
+    }
+
+    #[test]
+    fn test_doc_comments_attach_to_let() {
+        let input = r#"
+        /// Adds one.
+        /// Works on floats too.
+        let inc = fn(x) { x + 1 };
+        /// Dangling, the next statement is not a binding
+        inc(1);
+        // plain comment
+        let two = 2;
+        "#;
+
+        let mut program = test_helper(input);
+        assert_eq!(3, program.statements.len());
+        let inc = program.statements.remove(0);
+        let inc = inc
+            .as_let_statement()
+            .expect("statement was not LetStatement");
+        assert_eq!(&["Adds one.", "Works on floats too."], inc.docs());
+        let two = program.statements.remove(1);
+        let two = two
+            .as_let_statement()
+            .expect("statement was not LetStatement");
+        assert!(two.docs().is_empty());
+    }
+
+    #[test]
+    fn test_interpolation_errors() {
+        let inputs = [
+            (r#""${}""#, "invalid expression in interpolation: ${}"),
+            (r#""${1 2}""#, "invalid expression in interpolation: ${1 2}"),
+        ];
+        for (input, expected) in inputs {
+            let mut parser = Parser::new(Lexer::new(input.into()));
+            let _ = parser.parse_program();
+            assert_eq!(Some(&String::from(expected)), parser.errors().last());
+        }
+    }
+
     #[test]
     fn test_ident_expression() {
         let input = "foobar;";
@@ -874,6 +1752,16 @@ mod tests {
                 "add(a + b + c * d / f + g)",
                 "add((((a + b) + ((c * d) / f)) + g))\n",
             ),
+            ("a * b[2]", "(a * (b[2]))\n"),
+            (
+                r#""x = ${x * 2 + 1}, ${f(y)}""#,
+                "x = ${((x * 2) + 1)}, ${f(y)}\n",
+            ),
+            (
+                "a * [1, 2, 3, 4][b * c] * d",
+                "((a * ([1, 2, 3, 4][(b * c)])) * d)\n",
+            ),
+            ("add(a[b + 1], s[0][1])", "add((a[(b + 1)]), ((s[0])[1]))\n"),
         ];
 
         for input in input_expected {
@@ -986,8 +1874,8 @@ mod tests {
             .as_function_literal()
             .expect("expression was not a function literal");
         assert_eq!(fn_literal.parameters().len(), 2);
-        test_ident(fn_literal.parameters().get(0).unwrap(), "x");
-        test_ident(fn_literal.parameters().get(1).unwrap(), "y");
+        test_ident(fn_literal.parameters()[0].name().unwrap(), "x");
+        test_ident(fn_literal.parameters()[1].name().unwrap(), "y");
         assert_eq!(fn_literal.body().statements().len(), 1);
         let infix = fn_literal
             .body()
@@ -1025,8 +1913,8 @@ mod tests {
                 .as_function_literal()
                 .expect("expression was not a function literal");
             assert_eq!(expected.len(), fn_literal.parameters().len());
-            for (i, ident) in fn_literal.parameters().iter().enumerate() {
-                test_ident(ident, expected.get(i).unwrap());
+            for (i, param) in fn_literal.parameters().iter().enumerate() {
+                test_ident(param.name().unwrap(), expected.get(i).unwrap());
             }
         }
     }
@@ -1091,4 +1979,373 @@ mod tests {
             .expect("argument was not a string literal");
         assert_eq!("hello world", str_literal.value());
     }
+
+    #[test]
+    fn test_float_literal() {
+        let inputs = [("2.75;", 2.75), ("1e-3;", 0.001), ("2.5E2;", 250.0)];
+
+        for (input, exp) in inputs {
+            let mut program = test_helper(input);
+            let statement = program.statements.remove(0);
+            let float = statement
+                .as_expression_statement()
+                .expect("Statement was not an expression statement")
+                .expression()
+                .clone()
+                .as_float_literal()
+                .expect("expression was not a float literal");
+            assert_eq!(exp, float.value());
+            assert_eq!(&input[..input.len() - 1], float.token_literal());
+        }
+
+        let program = test_helper("-1.5 * 2 + 0.5");
+        assert_eq!("(((-1.5) * 2) + 0.5)\n", format!("{}", program));
+    }
+
+    #[test]
+    fn test_comparison_precedence() {
+        let input_expected = [
+            ("a + 1 <= b * 2", "((a + 1) <= (b * 2))\n"),
+            ("a >= b == c <= d", "((a >= b) == (c <= d))\n"),
+            ("5 <= 5;", "(5 <= 5)\n"),
+        ];
+
+        for (input, exp) in input_expected {
+            let program = test_helper(input);
+            assert_eq!(exp, format!("{}", program));
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_and_bitwise_precedence() {
+        let input_expected = [
+            ("a % b * c", "((a % b) * c)\n"),
+            ("a ** b ** c", "(a ** (b ** c))\n"),
+            ("a * b ** c", "(a * (b ** c))\n"),
+            ("-a ** b", "((-a) ** b)\n"),
+            ("a | b ^ c & d", "(a | (b ^ (c & d)))\n"),
+            ("a & b == c", "((a & b) == c)\n"),
+            ("a << b + c", "(a << (b + c))\n"),
+            ("a >> b < c", "((a >> b) < c)\n"),
+            ("~a & b", "((~a) & b)\n"),
+        ];
+
+        for (input, exp) in input_expected {
+            let program = test_helper(input);
+            assert_eq!(exp, format!("{}", program));
+        }
+    }
+
+    #[test]
+    fn test_array_and_char_literals() {
+        let mut program = test_helper("[1, 2 * 2, 'x', []]");
+        let statement = program.statements.remove(0);
+        let statement = statement
+            .as_expression_statement()
+            .expect("Expected expression statement");
+        let array = statement
+            .expression()
+            .clone()
+            .as_array_literal()
+            .expect("expression was not an array literal");
+        assert_eq!(4, array.elements().len());
+        assert_eq!("1", array.elements()[0].to_string());
+        assert_eq!("(2 * 2)", array.elements()[1].to_string());
+        match &array.elements()[2] {
+            Expressions::CharLiteral(c) => assert_eq!('x', c.value()),
+            other => panic!("expected a char literal, got {:?}", other),
+        }
+        assert_eq!("[]", array.elements()[3].to_string());
+    }
+
+    #[test]
+    fn test_hash_literals() {
+        let input_expected = [
+            (
+                r#"{"one": 1, 2: 1 + 1, true: [3]}"#,
+                "{one: 1, 2: (1 + 1), true: [3]}\n",
+            ),
+            ("{}", "{}\n"),
+            ("{'a': {1: 2}}", "{'a': {1: 2}}\n"),
+        ];
+        for (input, expected) in input_expected {
+            let program = test_helper(input);
+            assert_eq!(expected, format!("{}", program));
+        }
+    }
+
+    #[test]
+    fn test_match_expression() {
+        let input = r#"
+        match (x) {
+            0 => "zero",
+            -1 | 1 => { "one" }
+            [first, ..rest] => first,
+            [.., last] => last,
+            {"k": v, name} => v,
+            n if n > 10 => n,
+            _ => x * 2
+        }
+        "#;
+        let program = test_helper(input);
+        let statement = program.statements[0]
+            .clone()
+            .as_expression_statement()
+            .expect("Statement was not an expression statement");
+        let match_expression = match statement.expression() {
+            Expressions::MatchExpression(x) => x,
+            other => panic!("expected a match expression, got {}", other),
+        };
+        let patterns: Vec<String> = match_expression
+            .arms()
+            .iter()
+            .map(|arm| arm.pattern().to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "0",
+                "(-1) | 1",
+                "[first, ..rest]",
+                "[.., last]",
+                "{k: v, name: name}",
+                "n",
+                "_"
+            ],
+            patterns
+        );
+        assert_eq!(
+            "n if (n > 10) => { n }",
+            match_expression.arms()[5].to_string()
+        );
+    }
+
+    #[test]
+    fn test_match_expression_errors() {
+        let inputs = [
+            (
+                "match (x) { 1 => 2 3 => 4 }",
+                "expected next token to be Rbrace, but received Integer(\"3\")",
+            ),
+            (
+                "match (x) { [..a, ..b] => 1 }",
+                "only one rest pattern is allowed in an array pattern",
+            ),
+            ("match (x) { fn => 1 }", "unexpected fn in pattern"),
+        ];
+        for (input, expected) in inputs {
+            let mut parser = Parser::new(Lexer::new(input.into()));
+            let _ = parser.parse_program();
+            assert_eq!(Some(&String::from(expected)), parser.errors().first());
+        }
+    }
+
+    #[test]
+    fn test_destructuring_let() {
+        let inputs = [
+            ("let [a, b, ..rest] = arr;", "let [a, b, ..rest] = arr;"),
+            (
+                "let {name, age} = person;",
+                "let {name: name, age: age} = person;",
+            ),
+            (
+                "let [x, {\"pos\": [px, _]}] = v;",
+                "let [x, {pos: [px, _]}] = v;",
+            ),
+            ("fn([a, b], {c}) { a };", "fn([a, b], {c: c}) { a }"),
+        ];
+        for (input, expected) in inputs {
+            let program = test_helper(input);
+            assert_eq!(expected, program.statements[0].to_string());
+        }
+    }
+
+    #[test]
+    fn test_parameters_and_arguments() {
+        let inputs = [
+            ("fn(x, y = 10) { x };", "fn(x, y = 10) { x }"),
+            (
+                "fn(first, ...rest) { rest };",
+                "fn(first, ...rest) { rest }",
+            ),
+            ("f(...args, 1);", "f(...args, 1)"),
+            ("f(1, y: 2, x: a + b);", "f(1, y: 2, x: (a + b))"),
+            ("[0, ...xs];", "[0, ...xs]"),
+        ];
+        for (input, expected) in inputs {
+            let program = test_helper(input);
+            assert_eq!(expected, program.statements[0].to_string());
+        }
+
+        let errors = [
+            (
+                "fn(...rest, x) { x };",
+                "rest parameter must be the last parameter",
+            ),
+            (
+                "fn(x = 1, y) { y };",
+                "required parameter follows a parameter with a default",
+            ),
+            ("f(x: 1, 2);", "positional argument follows named argument"),
+        ];
+        for (input, expected) in errors {
+            let mut parser = Parser::new(Lexer::new(input.into()));
+            let _ = parser.parse_program();
+            assert_eq!(Some(&String::from(expected)), parser.errors().first());
+        }
+    }
+
+    #[test]
+    fn test_structs() {
+        let inputs = [
+            ("struct Point { x, y }", "struct Point { x, y }"),
+            ("Point { x: 1, y: a + 1 };", "Point { x: 1, y: (a + 1) }"),
+            ("Point { x, ..p };", "Point { x: x, ..p }"),
+            ("p.x + q.pos.y;", "((p.x) + ((q.pos).y))"),
+            ("a.b(1)[0];", "((a.b)(1)[0])"),
+        ];
+        for (input, expected) in inputs {
+            let program = test_helper(input);
+            assert_eq!(expected, program.statements[0].to_string());
+        }
+
+        let errors = [
+            (
+                "struct P { x, x }",
+                "field 'x' is declared more than once in struct P",
+            ),
+            (
+                "P { ..p, x: 1 }",
+                "the ..base of a struct literal must come last",
+            ),
+            (
+                "p.1",
+                "expected next token to be Ident(\"\"), but received Integer(\"1\")",
+            ),
+        ];
+        for (input, expected) in errors {
+            let mut parser = Parser::new(Lexer::new(input.into()));
+            let _ = parser.parse_program();
+            assert_eq!(Some(&String::from(expected)), parser.errors().first());
+        }
+    }
+
+    #[test]
+    fn test_impl_statement() {
+        let input = "impl Point { fn area(self) { self.x * self.y } fn origin() { 0 }; }";
+        let program = test_helper(input);
+        assert_eq!(
+            "impl Point { fn area(self) { ((self.x) * (self.y)) } fn origin() { 0 } }",
+            program.statements[0].to_string()
+        );
+        let program = test_helper("p.scale(2).area();");
+        assert_eq!("((p.scale)(2).area)()", program.statements[0].to_string());
+    }
+
+    #[test]
+    fn test_enum_statement() {
+        let program = test_helper("enum Shape { Circle(r), Rect(w, h), Empty }");
+        assert_eq!(
+            "enum Shape { Circle(r), Rect(w, h), Empty }",
+            program.statements[0].to_string()
+        );
+        let program =
+            test_helper("match (s) { Circle(r) => r, Rect(_, [h, ..]) | Some(h) => h, None => 0 }");
+        let statement = program.statements[0]
+            .clone()
+            .as_expression_statement()
+            .expect("Statement was not an expression statement");
+        let match_expression = match statement.expression() {
+            Expressions::MatchExpression(x) => x,
+            other => panic!("expected a match expression, got {}", other),
+        };
+        let patterns: Vec<String> = match_expression
+            .arms()
+            .iter()
+            .map(|arm| arm.pattern().to_string())
+            .collect();
+        assert_eq!(
+            vec!["Circle(r)", "Rect(_, [h, ..]) | Some(h)", "None"],
+            patterns
+        );
+
+        let mut parser = Parser::new(Lexer::new("enum E { A, A(x) }".into()));
+        let _ = parser.parse_program();
+        assert_eq!(
+            Some(&String::from(
+                "variant 'A' is declared more than once in enum E"
+            )),
+            parser.errors().first()
+        );
+    }
+
+    #[test]
+    fn test_try_expression() {
+        let inputs = [
+            ("f(x)?;", "(f(x)?)"),
+            ("a.b()? + 1;", "(((a.b)()?) + 1)"),
+            ("-x ?;", "(-(x?))"),
+            ("xs[0]?.name;", "(((xs[0])?).name)"),
+            ("r? + 1;", "(r? + 1)"),
+            ("a.b? * 2;", "((a.b?) * 2)"),
+            ("empty?(x);", "empty?(x)"),
+            ("a.empty?();", "(a.empty?)()"),
+        ];
+        for (input, expected) in inputs {
+            let program = test_helper(input);
+            assert_eq!(expected, program.statements[0].to_string());
+        }
+    }
+
+    #[test]
+    fn test_import_and_export() {
+        let program = test_helper(r#"import "lib/math.monkey" as m; export let [a, b] = m.pair;"#);
+        assert_eq!(
+            r#"import "lib/math.monkey" as m;"#,
+            program.statements[0].to_string()
+        );
+        let statement = program.statements[1]
+            .clone()
+            .as_let_statement()
+            .expect("Statement was not a let statement");
+        assert!(statement.is_exported());
+        assert_eq!("export let [a, b] = (m.pair);", statement.to_string());
+
+        let mut parser = Parser::new(Lexer::new("export fn(x) { x };".into()));
+        let _ = parser.parse_program();
+        assert_eq!(
+            Some(&String::from(
+                "expected next token to be Let, but received Function"
+            )),
+            parser.errors().first()
+        );
+    }
+
+    #[test]
+    fn test_type_annotations() {
+        let program = test_helper(
+            "let xs: [int] = [1];\nlet f = fn(a: string, b: int = 1, ...c: [any]) -> fn(int) -> bool { a };",
+        );
+        let statement = program.statements[0]
+            .clone()
+            .as_let_statement()
+            .expect("Statement was not a let statement");
+        assert_eq!("let xs: [int] = [1];", statement.to_string());
+        assert_eq!("1:1", statement.position().to_string());
+        let statement = program.statements[1]
+            .clone()
+            .as_let_statement()
+            .expect("Statement was not a let statement");
+        assert_eq!("2:1", statement.position().to_string());
+        assert_eq!(
+            "let f = fn(a: string, b: int = 1, ...c: [any]) -> fn(int) -> bool { a };",
+            statement.to_string()
+        );
+
+        let mut parser = Parser::new(Lexer::new("let x: 5 = 1;".into()));
+        let _ = parser.parse_program();
+        assert_eq!(
+            Some(&String::from("expected a type, but received 5")),
+            parser.errors().first()
+        );
+    }
 }
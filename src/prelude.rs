@@ -0,0 +1,112 @@
+use crate::ast::Statements;
+use crate::object::{Environment, Object};
+use crate::{evaluator, lexer::Lexer, parser::Parser};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub const PRELUDE: &str = include_str!("prelude.monkey");
+
+thread_local! {
+    // The statements of the prelude, parsed once per thread. Evaluation
+    // consumes function bodies so every environment gets its own copy.
+    static PARSED: RefCell<Option<Rc<Vec<Statements>>>> = const { RefCell::new(None) };
+}
+
+fn parse(source: &str) -> Result<Vec<Statements>, String> {
+    let mut parser = Parser::new(Lexer::new(source.to_string()));
+    match parser.parse_program() {
+        Ok(program) if parser.errors().is_empty() => Ok(program.statements),
+        _ => Err(parser.errors().join("\n")),
+    }
+}
+
+// Replaces the built-in prelude, None leaves root environments with the builtins only
+pub fn set_source(source: Option<&str>) -> Result<(), String> {
+    let statements = match source {
+        Some(source) => parse(source)?,
+        None => Vec::new(),
+    };
+    PARSED.with(|parsed| *parsed.borrow_mut() = Some(Rc::new(statements)));
+    Ok(())
+}
+
+pub fn statements() -> Rc<Vec<Statements>> {
+    PARSED.with(|parsed| {
+        let mut parsed = parsed.borrow_mut();
+        let statements = parsed.get_or_insert_with(|| {
+            Rc::new(parse(PRELUDE).expect("the built-in prelude does not parse"))
+        });
+        Rc::clone(statements)
+    })
+}
+
+// Adds the prelude's bindings to a root environment. Its functions close over
+// a scope of their own, so redefining a builtin later does not change them.
+pub fn load(env: &mut Environment) {
+    let statements = statements();
+    if statements.is_empty() {
+        return;
+    }
+    let scope = Rc::new(RefCell::new(env.clone()));
+    let mut statements = statements.to_vec();
+    if let Some(result) = evaluator::eval_statements(&mut statements, Rc::clone(&scope)) {
+        if result.is_err() {
+            eprintln!("error in prelude: {}", result.inspect());
+        }
+    }
+    let scope = scope.borrow();
+    for (name, value) in scope.bindings() {
+        env.set(name.clone(), value.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::object::Objects;
+
+    fn eval(input: &str) -> String {
+        let env = Rc::new(RefCell::new(Environment::new()));
+        let mut program = Parser::new(Lexer::new(input.into()))
+            .parse_program()
+            .expect("Program did not parse properly");
+        evaluator::eval_program(&mut program, env)
+            .expect("No output")
+            .inspect()
+    }
+
+    #[test]
+    fn test_prelude() {
+        let inputs = [
+            ("map([1, 2, 3], fn(x) { x * 2 })", "[2, 4, 6]"),
+            ("filter(range(0, 10), fn(x) { x % 3 == 0 })", "[0, 3, 6, 9]"),
+            ("reduce(range(1, 5), 0, fn(acc, x) { acc + x })", "10"),
+            (
+                "[range(3, 3), range(5, 2), len(range(0, 5000))]",
+                "[[], [], 5000]",
+            ),
+            ("compose(fn(x) { x + 1 }, fn(x) { x * 10 })(2)", "21"),
+            ("let map = 1; map", "1"),
+            // The prelude keeps its own scope
+            ("let array = 1; compose(fn(x) { x + 1 })(1)", "2"),
+        ];
+        for (input, expected) in inputs {
+            assert_eq!(expected, eval(input), "input: {}", input);
+        }
+        assert!(statements().len() >= 5);
+    }
+
+    #[test]
+    fn test_replaced_prelude() {
+        set_source(Some("let twice = fn(f, x) { f(f(x)) };")).unwrap();
+        assert_eq!("7", eval("twice(fn(x) { x + 2 }, 3)"));
+        assert_eq!("identifier not found: map", eval("map"));
+
+        set_source(None).unwrap();
+        let env = Environment::new();
+        assert!(env.get("twice".into()).is_none());
+        assert!(matches!(env.get("len".into()), Some(Objects::Builtin(_))));
+
+        assert!(set_source(Some("let = 1;")).is_err());
+    }
+}
@@ -1,12 +1,53 @@
+use crate::infer::{self, Inferer};
 use crate::object::Object;
+use crate::sandbox::Limits;
+use crate::typecheck::{self, Checker, TypeError};
 use crate::{evaluator, lexer::Lexer, object::Environment, parser::Parser};
 use std::cell::RefCell;
 use std::io;
+use std::path::Path;
 use std::rc::Rc;
 
-pub fn start() -> Result<(), Box<dyn std::error::Error>> {
+// Evaluates a script, its imports are resolved relative to the script itself. In strict
+// mode every type is inferred instead of only checking the annotated ones.
+pub fn run_file(
+    path: &str,
+    strict: bool,
+    limits: Limits,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let source = std::fs::read_to_string(path)?;
+    let mut env = Environment::with_limits(limits);
+    if let Some(dir) = Path::new(path).parent() {
+        env.set_base_dir(dir.to_path_buf());
+    }
+    let env = Rc::new(RefCell::new(env));
+    let mut parser = Parser::new(Lexer::new(source));
+    let mut program = match parser.parse_program() {
+        Ok(program) if parser.errors().is_empty() => program,
+        _ => return Err(parser.errors().join("\n").into()),
+    };
+    let checked = match strict {
+        true => infer::infer(&program),
+        false => typecheck::typecheck(&program),
+    };
+    if let Err(errors) = checked {
+        let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        return Err(errors.join("\n").into());
+    }
+    if let Some(evaluated) = evaluator::eval_program(&mut program, env) {
+        if evaluated.is_err() {
+            return Err(evaluated.inspect().into());
+        }
+    }
+    Ok(())
+}
+
+// `:type EXPR` prints the inferred type of an expression instead of evaluating it
+pub fn start(strict: bool, limits: Limits) -> Result<(), Box<dyn std::error::Error>> {
     let prompt = ">> ";
-    let env = Rc::new(RefCell::new(Environment::new()));
+    let env = Rc::new(RefCell::new(Environment::with_limits(limits)));
+    let mut checker = Checker::new();
+    let mut inferer = Inferer::new();
     loop {
         print!("{}", prompt);
         io::Write::flush(&mut io::stdout())?;
@@ -16,19 +57,47 @@ pub fn start() -> Result<(), Box<dyn std::error::Error>> {
                     "quit" | "exit" => std::process::exit(0),
                     _ => {}
                 }
+                if let Some(source) = line.strip_prefix(":type ") {
+                    let mut parser = Parser::new(Lexer::new(source.to_string()));
+                    match parser.parse_program() {
+                        Ok(program) if parser.errors().is_empty() => {
+                            match inferer.type_of(&program) {
+                                Ok(ty) => println!("{}", ty),
+                                Err(errors) => print_errors(&errors),
+                            }
+                        }
+                        _ => {
+                            for e in parser.errors() {
+                                println!("{}", e);
+                            }
+                        }
+                    }
+                    break;
+                }
                 let lex = Lexer::new(line);
                 let mut parser = Parser::new(lex);
 
                 let mut program = parser.parse_program();
 
                 match &mut program {
-                    Ok(program) => {
+                    Ok(program) if parser.errors().is_empty() => {
+                        let checked = match strict {
+                            true => inferer.check_program(program),
+                            false => checker.check_program(program).map(|()| {
+                                // Inference still runs so that `:type` knows the new bindings
+                                let _ = inferer.check_program(program);
+                            }),
+                        };
+                        if let Err(errors) = checked {
+                            print_errors(&errors);
+                            break;
+                        }
                         let evaluated = evaluator::eval_program(program, Rc::clone(&env));
                         if let Some(evaluated) = evaluated {
                             println!("{}", evaluated.inspect());
                         }
                     }
-                    Err(_) => {
+                    _ => {
                         for e in parser.errors() {
                             println!("{}", e);
                         }
@@ -39,3 +108,9 @@ pub fn start() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 }
+
+fn print_errors(errors: &[TypeError]) {
+    for e in errors {
+        println!("{}", e);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::object::{ErrorObject, Limit};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Every script call costs several evaluator frames, hosts running deep recursion
+// should evaluate on a thread with a large stack (see main)
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1000;
+
+// Native stack a program may use, safe on the 2MB stack of a spawned thread
+pub const DEFAULT_MAX_STACK: usize = 1536 * 1024;
+
+// No single string or array may grow past this, whatever the limits
+pub const MAX_ALLOCATION_BYTES: usize = 1 << 30;
+
+// How often a sleeping builtin looks at the timeout and cancellation
+const SLEEP_SLICE: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub max_steps: Option<usize>,
+    pub max_call_depth: Option<usize>,
+    pub max_stack: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub max_allocations: Option<usize>,
+}
+
+impl Limits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_steps: None,
+            max_call_depth: None,
+            max_stack: None,
+            timeout: None,
+            max_allocations: None,
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_call_depth: Some(DEFAULT_MAX_CALL_DEPTH),
+            max_stack: Some(DEFAULT_MAX_STACK),
+            ..Limits::unlimited()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+// Host API, the binary itself never cancels. A cancel stays in effect until it
+// is reset, so one made before a program starts still stops that program.
+impl CancelHandle {
+    #[allow(dead_code)]
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    #[allow(dead_code)]
+    pub fn reset(&self) {
+        self.cancelled.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+// Bookkeeping shared by every environment of one interpreter. The counters are
+// reset each time a program starts evaluating.
+#[derive(Debug)]
+pub struct Sandbox {
+    limits: Limits,
+    steps: Cell<usize>,
+    depth: Cell<usize>,
+    stack_base: Cell<usize>,
+    allocations: Cell<usize>,
+    started: Cell<Instant>,
+    cancel: CancelHandle,
+}
+
+impl Sandbox {
+    pub fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            steps: Cell::new(0),
+            depth: Cell::new(0),
+            stack_base: Cell::new(stack_address()),
+            allocations: Cell::new(0),
+            started: Cell::new(Instant::now()),
+            cancel: CancelHandle::default(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    // Leaves the cancel flag alone, see CancelHandle
+    pub fn start(&self) {
+        self.steps.set(0);
+        self.depth.set(0);
+        self.stack_base.set(stack_address());
+        self.allocations.set(0);
+        self.started.set(Instant::now());
+    }
+
+    // Every evaluated expression is a step, deep expressions use up the stack
+    // without any call
+    pub fn step(&self) -> Result<(), ErrorObject> {
+        self.check_cancelled()?;
+        self.check_stack()?;
+        let steps = self.steps.get() + 1;
+        self.steps.set(steps);
+        if let Some(max) = self.limits.max_steps {
+            if steps > max {
+                return Err(ErrorObject::limit_exceeded(
+                    Limit::Steps,
+                    format!("step limit exceeded: {} steps", max),
+                ));
+            }
+        }
+        self.check_timeout()
+    }
+
+    // Sleeps in short slices so a timeout or cancellation ends the wait early
+    pub fn sleep(&self, duration: Duration) -> Result<(), ErrorObject> {
+        let wake = Instant::now().checked_add(duration);
+        loop {
+            self.check_cancelled()?;
+            self.check_timeout()?;
+            let left = match wake {
+                Some(wake) => wake.saturating_duration_since(Instant::now()),
+                None => SLEEP_SLICE,
+            };
+            if left.is_zero() {
+                return Ok(());
+            }
+            std::thread::sleep(left.min(SLEEP_SLICE));
+        }
+    }
+
+    fn check_cancelled(&self) -> Result<(), ErrorObject> {
+        if self.cancel.is_cancelled() {
+            return Err(ErrorObject::limit_exceeded(
+                Limit::Cancelled,
+                String::from("evaluation cancelled"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_timeout(&self) -> Result<(), ErrorObject> {
+        if let Some(timeout) = self.limits.timeout {
+            if self.started.get().elapsed() > timeout {
+                return Err(ErrorObject::limit_exceeded(
+                    Limit::Timeout,
+                    format!("evaluation timed out after {:?}", timeout),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_stack(&self) -> Result<(), ErrorObject> {
+        if let Some(max) = self.limits.max_stack {
+            if self.stack_base.get().abs_diff(stack_address()) > max {
+                return Err(ErrorObject::limit_exceeded(
+                    Limit::Stack,
+                    format!("stack limit exceeded: {} bytes", max),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn enter_call(&self) -> Result<(), ErrorObject> {
+        self.check_stack()?;
+        let depth = self.depth.get() + 1;
+        if let Some(max) = self.limits.max_call_depth {
+            if depth > max {
+                return Err(ErrorObject::limit_exceeded(
+                    Limit::CallDepth,
+                    format!("maximum call depth exceeded: {}", max),
+                ));
+            }
+        }
+        self.depth.set(depth);
+        Ok(())
+    }
+
+    pub fn exit_call(&self) {
+        self.depth.set(self.depth.get().saturating_sub(1));
+    }
+
+    pub fn allocate(&self) -> Result<(), ErrorObject> {
+        let allocations = self.allocations.get() + 1;
+        self.allocations.set(allocations);
+        if let Some(max) = self.limits.max_allocations {
+            if allocations > max {
+                return Err(ErrorObject::limit_exceeded(
+                    Limit::Allocations,
+                    format!("allocation limit exceeded: {} objects", max),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Checks the size of a single allocation before a builtin makes it, None
+// meaning the size computation overflowed
+pub fn reserve(bytes: Option<usize>) -> Result<(), ErrorObject> {
+    match bytes {
+        Some(bytes) if bytes <= MAX_ALLOCATION_BYTES => Ok(()),
+        _ => Err(ErrorObject::limit_exceeded(
+            Limit::Allocations,
+            format!("allocation too large: over {} bytes", MAX_ALLOCATION_BYTES),
+        )),
+    }
+}
+
+// Address of a local in the caller's frame, how far the stack has grown is
+// the distance between two of them
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
+}
+
+impl Default for Sandbox {
+    fn default() -> Self {
+        Sandbox::new(Limits::default())
+    }
+}
@@ -0,0 +1,187 @@
+use super::{expect_args, function, unsupported};
+use crate::bigint::BigInt;
+use crate::evaluator::is_equal;
+use crate::object::{Array, Boolean, ErrorObject, Null, Object, Objects};
+use std::cmp::Ordering;
+
+// Arrays are values, every function returns a new array
+pub fn members() -> Vec<(&'static str, Objects)> {
+    vec![
+        ("first", function(first)),
+        ("last", function(last)),
+        ("rest", function(rest)),
+        ("push", function(push)),
+        ("concat", function(concat)),
+        ("reverse", function(reverse)),
+        ("slice", function(slice)),
+        ("sort", function(sort)),
+        ("contains", function(contains)),
+        ("join", function(super::string::join)),
+    ]
+}
+
+fn elements<'a>(name: &str, args: &'a [Objects], count: usize) -> Result<&'a [Objects], Objects> {
+    expect_args(name, args, count)?;
+    match &args[0] {
+        Objects::Array(a) => Ok(a.elements()),
+        arg => Err(unsupported(name, arg)),
+    }
+}
+
+fn array(elements: Vec<Objects>) -> Objects {
+    Objects::Array(Array::new(elements))
+}
+
+fn first(args: &[Objects]) -> Objects {
+    match elements("first", args, 1) {
+        Ok(elements) => elements.first().cloned().unwrap_or(Objects::Null(Null {})),
+        Err(err) => err,
+    }
+}
+
+fn last(args: &[Objects]) -> Objects {
+    match elements("last", args, 1) {
+        Ok(elements) => elements.last().cloned().unwrap_or(Objects::Null(Null {})),
+        Err(err) => err,
+    }
+}
+
+fn rest(args: &[Objects]) -> Objects {
+    match elements("rest", args, 1) {
+        Ok([]) => Objects::Null(Null {}),
+        Ok(elements) => array(elements[1..].to_vec()),
+        Err(err) => err,
+    }
+}
+
+fn push(args: &[Objects]) -> Objects {
+    match elements("push", args, 2) {
+        Ok(elements) => {
+            let mut elements = elements.to_vec();
+            elements.push(args[1].clone());
+            array(elements)
+        }
+        Err(err) => err,
+    }
+}
+
+fn concat(args: &[Objects]) -> Objects {
+    let left = match elements("concat", args, 2) {
+        Ok(elements) => elements,
+        Err(err) => return err,
+    };
+    match &args[1] {
+        Objects::Array(right) => array(left.iter().chain(right.elements()).cloned().collect()),
+        arg => unsupported("concat", arg),
+    }
+}
+
+fn reverse(args: &[Objects]) -> Objects {
+    match elements("reverse", args, 1) {
+        Ok(elements) => array(elements.iter().rev().cloned().collect()),
+        Err(err) => err,
+    }
+}
+
+// `slice(xs, start, end)` with the end excluded and clamped to the length
+fn slice(args: &[Objects]) -> Objects {
+    let elements = match elements("slice", args, 3) {
+        Ok(elements) => elements,
+        Err(err) => return err,
+    };
+    let mut bounds = [0; 2];
+    for (bound, arg) in bounds.iter_mut().zip(&args[1..]) {
+        match arg {
+            Objects::Integer(i) => *bound = (*i.value()).clamp(0, elements.len() as isize) as usize,
+            _ => return unsupported("slice", arg),
+        }
+    }
+    let [start, end] = bounds;
+    array(elements[start.min(end)..end].to_vec())
+}
+
+// Sorts numbers or strings, other elements cannot be compared
+fn sort(args: &[Objects]) -> Objects {
+    let elements = match elements("sort", args, 1) {
+        Ok(elements) => elements,
+        Err(err) => return err,
+    };
+    // sort_by needs a total order, so the elements are checked up front
+    if let [first, _, ..] = elements {
+        let unordered = elements
+            .iter()
+            .find(|element| kind(element).is_none() || kind(element) != kind(first));
+        if let Some(other) = unordered {
+            return Objects::Error(ErrorObject::new(format!(
+                "cannot compare {} with {}",
+                other.obj_type(),
+                first.obj_type()
+            )));
+        }
+    }
+    let mut sorted = elements.to_vec();
+    sorted.sort_by(compare);
+    array(sorted)
+}
+
+fn contains(args: &[Objects]) -> Objects {
+    match elements("contains", args, 2) {
+        Ok(elements) => Objects::Boolean(Boolean::new(
+            elements.iter().any(|element| is_equal(element, &args[1])),
+        )),
+        Err(err) => err,
+    }
+}
+
+// Elements of different kinds have no order between them
+fn kind(obj: &Objects) -> Option<&'static str> {
+    match obj {
+        Objects::Integer(_) | Objects::BigInteger(_) | Objects::Float(_) => Some("number"),
+        Objects::String(_) => Some("string"),
+        Objects::Char(_) => Some("char"),
+        _ => None,
+    }
+}
+
+// Integers and floats are compared exactly, NaN goes after every number or
+// before them when its sign is negative
+fn compare(a: &Objects, b: &Objects) -> Ordering {
+    match (a, b) {
+        (Objects::Integer(a), Objects::Integer(b)) => a.value().cmp(b.value()),
+        (Objects::String(a), Objects::String(b)) => a.value().cmp(b.value()),
+        (Objects::Char(a), Objects::Char(b)) => a.value().cmp(&b.value()),
+        (Objects::Float(a), Objects::Float(b)) => compare_floats(*a.value(), *b.value()),
+        (Objects::Float(a), b) => compare_mixed(&integer(b), *a.value()).reverse(),
+        (a, Objects::Float(b)) => compare_mixed(&integer(a), *b.value()),
+        (a, b) => integer(a).cmp(&integer(b)),
+    }
+}
+
+fn compare_floats(a: f64, b: f64) -> Ordering {
+    match a.partial_cmp(&b) {
+        Some(ordering) => ordering,
+        None => a.total_cmp(&b),
+    }
+}
+
+fn compare_mixed(integer: &BigInt, float: f64) -> Ordering {
+    if float.is_nan() {
+        return compare_floats(0.0, float);
+    }
+    match BigInt::from_f64(float) {
+        Some(whole) => integer
+            .cmp(&whole)
+            .then_with(|| compare_floats(0.0, float - float.trunc())),
+        None if float > 0.0 => Ordering::Less,
+        None => Ordering::Greater,
+    }
+}
+
+// Only called once kind() made sure the object is a number
+fn integer(obj: &Objects) -> BigInt {
+    match obj {
+        Objects::Integer(i) => BigInt::from_isize(*i.value()),
+        Objects::BigInteger(i) => i.value().clone(),
+        _ => BigInt::zero(),
+    }
+}
@@ -0,0 +1,47 @@
+use super::{expect_args, function};
+use crate::object::{ErrorObject, Null, Object, Objects, StringObject};
+use std::io::Write;
+
+pub fn members() -> Vec<(&'static str, Objects)> {
+    vec![
+        ("print", function(print)),
+        ("write", function(write)),
+        ("read_line", function(read_line)),
+    ]
+}
+
+fn line(args: &[Objects]) -> String {
+    let parts: Vec<String> = args.iter().map(|arg| arg.inspect()).collect();
+    parts.join(" ")
+}
+
+// Prints the arguments separated by spaces and ends the line
+pub fn print(args: &[Objects]) -> Objects {
+    println!("{}", line(args));
+    Objects::Null(Null {})
+}
+
+fn write(args: &[Objects]) -> Objects {
+    let mut stdout = std::io::stdout();
+    match write!(stdout, "{}", line(args)).and_then(|_| stdout.flush()) {
+        Ok(()) => Objects::Null(Null {}),
+        Err(err) => Objects::Error(ErrorObject::new(format!("cannot write: {}", err))),
+    }
+}
+
+// The next line of stdin without its line break, null at the end of input
+fn read_line(args: &[Objects]) -> Objects {
+    if let Err(err) = expect_args("read_line", args, 0) {
+        return err;
+    }
+    let mut line = String::new();
+    match std::io::stdin().read_line(&mut line) {
+        Ok(0) => Objects::Null(Null {}),
+        Ok(_) => {
+            let trimmed = line.trim_end_matches(['\n', '\r']).len();
+            line.truncate(trimmed);
+            Objects::String(StringObject::new(line))
+        }
+        Err(err) => Objects::Error(ErrorObject::new(format!("cannot read: {}", err))),
+    }
+}
@@ -0,0 +1,280 @@
+use super::{expect_args, function, unsupported};
+use crate::bigint::BigInt;
+use crate::object::{
+    Array, BigInteger, Boolean, ErrorObject, Float, Hash, Null, Object, Objects, StringObject,
+};
+
+pub fn members() -> Vec<(&'static str, Objects)> {
+    vec![
+        ("stringify", function(stringify)),
+        ("parse", function(parse)),
+    ]
+}
+
+fn stringify(args: &[Objects]) -> Objects {
+    if let Err(err) = expect_args("stringify", args, 1) {
+        return err;
+    }
+    let mut out = String::new();
+    match write_value(&args[0], &mut out) {
+        Ok(()) => Objects::String(StringObject::new(out)),
+        Err(err) => err,
+    }
+}
+
+fn write_value(value: &Objects, out: &mut String) -> Result<(), Objects> {
+    match value {
+        Objects::Null(_) => out.push_str("null"),
+        Objects::Boolean(_) | Objects::Integer(_) | Objects::BigInteger(_) => {
+            out.push_str(&value.inspect())
+        }
+        Objects::Float(f) if f.value().is_finite() => out.push_str(&value.inspect()),
+        Objects::String(_) | Objects::Char(_) => write_string(&value.inspect(), out),
+        Objects::Array(a) => {
+            out.push('[');
+            for (i, element) in a.elements().iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(element, out)?;
+            }
+            out.push(']');
+        }
+        // Keys become strings, as JSON has no other kind
+        Objects::Hash(h) => {
+            out.push('{');
+            for (i, (key, value)) in h.pairs().iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(&key.inspect(), out);
+                out.push(':');
+                write_value(value, out)?;
+            }
+            out.push('}');
+        }
+        _ => {
+            return Err(Objects::Error(ErrorObject::new(format!(
+                "cannot convert {} to JSON",
+                value.obj_type()
+            ))))
+        }
+    }
+    Ok(())
+}
+
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn parse(args: &[Objects]) -> Objects {
+    if let Err(err) = expect_args("parse", args, 1) {
+        return err;
+    }
+    let source = match &args[0] {
+        Objects::String(s) => s.value(),
+        arg => return unsupported("parse", arg),
+    };
+    let mut reader = Reader {
+        chars: source.chars().collect(),
+        position: 0,
+    };
+    let value = reader.value().and_then(|value| {
+        reader.skip_whitespace();
+        match reader.peek() {
+            None => Ok(value),
+            Some(_) => Err(reader.error("unexpected trailing characters")),
+        }
+    });
+    match value {
+        Ok(value) => value,
+        Err(message) => Objects::Error(ErrorObject::new(message)),
+    }
+}
+
+struct Reader {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("invalid JSON at position {}: {}", self.position, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, word: &str) -> Result<(), String> {
+        for expected in word.chars() {
+            if self.next() != Some(expected) {
+                return Err(self.error(&format!("expected '{}'", word)));
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Objects, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('n') => self.expect("null").map(|_| Objects::Null(Null {})),
+            Some('t') => self
+                .expect("true")
+                .map(|_| Objects::Boolean(Boolean::new(true))),
+            Some('f') => self
+                .expect("false")
+                .map(|_| Objects::Boolean(Boolean::new(false))),
+            Some('"') => self.string().map(|s| Objects::String(StringObject::new(s))),
+            Some('[') => self.array(),
+            Some('{') => self.object(),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            Some(c) => Err(self.error(&format!("unexpected character '{}'", c))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.next();
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => {
+                    let escaped = match self.next() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('u') => self.unicode_escape()?,
+                        Some(c @ ('"' | '\\' | '/')) => c,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    value.push(escaped);
+                }
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let mut code = self.hex_digits()?;
+        // Characters outside the basic plane are written as a surrogate pair
+        let paired = self.chars.get(self.position..self.position + 2) == Some(&['\\', 'u'][..]);
+        if (0xD800..0xDC00).contains(&code) && paired {
+            self.position += 2;
+            let low = self.hex_digits()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("invalid unicode escape"));
+            }
+            code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+        }
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex_digits(&mut self) -> Result<u32, String> {
+        let end = self.position + 4;
+        let digits: String = self
+            .chars
+            .get(self.position..end)
+            .unwrap_or(&[])
+            .iter()
+            .collect();
+        self.position = end;
+        u32::from_str_radix(&digits, 16).map_err(|_| self.error("invalid unicode escape"))
+    }
+
+    fn number(&mut self) -> Result<Objects, String> {
+        let start = self.position;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        {
+            self.position += 1;
+        }
+        let text: String = self.chars[start..self.position].iter().collect();
+        if !text.contains(['.', 'e', 'E']) {
+            if let Some(value) = BigInt::parse(&text) {
+                return Ok(BigInteger::normalize(value));
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(value) => Ok(Objects::Float(Float::new(value))),
+            Err(_) => Err(self.error(&format!("invalid number '{}'", text))),
+        }
+    }
+
+    fn array(&mut self) -> Result<Objects, String> {
+        self.next();
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Ok(Objects::Array(Array::new(elements)));
+        }
+        loop {
+            elements.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Objects::Array(Array::new(elements))),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Objects, String> {
+        self.next();
+        let mut hash = Hash::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Ok(Objects::Hash(hash));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a string key"));
+            }
+            let key = Objects::String(StringObject::new(self.string()?));
+            self.skip_whitespace();
+            if self.next() != Some(':') {
+                return Err(self.error("expected ':'"));
+            }
+            let value = self.value()?;
+            let _ = hash.insert(key, value);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Objects::Hash(hash)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use super::{expect_args, function, unsupported};
+use crate::bigint::BigInt;
+use crate::object::{BigInteger, ErrorObject, Float, Integer, Objects};
+
+pub fn members() -> Vec<(&'static str, Objects)> {
+    vec![
+        ("pi", Objects::Float(Float::new(std::f64::consts::PI))),
+        ("e", Objects::Float(Float::new(std::f64::consts::E))),
+        ("sqrt", function(sqrt)),
+        ("pow", function(pow)),
+        ("abs", function(abs)),
+        ("floor", function(floor)),
+        ("ceil", function(ceil)),
+        ("round", function(round)),
+        ("min", function(min)),
+        ("max", function(max)),
+    ]
+}
+
+fn number(name: &str, arg: &Objects) -> Result<f64, Objects> {
+    match arg {
+        Objects::Integer(i) => Ok(*i.value() as f64),
+        Objects::BigInteger(i) => Ok(i.value().to_f64()),
+        Objects::Float(f) => Ok(*f.value()),
+        _ => Err(unsupported(name, arg)),
+    }
+}
+
+fn float_fn(name: &str, args: &[Objects], count: usize, f: fn(&[f64]) -> f64) -> Objects {
+    if let Err(err) = expect_args(name, args, count) {
+        return err;
+    }
+    let mut values = Vec::new();
+    for arg in args {
+        match number(name, arg) {
+            Ok(value) => values.push(value),
+            Err(err) => return err,
+        }
+    }
+    Objects::Float(Float::new(f(&values)))
+}
+
+// Rounds with `f` and keeps integers as they are
+fn integer_fn(name: &str, args: &[Objects], f: fn(f64) -> f64) -> Objects {
+    if let Err(err) = expect_args(name, args, 1) {
+        return err;
+    }
+    let value = match &args[0] {
+        Objects::Integer(_) | Objects::BigInteger(_) => return args[0].clone(),
+        Objects::Float(value) => f(*value.value()),
+        arg => return unsupported(name, arg),
+    };
+    match BigInt::from_f64(value) {
+        Some(value) => BigInteger::normalize(value),
+        None => Objects::Error(ErrorObject::new(format!(
+            "cannot convert {:?} to INTEGER",
+            value
+        ))),
+    }
+}
+
+fn sqrt(args: &[Objects]) -> Objects {
+    float_fn("sqrt", args, 1, |v| v[0].sqrt())
+}
+
+fn pow(args: &[Objects]) -> Objects {
+    float_fn("pow", args, 2, |v| v[0].powf(v[1]))
+}
+
+fn abs(args: &[Objects]) -> Objects {
+    if let Err(err) = expect_args("abs", args, 1) {
+        return err;
+    }
+    match &args[0] {
+        Objects::Integer(i) => match i.value().checked_abs() {
+            Some(value) => Objects::Integer(Integer::new(value)),
+            None => BigInteger::normalize(-&BigInt::from_isize(*i.value())),
+        },
+        Objects::BigInteger(i) if i.value().is_negative() => BigInteger::normalize(-i.value()),
+        Objects::BigInteger(_) => args[0].clone(),
+        Objects::Float(f) => Objects::Float(Float::new(f.value().abs())),
+        arg => unsupported("abs", arg),
+    }
+}
+
+fn floor(args: &[Objects]) -> Objects {
+    integer_fn("floor", args, f64::floor)
+}
+
+fn ceil(args: &[Objects]) -> Objects {
+    integer_fn("ceil", args, f64::ceil)
+}
+
+fn round(args: &[Objects]) -> Objects {
+    integer_fn("round", args, f64::round)
+}
+
+// Returns the argument itself so integers stay integers
+fn extreme(name: &str, args: &[Objects], pick_right: fn(f64, f64) -> bool) -> Objects {
+    if args.is_empty() {
+        return Objects::Error(ErrorObject::new(format!(
+            "{} expects at least 1 argument(s) but received 0",
+            name
+        )));
+    }
+    let mut best = (&args[0], 0.0);
+    for (i, arg) in args.iter().enumerate() {
+        let value = match number(name, arg) {
+            Ok(value) => value,
+            Err(err) => return err,
+        };
+        if i == 0 || pick_right(best.1, value) {
+            best = (arg, value);
+        }
+    }
+    best.0.clone()
+}
+
+fn min(args: &[Objects]) -> Objects {
+    extreme("min", args, |best, value| value < best)
+}
+
+fn max(args: &[Objects]) -> Objects {
+    extreme("max", args, |best, value| value > best)
+}
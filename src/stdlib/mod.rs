@@ -0,0 +1,76 @@
+use crate::object::{BuiltinFunction, BuiltinWrapper, ErrorObject, Module, Object, Objects};
+use crate::sandbox::Sandbox;
+use std::rc::Rc;
+
+mod array;
+mod io;
+mod json;
+mod math;
+mod string;
+mod time;
+
+pub use io::print;
+
+// Every root environment binds these names, `import "math" as m;` works as well
+pub const MODULES: [&str; 6] = ["math", "string", "array", "io", "time", "json"];
+
+pub fn module(name: &str) -> Option<Module> {
+    let members = match name {
+        "math" => math::members(),
+        "string" => string::members(),
+        "array" => array::members(),
+        "io" => io::members(),
+        "time" => time::members(),
+        "json" => json::members(),
+        _ => return None,
+    };
+    let members = members
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    Some(Module::new(name, members))
+}
+
+// Strings and arrays have the functions of their module as methods,
+// `"a".upper()` is `string.upper("a")`
+pub fn method(receiver: &Objects, name: &str) -> Option<Objects> {
+    let members = match receiver {
+        Objects::String(_) => string::members(),
+        Objects::Array(_) => array::members(),
+        _ => return None,
+    };
+    members
+        .into_iter()
+        .find(|(member, _)| *member == name)
+        .map(|(_, value)| value)
+}
+
+fn function(func: fn(&[Objects]) -> Objects) -> Objects {
+    Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Default(func))))
+}
+
+fn sandboxed(func: fn(&[Objects], &Sandbox) -> Objects) -> Objects {
+    Objects::Builtin(Rc::new(BuiltinWrapper::new(BuiltinFunction::Sandboxed(
+        func,
+    ))))
+}
+
+fn expect_args(name: &str, args: &[Objects], count: usize) -> Result<(), Objects> {
+    if args.len() == count {
+        return Ok(());
+    }
+    Err(Objects::Error(ErrorObject::new(format!(
+        "{} expects {} argument(s) but received {}",
+        name,
+        count,
+        args.len()
+    ))))
+}
+
+fn unsupported(name: &str, arg: &Objects) -> Objects {
+    Objects::Error(ErrorObject::new(format!(
+        "argument to '{}' not supported, got {}",
+        name,
+        arg.obj_type()
+    )))
+}
@@ -0,0 +1,129 @@
+use super::{expect_args, function, unsupported};
+use crate::builtins::BuiltinFunctions;
+use crate::object::{Array, Boolean, Object, Objects, StringObject};
+use crate::sandbox;
+
+pub fn members() -> Vec<(&'static str, Objects)> {
+    vec![
+        ("upper", function(upper)),
+        ("lower", function(lower)),
+        ("trim", function(trim)),
+        ("split", function(split)),
+        ("join", function(join)),
+        ("contains", function(contains)),
+        ("starts_with", function(starts_with)),
+        ("ends_with", function(ends_with)),
+        ("replace", function(replace)),
+        ("repeat", function(repeat)),
+        ("chars", function(BuiltinFunctions::chars)),
+        ("ord", function(BuiltinFunctions::ord)),
+        ("chr", function(BuiltinFunctions::chr)),
+    ]
+}
+
+// The string arguments of a call, checking their number and types
+fn strings<'a>(name: &str, args: &'a [Objects], count: usize) -> Result<Vec<&'a str>, Objects> {
+    expect_args(name, args, count)?;
+    args.iter()
+        .map(|arg| match arg {
+            Objects::String(s) => Ok(s.value()),
+            _ => Err(unsupported(name, arg)),
+        })
+        .collect()
+}
+
+fn string(value: String) -> Objects {
+    Objects::String(StringObject::new(value))
+}
+
+fn upper(args: &[Objects]) -> Objects {
+    match strings("upper", args, 1) {
+        Ok(s) => string(s[0].to_uppercase()),
+        Err(err) => err,
+    }
+}
+
+fn lower(args: &[Objects]) -> Objects {
+    match strings("lower", args, 1) {
+        Ok(s) => string(s[0].to_lowercase()),
+        Err(err) => err,
+    }
+}
+
+fn trim(args: &[Objects]) -> Objects {
+    match strings("trim", args, 1) {
+        Ok(s) => string(s[0].trim().to_string()),
+        Err(err) => err,
+    }
+}
+
+fn split(args: &[Objects]) -> Objects {
+    match strings("split", args, 2) {
+        Ok(s) if s[1].is_empty() => Objects::Array(Array::new(
+            s[0].chars().map(|c| string(c.to_string())).collect(),
+        )),
+        Ok(s) => Objects::Array(Array::new(
+            s[0].split(s[1])
+                .map(|part| string(part.to_string()))
+                .collect(),
+        )),
+        Err(err) => err,
+    }
+}
+
+// Shared with the array module so `[1, 2].join(", ")` works as well
+pub(super) fn join(args: &[Objects]) -> Objects {
+    if let Err(err) = expect_args("join", args, 2) {
+        return err;
+    }
+    let (elements, separator) = match (&args[0], &args[1]) {
+        (Objects::Array(a), Objects::String(s)) => (a.elements(), s.value()),
+        (Objects::Array(_), arg) | (arg, _) => return unsupported("join", arg),
+    };
+    let parts: Vec<String> = elements.iter().map(|element| element.inspect()).collect();
+    string(parts.join(separator))
+}
+
+fn contains(args: &[Objects]) -> Objects {
+    match strings("contains", args, 2) {
+        Ok(s) => Objects::Boolean(Boolean::new(s[0].contains(s[1]))),
+        Err(err) => err,
+    }
+}
+
+fn starts_with(args: &[Objects]) -> Objects {
+    match strings("starts_with", args, 2) {
+        Ok(s) => Objects::Boolean(Boolean::new(s[0].starts_with(s[1]))),
+        Err(err) => err,
+    }
+}
+
+fn ends_with(args: &[Objects]) -> Objects {
+    match strings("ends_with", args, 2) {
+        Ok(s) => Objects::Boolean(Boolean::new(s[0].ends_with(s[1]))),
+        Err(err) => err,
+    }
+}
+
+fn replace(args: &[Objects]) -> Objects {
+    match strings("replace", args, 3) {
+        Ok(s) => string(s[0].replace(s[1], s[2])),
+        Err(err) => err,
+    }
+}
+
+fn repeat(args: &[Objects]) -> Objects {
+    if let Err(err) = expect_args("repeat", args, 2) {
+        return err;
+    }
+    match (&args[0], &args[1]) {
+        (Objects::String(s), Objects::Integer(n)) if *n.value() >= 0 => {
+            let count = *n.value() as usize;
+            match sandbox::reserve(s.value().len().checked_mul(count)) {
+                Ok(()) => string(s.value().repeat(count)),
+                Err(err) => Objects::Error(err),
+            }
+        }
+        (Objects::String(_), arg) | (arg, _) => unsupported("repeat", arg),
+    }
+}
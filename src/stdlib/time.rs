@@ -0,0 +1,34 @@
+use super::{expect_args, function, sandboxed, unsupported};
+use crate::object::{ErrorObject, Integer, Null, Objects};
+use crate::sandbox::Sandbox;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub fn members() -> Vec<(&'static str, Objects)> {
+    vec![("now", function(now)), ("sleep", sandboxed(sleep))]
+}
+
+// Milliseconds since the Unix epoch
+fn now(args: &[Objects]) -> Objects {
+    if let Err(err) = expect_args("now", args, 0) {
+        return err;
+    }
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => Objects::Integer(Integer::new(elapsed.as_millis() as isize)),
+        Err(err) => Objects::Error(ErrorObject::new(format!("cannot read the clock: {}", err))),
+    }
+}
+
+fn sleep(args: &[Objects], sandbox: &Sandbox) -> Objects {
+    if let Err(err) = expect_args("sleep", args, 1) {
+        return err;
+    }
+    match &args[0] {
+        Objects::Integer(ms) if *ms.value() >= 0 => {
+            match sandbox.sleep(Duration::from_millis(*ms.value() as u64)) {
+                Ok(()) => Objects::Null(Null {}),
+                Err(err) => Objects::Error(err),
+            }
+        }
+        arg => unsupported("sleep", arg),
+    }
+}
@@ -0,0 +1,706 @@
+use crate::ast::{
+    BlockStatement, CallExpression, Expressions, FunctionLiteral, IfExpression, InterpolationPart,
+    Pattern, Program, Statements, TypeAnnotation,
+};
+use crate::lexer::Position;
+use crate::object::ObjectTypes;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+// What the checker knows about a value. Unannotated code is Dynamic and
+// never causes an error, annotations and literals narrow it down.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Dynamic,
+    Integer,
+    Float,
+    Boolean,
+    String,
+    Char,
+    Null,
+    Hash,
+    Array(Box<Type>),
+    // The parameter types are None when defaults or a rest parameter make
+    // the number of arguments flexible
+    Function(Option<Vec<Type>>, Box<Type>),
+    // A struct or an enum
+    Named(String),
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Dynamic => write!(f, "ANY"),
+            Type::Integer => write!(f, "{}", ObjectTypes::Integer),
+            Type::Float => write!(f, "{}", ObjectTypes::Float),
+            Type::Boolean => write!(f, "{}", ObjectTypes::Boolean),
+            Type::String => write!(f, "{}", ObjectTypes::String),
+            Type::Char => write!(f, "{}", ObjectTypes::Char),
+            Type::Null => write!(f, "{}", ObjectTypes::Null),
+            Type::Hash => write!(f, "{}", ObjectTypes::Hash),
+            Type::Array(element) if **element == Type::Dynamic => {
+                write!(f, "{}", ObjectTypes::Array)
+            }
+            Type::Array(element) => write!(f, "[{}]", element),
+            Type::Function(None, result) if **result == Type::Dynamic => {
+                write!(f, "{}", ObjectTypes::Function)
+            }
+            Type::Function(None, result) => write!(f, "fn(...) -> {}", result),
+            Type::Function(Some(parameters), result) => {
+                let parameters: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
+                write!(f, "fn({}) -> {}", parameters.join(", "), result)
+            }
+            Type::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeError {
+    position: Position,
+    message: String,
+}
+
+impl TypeError {
+    pub fn new(position: Position, message: String) -> Self {
+        Self { position, message }
+    }
+}
+
+impl Display for TypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.position, self.message)
+    }
+}
+
+// Checks a whole program before it is evaluated
+pub fn typecheck(program: &Program) -> Result<(), Vec<TypeError>> {
+    Checker::new().check_program(program)
+}
+
+// Keeps what earlier programs declared, so the REPL can check line by line
+pub struct Checker {
+    scopes: Vec<HashMap<String, Type>>,
+    // Structs and enums declared so far, annotations may name them
+    types: HashSet<String>,
+    // The declared result of every function being checked, innermost last
+    returns: Vec<Type>,
+    errors: Vec<TypeError>,
+}
+
+impl Checker {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            types: HashSet::from([String::from("Option"), String::from("Result")]),
+            returns: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    // Nothing the program declared is kept when it does not check
+    pub fn check_program(&mut self, program: &Program) -> Result<(), Vec<TypeError>> {
+        let (globals, types) = (self.scopes[0].clone(), self.types.clone());
+        for statement in &program.statements {
+            self.check_statement(statement);
+        }
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        self.scopes = vec![globals];
+        self.types = types;
+        self.returns.clear();
+        Err(std::mem::take(&mut self.errors))
+    }
+
+    fn error(&mut self, position: Position, message: String) {
+        self.errors.push(TypeError { position, message });
+    }
+
+    fn bind(&mut self, name: &str, value: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.to_string(), value);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Type {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).cloned())
+            .unwrap_or(Type::Dynamic)
+    }
+
+    // Blocks share the environment they run in, so names bound inside a
+    // branch stay visible afterwards but may not have been bound at all
+    fn check_branch(&mut self, block: &BlockStatement) -> Type {
+        self.scopes.push(HashMap::new());
+        let result = self.check_block(block);
+        let scope = self.scopes.pop().unwrap_or_default();
+        for name in scope.keys() {
+            self.bind(name, Type::Dynamic);
+        }
+        result
+    }
+
+    fn check_block(&mut self, block: &BlockStatement) -> Type {
+        let mut result = Type::Null;
+        for statement in block.statements() {
+            result = self.check_statement(statement);
+        }
+        result
+    }
+
+    fn check_statement(&mut self, statement: &Statements) -> Type {
+        match statement {
+            Statements::LetStatement(stmt) => {
+                let name = stmt.name().map(|n| n.value().to_string());
+                let declared = stmt
+                    .annotation()
+                    .map(|annotation| self.resolve(annotation, stmt.position()));
+                // A function can call itself by name
+                if let (Some(name), Expressions::FunctionLiteral(literal)) = (&name, stmt.value()) {
+                    let signature = declared.clone().unwrap_or_else(|| self.signature(literal));
+                    self.bind(name, signature);
+                }
+                let actual = self.infer(stmt.value());
+                if let Some(declared) = &declared {
+                    if !compatible(declared, &actual) {
+                        self.error(
+                            stmt.position(),
+                            format!(
+                                "type mismatch in let {}: expected {}, found {}",
+                                stmt.pattern(),
+                                declared,
+                                actual
+                            ),
+                        );
+                    }
+                }
+                // A later `let` may rebind the name to anything, so only an
+                // annotation or a function's own signature is trusted
+                let bound = match (declared, stmt.value()) {
+                    (Some(declared), _) => declared,
+                    (None, Expressions::FunctionLiteral(_)) => actual,
+                    (None, _) => Type::Dynamic,
+                };
+                match name {
+                    Some(name) => self.bind(&name, bound),
+                    None => self.bind_pattern(stmt.pattern()),
+                }
+                Type::Dynamic
+            }
+            Statements::ReturnStatement(stmt) => {
+                let actual = self.infer(stmt.return_value());
+                if let Some(expected) = self.returns.last().cloned() {
+                    if !compatible(&expected, &actual) {
+                        self.error(
+                            stmt.position(),
+                            format!(
+                                "type mismatch in return value: expected {}, found {}",
+                                expected, actual
+                            ),
+                        );
+                    }
+                }
+                Type::Dynamic
+            }
+            Statements::ExpressionStatement(stmt) => self.infer(stmt.expression()),
+            Statements::BlockStatement(block) => self.check_block(block),
+            Statements::StructStatement(stmt) => {
+                self.types.insert(stmt.name().value().to_string());
+                self.bind(stmt.name().value(), Type::Dynamic);
+                Type::Dynamic
+            }
+            Statements::EnumStatement(stmt) => {
+                self.types.insert(stmt.name().value().to_string());
+                self.bind(stmt.name().value(), Type::Dynamic);
+                for (variant, _) in stmt.variants() {
+                    self.bind(variant.value(), Type::Dynamic);
+                }
+                Type::Dynamic
+            }
+            Statements::ImplStatement(stmt) => {
+                for (_, method) in stmt.methods() {
+                    self.infer_function(method);
+                }
+                Type::Dynamic
+            }
+            Statements::ImportStatement(stmt) => {
+                self.bind(stmt.alias().value(), Type::Dynamic);
+                Type::Dynamic
+            }
+            Statements::Empty => Type::Dynamic,
+        }
+    }
+
+    fn bind_pattern(&mut self, pattern: &Pattern) {
+        for name in pattern.bindings() {
+            self.bind(name.value(), Type::Dynamic);
+        }
+    }
+
+    fn resolve(&mut self, annotation: &TypeAnnotation, position: Position) -> Type {
+        match annotation {
+            TypeAnnotation::Named(name) => match name.as_str() {
+                "any" => Type::Dynamic,
+                "int" => Type::Integer,
+                "float" => Type::Float,
+                "bool" => Type::Boolean,
+                "string" => Type::String,
+                "char" => Type::Char,
+                "null" => Type::Null,
+                "hash" => Type::Hash,
+                "array" => Type::Array(Box::new(Type::Dynamic)),
+                _ if self.types.contains(name) => Type::Named(name.clone()),
+                _ => {
+                    self.error(position, format!("unknown type: {}", name));
+                    Type::Dynamic
+                }
+            },
+            TypeAnnotation::Array(element) => {
+                Type::Array(Box::new(self.resolve(element, position)))
+            }
+            TypeAnnotation::Function(parameters, result) => {
+                let parameters = parameters
+                    .iter()
+                    .map(|p| self.resolve(p, position))
+                    .collect();
+                Type::Function(Some(parameters), Box::new(self.resolve(result, position)))
+            }
+        }
+    }
+
+    // The type of a function literal from its annotations alone
+    fn signature(&mut self, literal: &FunctionLiteral) -> Type {
+        let position = literal.position();
+        let fixed = literal
+            .parameters()
+            .iter()
+            .all(|p| !p.is_rest() && p.default().is_none());
+        let parameters: Vec<Type> = literal
+            .parameters()
+            .iter()
+            .map(|p| match p.annotation() {
+                Some(annotation) => self.resolve(annotation, position),
+                None => Type::Dynamic,
+            })
+            .collect();
+        let result = match literal.return_type() {
+            Some(annotation) => self.resolve(annotation, position),
+            None => Type::Dynamic,
+        };
+        Type::Function(fixed.then_some(parameters), Box::new(result))
+    }
+
+    fn infer_function(&mut self, literal: &FunctionLiteral) -> Type {
+        let signature = self.signature(literal);
+        self.scopes.push(HashMap::new());
+        for param in literal.parameters() {
+            let declared = match param.annotation() {
+                Some(annotation) => self.resolve(annotation, literal.position()),
+                None => Type::Dynamic,
+            };
+            if let Some(default) = param.default() {
+                let actual = self.infer(default);
+                if !compatible(&declared, &actual) {
+                    self.error(
+                        literal.position(),
+                        format!(
+                            "type mismatch in default of {}: expected {}, found {}",
+                            param.pattern(),
+                            declared,
+                            actual
+                        ),
+                    );
+                }
+            }
+            match param.name() {
+                Some(name) => self.bind(name.value(), declared),
+                None => self.bind_pattern(param.pattern()),
+            }
+        }
+        let expected = match &signature {
+            Type::Function(_, result) => (**result).clone(),
+            _ => Type::Dynamic,
+        };
+        self.returns.push(expected.clone());
+        let actual = self.check_block(literal.body());
+        if !compatible(&expected, &actual) {
+            self.error(
+                literal.position(),
+                format!(
+                    "type mismatch in function result: expected {}, found {}",
+                    expected, actual
+                ),
+            );
+        }
+        self.returns.pop();
+        self.scopes.pop();
+        signature
+    }
+
+    fn infer(&mut self, expression: &Expressions) -> Type {
+        match expression {
+            Expressions::Identifier(ident) => self.lookup(ident.value()),
+            Expressions::BooleanLiteral(_) => Type::Boolean,
+            Expressions::IntegerLiteral(_) => Type::Integer,
+            Expressions::FloatLiteral(_) => Type::Float,
+            Expressions::StringLiteral(_) => Type::String,
+            Expressions::CharLiteral(_) => Type::Char,
+            Expressions::InterpolatedString(string) => {
+                for part in string.parts() {
+                    if let InterpolationPart::Expression(value) = part {
+                        self.infer(value);
+                    }
+                }
+                Type::String
+            }
+            Expressions::ArrayLiteral(array) => {
+                let mut element = None;
+                for value in array.elements() {
+                    let actual = match value {
+                        Expressions::SpreadExpression(_) => {
+                            self.infer(value);
+                            Type::Dynamic
+                        }
+                        _ => self.infer(value),
+                    };
+                    element = match element {
+                        None => Some(actual),
+                        Some(previous) if previous == actual => Some(previous),
+                        Some(_) => Some(Type::Dynamic),
+                    };
+                }
+                Type::Array(Box::new(element.unwrap_or(Type::Dynamic)))
+            }
+            Expressions::HashLiteral(hash) => {
+                for (key, value) in hash.pairs() {
+                    self.infer(key);
+                    self.infer(value);
+                }
+                Type::Hash
+            }
+            Expressions::PrefixExpression(prefix) => {
+                let right = self.infer(prefix.expression_right());
+                let result = match (prefix.operator(), &right) {
+                    ("!", _) => Some(Type::Boolean),
+                    (_, Type::Dynamic) => Some(Type::Dynamic),
+                    ("-", Type::Integer | Type::Float) | ("~", Type::Integer) => {
+                        Some(right.clone())
+                    }
+                    _ => None,
+                };
+                result.unwrap_or_else(|| {
+                    self.error(
+                        prefix.position(),
+                        format!("unknown operator: {}{}", prefix.operator(), right),
+                    );
+                    Type::Dynamic
+                })
+            }
+            Expressions::InfixExpression(infix) => {
+                let left = self.infer(infix.expression_left());
+                let right = self.infer(infix.expression_right());
+                match infix_type(infix.operator(), &left, &right) {
+                    Ok(result) => result,
+                    Err(message) => {
+                        self.error(infix.position(), message);
+                        Type::Dynamic
+                    }
+                }
+            }
+            Expressions::IfExpression(if_expression) => self.infer_if(if_expression),
+            Expressions::MatchExpression(match_expression) => {
+                self.infer(match_expression.subject());
+                for arm in match_expression.arms() {
+                    self.scopes.push(HashMap::new());
+                    self.bind_pattern(arm.pattern());
+                    if let Some(guard) = arm.guard() {
+                        self.infer(guard);
+                    }
+                    self.check_block(arm.body());
+                    let scope = self.scopes.pop().unwrap_or_default();
+                    for name in scope.keys() {
+                        self.bind(name, Type::Dynamic);
+                    }
+                }
+                Type::Dynamic
+            }
+            Expressions::CallExpression(call) => self.infer_call(call),
+            Expressions::IndexExpression(index) => {
+                let left = self.infer(index.left());
+                let position = self.infer(index.index());
+                match (left, position) {
+                    (Type::Array(element), Type::Integer) => *element,
+                    _ => Type::Dynamic,
+                }
+            }
+            Expressions::SpreadExpression(spread) => {
+                self.infer(spread.value());
+                Type::Dynamic
+            }
+            Expressions::FieldAccess(access) => {
+                self.infer(access.object());
+                Type::Dynamic
+            }
+            Expressions::TryExpression(try_expression) => {
+                self.infer(try_expression.value());
+                Type::Dynamic
+            }
+            Expressions::FunctionLiteral(literal) => self.infer_function(literal),
+            Expressions::StructLiteral(literal) => {
+                for (_, value) in literal.fields() {
+                    self.infer(value);
+                }
+                if let Some(base) = literal.base() {
+                    self.infer(base);
+                }
+                if self.types.contains(literal.name().value()) {
+                    Type::Named(literal.name().value().to_string())
+                } else {
+                    Type::Dynamic
+                }
+            }
+            Expressions::Empty => Type::Dynamic,
+        }
+    }
+
+    fn infer_if(&mut self, if_expression: &IfExpression) -> Type {
+        self.infer(if_expression.condition());
+        let consequence = self.check_branch(if_expression.consequence());
+        match if_expression.alternative() {
+            Some(alternative) => {
+                let alternative = self.check_branch(alternative);
+                if consequence == alternative {
+                    consequence
+                } else {
+                    Type::Dynamic
+                }
+            }
+            None => Type::Dynamic,
+        }
+    }
+
+    fn infer_call(&mut self, call: &CallExpression) -> Type {
+        let function = self.infer(call.function());
+        let mut arguments = Vec::new();
+        let mut spread = false;
+        for argument in call.arguments() {
+            spread |= matches!(argument, Expressions::SpreadExpression(_));
+            arguments.push(self.infer(argument));
+        }
+        for (_, argument) in call.named() {
+            self.infer(argument);
+        }
+        // Methods are looked up at runtime
+        if let Expressions::FieldAccess(_) = call.function() {
+            return Type::Dynamic;
+        }
+        match function {
+            Type::Function(parameters, result) => {
+                let parameters = match parameters {
+                    Some(parameters) if !spread && call.named().is_empty() => parameters,
+                    _ => return *result,
+                };
+                if parameters.len() != arguments.len() {
+                    self.error(
+                        call.position(),
+                        format!(
+                            "wrong number of arguments: expected {} but received {}",
+                            parameters.len(),
+                            arguments.len()
+                        ),
+                    );
+                    return *result;
+                }
+                for (i, (expected, actual)) in parameters.iter().zip(&arguments).enumerate() {
+                    if !compatible(expected, actual) {
+                        self.error(
+                            call.position(),
+                            format!(
+                                "type mismatch in argument {} of {}: expected {}, found {}",
+                                i + 1,
+                                call.function(),
+                                expected,
+                                actual
+                            ),
+                        );
+                    }
+                }
+                *result
+            }
+            Type::Dynamic | Type::Named(_) => Type::Dynamic,
+            other => {
+                self.error(call.position(), format!("not a function: {}", other));
+                Type::Dynamic
+            }
+        }
+    }
+}
+
+// Dynamic fits everywhere, otherwise the types have to agree
+fn compatible(expected: &Type, actual: &Type) -> bool {
+    match (expected, actual) {
+        (Type::Dynamic, _) | (_, Type::Dynamic) => true,
+        (Type::Array(expected), Type::Array(actual)) => compatible(expected, actual),
+        (Type::Function(expected_params, expected), Type::Function(actual_params, actual)) => {
+            let parameters = match (expected_params, actual_params) {
+                (Some(e), Some(a)) => {
+                    e.len() == a.len() && e.iter().zip(a).all(|(e, a)| compatible(e, a))
+                }
+                _ => true,
+            };
+            parameters && compatible(expected, actual)
+        }
+        _ => expected == actual,
+    }
+}
+
+// The result of an operator, following what the evaluator accepts
+fn infix_type(operator: &str, left: &Type, right: &Type) -> Result<Type, String> {
+    let comparison = matches!(operator, "<" | ">" | "<=" | ">=" | "==" | "!=");
+    let equality = matches!(operator, "==" | "!=");
+    let arithmetic = matches!(operator, "+" | "-" | "*" | "/" | "%" | "**");
+    let bitwise = matches!(operator, "&" | "|" | "^" | "<<" | ">>");
+    let result = match (left, right) {
+        (Type::Dynamic, _) | (_, Type::Dynamic) if comparison => Some(Type::Boolean),
+        (Type::Dynamic, _) | (_, Type::Dynamic) => Some(Type::Dynamic),
+        _ if comparison
+            && matches!(left, Type::Integer | Type::Float)
+            && matches!(right, Type::Integer | Type::Float) =>
+        {
+            Some(Type::Boolean)
+        }
+        // Overflow and negative exponents leave the machine integers
+        (Type::Integer, Type::Integer) if operator == "**" => Some(Type::Dynamic),
+        (Type::Integer, Type::Integer) if arithmetic || bitwise => Some(Type::Integer),
+        (Type::Integer | Type::Float, Type::Integer | Type::Float) if arithmetic => {
+            Some(Type::Float)
+        }
+        (Type::String, Type::String) | (Type::Char, Type::Char) if comparison => {
+            Some(Type::Boolean)
+        }
+        (Type::String | Type::Char, Type::String | Type::Char) if operator == "+" => {
+            Some(Type::String)
+        }
+        (Type::Boolean, Type::Boolean) | (Type::Null, Type::Null) if equality => {
+            Some(Type::Boolean)
+        }
+        // Values of different types are never equal
+        _ if equality && left != right => Some(Type::Boolean),
+        // Equality of structs, enums and collections is decided at runtime
+        (Type::Array(_) | Type::Hash | Type::Function(..) | Type::Named(_), _) => {
+            Some(Type::Dynamic)
+        }
+        _ => None,
+    };
+    match result {
+        Some(result) => Ok(result),
+        None if std::mem::discriminant(left) != std::mem::discriminant(right) => {
+            Err(format!("type mismatch: {} {} {}", left, operator, right))
+        }
+        None => Err(format!("unknown operator: {} {} {}", left, operator, right)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lexer::Lexer, parser::Parser};
+
+    fn check(input: &str) -> Result<(), Vec<String>> {
+        let program = Parser::new(Lexer::new(input.into()))
+            .parse_program()
+            .expect("Program did not parse properly");
+        typecheck(&program).map_err(|errors| errors.iter().map(|e| e.to_string()).collect())
+    }
+
+    #[test]
+    fn test_well_typed_programs() {
+        let inputs = [
+            "let x: int = 5; let y: float = x * 2.5; y",
+            "let add = fn(a: int, b: int) -> int { a + b }; add(1, 2)",
+            "let fact = fn(n: int) -> int { if (n < 2) { return 1; } n * fact(n - 1) }; fact(5)",
+            "let xs: [int] = [1, 2, 3]; let first: int = xs[0];",
+            "let apply = fn(f: fn(int) -> int, x: int) -> int { f(x) }; apply(fn(x: int) -> int { x + 1 }, 1)",
+            "let greet = fn(name: string, punct: string = \"!\") -> string { \"hi \" + name + punct }; greet(\"a\")",
+            "let untyped = fn(x) { x }; let n: int = untyped(\"s\");",
+            "struct Point { x, y } let p: Point = Point { x: 1, y: 2 };",
+            "let o: Option = Some(1); let v: any = 1; let s: string = v;",
+            "let [a, b] = [1, \"x\"]; a + b",
+            "1 == \"one\"",
+            "let x = 1; let g = fn() { x + \"a\" }; let x = \"s\";",
+            crate::prelude::PRELUDE,
+        ];
+        for input in inputs {
+            assert_eq!(Ok(()), check(input), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_type_errors() {
+        let inputs = [
+            (
+                "let x: int = \"five\";",
+                "1:1: type mismatch in let x: expected INTEGER, found STRING",
+            ),
+            (
+                "let f = fn(a: int) { a };\nf(true)",
+                "2:2: type mismatch in argument 1 of f: expected INTEGER, found BOOLEAN",
+            ),
+            (
+                "let f = fn(a: int, b: int) { a }; f(1)",
+                "1:36: wrong number of arguments: expected 2 but received 1",
+            ),
+            (
+                "let f = fn() -> string { return 1; };",
+                "1:26: type mismatch in return value: expected STRING, found INTEGER",
+            ),
+            (
+                "let f = fn() -> bool { 1 };",
+                "1:9: type mismatch in function result: expected BOOLEAN, found INTEGER",
+            ),
+            (
+                "let f = fn(a: int = \"x\") { a };",
+                "1:9: type mismatch in default of a: expected INTEGER, found STRING",
+            ),
+            ("  5 + true", "1:5: type mismatch: INTEGER + BOOLEAN"),
+            ("true - false", "1:6: unknown operator: BOOLEAN - BOOLEAN"),
+            ("-\"s\"", "1:1: unknown operator: -STRING"),
+            ("let x: int = 1; x(2)", "1:18: not a function: INTEGER"),
+            ("let x: number = 1;", "1:1: unknown type: number"),
+            (
+                "let xs: [string] = [1, 2];",
+                "1:1: type mismatch in let xs: expected [STRING], found [INTEGER]",
+            ),
+        ];
+        for (input, expected) in inputs {
+            assert_eq!(
+                Err(vec![expected.to_string()]),
+                check(input),
+                "input: {}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_checker_keeps_declarations() {
+        let parse = |input: &str| {
+            Parser::new(Lexer::new(input.into()))
+                .parse_program()
+                .unwrap()
+        };
+        let mut checker = Checker::new();
+        assert!(checker.check_program(&parse("let x: int = 1;")).is_ok());
+        assert!(checker.check_program(&parse("let y: string = x;")).is_err());
+        // A program that fails leaves no bindings behind
+        assert!(checker
+            .check_program(&parse("let z: int = 1; let w: int = \"\";"))
+            .is_err());
+        assert!(checker
+            .check_program(&parse("let z: string = \"\";"))
+            .is_ok());
+    }
+}